@@ -0,0 +1,52 @@
+//! # `no_std` 构建检查
+//!
+//! 以 `#![no_std]` 静态库链接 `sm-algorithm`，确认关闭 `std` 特性后各模块可在
+//! `thumbv7em-none-eabihf` 等嵌入式目标上编译
+//!
+//! 仅用于构建检查，分配器不分配任何内存，不应实际运行
+
+#![no_std]
+
+extern crate alloc;
+
+use {
+  core::{
+    alloc::{GlobalAlloc, Layout},
+    panic::PanicInfo,
+    ptr
+  },
+  sm_algorithm::{sm_2, sm_3, sm_4}
+};
+
+/// # 不分配内存的全局分配器
+struct NullAllocator;
+
+unsafe impl GlobalAlloc for NullAllocator {
+  unsafe fn alloc(&self, _layout: Layout) -> *mut u8 {
+    ptr::null_mut()
+  }
+
+  unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {
+  }
+}
+
+#[global_allocator]
+static ALLOCATOR: NullAllocator = NullAllocator;
+
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+  loop {}
+}
+
+/// # 调用 SM2、SM3、SM4 的入口，使其实现参与链接
+#[no_mangle]
+pub extern "C" fn sm_algorithm_smoke() -> u8 {
+  let digest = sm_3::hash_bytes(b"abc");
+  let block = sm_4::encrypt(&[0; 16], &[0; 16]);
+  let verified = match sm_2::public_key_bytes(&digest) {
+    Ok(public_key) => sm_2::verify_bytes(&public_key, b"abc", &[]),
+    Err(_) => false
+  };
+
+  digest[0] ^ block[0] ^ verified as u8
+}
@@ -0,0 +1,131 @@
+//! # DER 编解码
+//!
+//! 解析 SM2 密钥和编解码 SM2 签名所需的最小 DER（ASN.1
+//! 可辨别编码规则）实现，仅支持定长编码
+
+use {crate::Error, alloc::vec::Vec};
+
+/// # DER 标签：INTEGER
+pub(crate) const TAG_INTEGER: u8 = 0x02;
+/// # DER 标签：BIT STRING
+pub(crate) const TAG_BIT_STRING: u8 = 0x03;
+/// # DER 标签：OCTET STRING
+pub(crate) const TAG_OCTET_STRING: u8 = 0x04;
+/// # DER 标签：OBJECT IDENTIFIER
+pub(crate) const TAG_OID: u8 = 0x06;
+/// # DER 标签：SEQUENCE
+pub(crate) const TAG_SEQUENCE: u8 = 0x30;
+/// # DER 标签：上下文相关的构造类型 [0]
+pub(crate) const TAG_CONTEXT_0: u8 = 0xa0;
+/// # DER 标签：上下文相关的构造类型 [1]
+pub(crate) const TAG_CONTEXT_1: u8 = 0xa1;
+
+/// # OID：id-ecPublicKey（1.2.840.10045.2.1）
+pub(crate) const OID_EC_PUBLIC_KEY: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01];
+/// # OID：SM2 推荐曲线（1.2.156.10197.1.301）
+pub(crate) const OID_SM2: &[u8] = &[0x2a, 0x81, 0x1c, 0xcf, 0x55, 0x01, 0x82, 0x2d];
+
+/// # DER 读取器
+///
+/// 依次读取一段 DER 编码中的 TLV（标签-长度-值）
+///
+/// ## 成员
+///
+/// * `data` - 尚未读取的数据
+///
+/// ## 构造方法
+///
+/// * `DerReader::new(data)` - 从字节序列创建
+///
+/// ## 方法
+///
+/// * `is_empty(&self) -> bool` - 是否已读取完毕
+/// * `peek_tag(&self) -> Option<u8>` - 查看下一个 TLV 的标签
+/// * `read(&mut self) -> Result<(u8, &'a [u8]), Error>` - 读取一个 TLV
+/// * `expect(&mut self, tag: u8) -> Result<&'a [u8], Error>` -
+///   读取一个指定标签的 TLV，返回其值
+pub(crate) struct DerReader<'a> {
+  data: &'a [u8]
+}
+
+impl<'a> DerReader<'a> {
+  pub(crate) fn new(data: &'a [u8]) -> Self {
+    Self { data }
+  }
+
+  pub(crate) fn is_empty(&self) -> bool {
+    self.data.is_empty()
+  }
+
+  pub(crate) fn peek_tag(&self) -> Option<u8> {
+    self.data.first().copied()
+  }
+
+  pub(crate) fn read(&mut self) -> Result<(u8, &'a [u8]), Error> {
+    let [tag, first, rest @ ..] = self.data else {
+      return Err(Error::InvalidDer);
+    };
+
+    // 短格式长度直接存储；长格式中低 7 位为长度字段的字节数
+    let (len, rest) = match *first {
+      len @ 0x00 ..= 0x7f => (len as usize, rest),
+      0x81 ..= 0x84 => {
+        let count = (*first & 0x7f) as usize;
+
+        if rest.len() < count {
+          return Err(Error::InvalidDer);
+        }
+
+        let len = rest[.. count].iter().fold(0usize, |acc, b| acc << 8 | *b as usize);
+
+        // DER 要求最短编码：长格式不得有前导 0，也不得用于小于 0x80 的长度
+        if rest[0] == 0 || len < 0x80 {
+          return Err(Error::InvalidDer);
+        }
+
+        (len, &rest[count ..])
+      },
+      _ => return Err(Error::InvalidDer)
+    };
+
+    if rest.len() < len {
+      return Err(Error::InvalidDer);
+    }
+
+    let (value, rest) = rest.split_at(len);
+    self.data = rest;
+
+    Ok((*tag, value))
+  }
+
+  pub(crate) fn expect(&mut self, tag: u8) -> Result<&'a [u8], Error> {
+    match self.read()? {
+      (actual, value) if actual == tag => Ok(value),
+      _ => Err(Error::InvalidDer)
+    }
+  }
+}
+
+/// # 写入一个 TLV
+///
+/// ## 参数
+///
+/// * `out` - 输出缓冲区
+/// * `tag` - 标签
+/// * `value` - 值，长度不超过 2^32 - 1
+pub(crate) fn write_tlv(out: &mut Vec<u8>, tag: u8, value: &[u8]) {
+  out.push(tag);
+
+  // 长度小于 0x80 时使用短格式，否则使用最短的长格式
+  if value.len() < 0x80 {
+    out.push(value.len() as u8);
+  } else {
+    let len_bytes = (value.len() as u32).to_be_bytes();
+    let skip = len_bytes.iter().take_while(|b| **b == 0).count();
+
+    out.push(0x80 | (4 - skip) as u8);
+    out.extend_from_slice(&len_bytes[skip ..]);
+  }
+
+  out.extend_from_slice(value);
+}
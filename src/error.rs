@@ -0,0 +1,87 @@
+//! # 错误类型
+//!
+//! 本 crate 中可失败操作统一使用的错误类型
+
+use core::{error, fmt};
+
+/// # 错误
+///
+/// ## 变体
+///
+/// * `InvalidLength` - 输入长度不符合要求
+/// * `OutOfRange` - 下标或区间越界
+/// * `InvalidPoint` - 点的编码无效，或运算得到无穷远点
+/// * `NotOnCurve` - 点不在给定的椭圆曲线上
+/// * `InvalidNonce` - 随机数 k 不在 [1, n - 1] 范围内，或导致签名值 r、s 无效
+/// * `InvalidPrivateKey` - 私钥无效
+/// * `InvalidPublicKey` - 公钥无效
+/// * `InvalidCipherText` - 密文格式无效
+/// * `DigestMismatch` - 解密时杂凑值 C3 校验失败
+/// * `ValidatorMismatch` - 密钥交换时验证参数不匹配
+/// * `KdfAllZero` - 密钥派生函数的输出全为 0
+/// * `InvalidPadding` - PKCS#7 填充无效
+/// * `InvalidDer` - DER 编码无效
+/// * `UnsupportedCurve` - 不支持的曲线
+/// * `UnsupportedAlgorithm` - 不支持的密钥算法
+/// * `UnsupportedEncoding` - 不支持的点编码或私钥版本
+/// * `IncompatibleParams` - 参与运算的点使用了不同的曲线参数
+/// * `PublicKeyMismatch` - 私钥与附带的公钥不匹配
+///
+/// ## 实现特征
+///
+/// * `Clone`
+/// * `Copy`
+/// * `PartialEq`
+/// * `Eq`
+/// * `Debug`
+/// * `Display`
+/// * `Error`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Error {
+  InvalidLength,
+  OutOfRange,
+  InvalidPoint,
+  NotOnCurve,
+  InvalidNonce,
+  InvalidPrivateKey,
+  InvalidPublicKey,
+  InvalidCipherText,
+  DigestMismatch,
+  ValidatorMismatch,
+  KdfAllZero,
+  InvalidPadding,
+  InvalidDer,
+  UnsupportedCurve,
+  UnsupportedAlgorithm,
+  UnsupportedEncoding,
+  IncompatibleParams,
+  PublicKeyMismatch
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Error::InvalidLength => write!(f, "Invalid length"),
+      Error::OutOfRange => write!(f, "Index out of range"),
+      Error::InvalidPoint => write!(f, "Invalid point"),
+      Error::NotOnCurve => write!(f, "Point not on curve"),
+      Error::InvalidNonce => write!(f, "Invalid k"),
+      Error::InvalidPrivateKey => write!(f, "Invalid private key"),
+      Error::InvalidPublicKey => write!(f, "Invalid public key"),
+      Error::InvalidCipherText => write!(f, "Invalid cipher text"),
+      Error::DigestMismatch => write!(f, "Digest mismatch"),
+      Error::ValidatorMismatch => write!(f, "Validator mismatch"),
+      Error::KdfAllZero => write!(f, "KDF output is all zero"),
+      Error::InvalidPadding => write!(f, "Invalid padding"),
+      Error::InvalidDer => write!(f, "Invalid DER"),
+      Error::UnsupportedCurve => write!(f, "Unsupported curve"),
+      Error::UnsupportedAlgorithm => write!(f, "Unsupported key algorithm"),
+      Error::UnsupportedEncoding => write!(f, "Unsupported encoding"),
+      Error::IncompatibleParams => write!(f, "Incompatible curve parameters"),
+      Error::PublicKeyMismatch => write!(f, "Public key mismatch")
+    }
+  }
+}
+
+impl error::Error for Error {
+}
@@ -1,4 +1,19 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod der;
+pub mod error;
 pub mod math;
+pub mod prelude;
 pub mod sm_2;
 pub mod sm_3;
 pub mod sm_4;
+
+#[cfg(feature = "rustcrypto")]
+pub mod rustcrypto;
+
+#[cfg(feature = "serde")]
+pub mod serialization;
+
+pub use error::Error;
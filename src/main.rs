@@ -1,3 +1,7 @@
+use sm_algorithm::sm_3::hash_bytes;
+
 fn main() {
-  println!("Hello, world!");
+  let digest = hash_bytes(b"abc");
+
+  println!("SM3(\"abc\") = {}", digest.iter().map(|b| format!("{:02x}", b)).collect::<String>());
 }
@@ -1,3 +1,27 @@
+#[cfg(feature = "std")]
+use std::io::{self, Read, Write};
+use {
+  super::u256::U256,
+  crate::Error,
+  alloc::{vec, vec::Vec},
+  core::cmp
+};
+
+/// # 字节序
+///
+/// 用于指定整数序列化为字节时的字节序
+///
+/// ## 变体
+///
+/// * `BigEndian` - 大端序，GB/T 32918 等标准使用的字节序，为默认值
+/// * `LittleEndian` - 小端序
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ByteOrder {
+  #[default]
+  BigEndian,
+  LittleEndian
+}
+
 /// # 比特序列
 ///
 /// 字节存储的比特序列，如果最后一个字节未填满，使用 `last_byte_len` 记录长度。
@@ -15,7 +39,7 @@
 ///   从字节序列和最后一个字节的长度构造
 /// * `new_empty() -> Self` - 创建一个空序列
 /// * `with_bytes(bytes: &[u8]) -> Self` - 从字节序列构造
-/// * `try_with_bits(bytes: &[u8], size: u64) -> Result<Self, String>` -
+/// * `try_with_bits(bytes: &[u8], size: u64) -> Result<Self, Error>` -
 ///   从字节序列和长度构造
 ///
 /// ## 实现特征
@@ -23,8 +47,12 @@
 /// * `Clone`
 /// * `From<&[u8]>`
 /// * `From<BitSequence> -> Vec<u8>`
-/// * `PartialEq`
+/// * `PartialEq` - 仅比较有效位
 /// * `Eq`
+/// * `PartialOrd`
+/// * `Ord` - 先比较长度，长度相同时按大端序逐位比较有效位
+/// * `Serialize` - 启用 `serde` 特性时可用
+/// * `Deserialize` - 启用 `serde` 特性时可用
 ///
 /// ## 方法
 ///
@@ -33,12 +61,38 @@
 /// * `get_last_byte_len(&self) -> u8` - 获取最后一个字节的长度
 /// * `append_bytes(&mut self, bytes: &[u8])` - 追加字节序列
 /// * `append_bits(&mut self, bits: &Self)` - 追加比特序列
+/// * `append_bit(&mut self, bit: bool)` - 追加单个比特
+/// * `get_bit(&self, index: u64) -> Option<bool>` - 获取第 `index`
+///   位，按大端序计数，越界时返回 `None`
+/// * `set_bit(&mut self, index: u64, value: bool) -> Result<(), Error>`
+///   - 设置第 `index` 位，越界时返回错误
 /// * `into_le_bytes(&self) -> Vec<u8>` - 转换为小端序字节序列
 /// * `len(&self) -> u64` - 获取比特序列的长度
-/// * `xor(&self, other: &Self) -> Result<Self, &'static str>` - 异或运算
-/// * `xor_inplace(&mut self, other: &Self) -> Result<(), &'static str>` -
-///   就地异或运算
-/// * `slice(&self, start: u64, end: u64) -> Result<Self, &'static str>` - 切片
+/// * `xor(&self, other: &Self) -> Result<Self, Error>` - 异或运算
+/// * `xor_inplace(&mut self, other: &Self) -> Result<(), Error>` - 就地异或运算
+/// * `slice(&self, start: u64, end: u64) -> Result<Self, Error>` - 切片
+/// * `split_at(&self, mid: u64) -> Result<(Self, Self), Error>` - 在第 `mid`
+///   位处拆分为前后两个序列
+/// * `pop_front_bits(&mut self, n: u64) -> Result<Self, Error>` - 移除并返回前
+///   `n` 位，保留其余部分
+/// * `first_bytes(&self, n: usize) -> Option<&[u8]>` - 获取前 `n` 个字节，不足
+///   `n` 个字节时返回 `None`
+/// * `last_bytes(&self, n: usize) -> Option<&[u8]>` - 获取后 `n` 个字节，不足
+///   `n` 个字节时返回 `None`，最后一个字节未填满时包含其无效位
+/// * `to_u256_chunks(&self) -> Result<Vec<U256>, Error>` - 按 256 位分块转换为
+///   `U256` 序列
+/// * `shl_bits(&mut self, n: u64)` - 有效位整体左移，长度不变
+/// * `shr_bits(&mut self, n: u64)` - 有效位整体右移，长度不变
+/// * `reverse_bits_in_bytes(&self) -> Self` - 反转每个字节内的比特顺序
+/// * `write_to<W: Write>(&self, w: &mut W) -> io::Result<()>` - 以帧格式写出，
+///   需启用 `std` 特性
+/// * `read_from<R: Read>(r: &mut R) -> io::Result<Self>` -
+///   读取帧格式的比特序列，需启用 `std` 特性
+///
+/// ## 帧格式
+///
+/// 8 字节大端序的比特长度，随后为按大端序存储的字节序列，最后一个字节的无效位为
+/// 0
 #[derive(Clone)]
 pub struct BitSequence {
   bytes: Vec<u8>,
@@ -58,19 +112,19 @@ impl BitSequence {
     Self { bytes: bytes.to_vec(), last_byte_len: 0 }
   }
 
-  pub fn try_with_bits(bytes: &[u8], size: u64) -> Result<Self, String> {
+  pub fn try_with_bits(bytes: &[u8], size: u64) -> Result<Self, Error> {
     let lower_bound: Result<u64, _> = ((bytes.len() - 1) * 8 + 1).try_into();
     let upper_bound: Result<u64, _> = (bytes.len() * 8).try_into();
 
     if lower_bound.is_err() || upper_bound.is_err() {
-      return Err("Invalid input size".to_string().into());
+      return Err(Error::InvalidLength);
     }
 
     let lower_bound = lower_bound.unwrap();
     let upper_bound = upper_bound.unwrap();
 
     if size < lower_bound || size > upper_bound {
-      return Err("Invalid input size".to_string().into());
+      return Err(Error::InvalidLength);
     }
 
     Ok(Self { bytes: bytes.to_vec(), last_byte_len: (size % 8) as u8 })
@@ -95,6 +149,9 @@ impl BitSequence {
       let l_shift = 8 - self.last_byte_len;
       let r_shift = self.last_byte_len;
 
+      // 清除最后一个字节中的无效位，避免与追加的比特混合
+      *self.bytes.last_mut().unwrap() &= 0xff << l_shift;
+
       for byte in bytes {
         *self.bytes.last_mut().unwrap() |= byte >> r_shift;
         self.bytes.push(byte << l_shift);
@@ -116,21 +173,70 @@ impl BitSequence {
       self.append_bytes(&bits.bytes[.. bits.bytes.len() - 1]);
     }
 
-    let last_byte = bits.bytes.last().unwrap();
+    // 仅保留最后一个字节中的有效位
+    let last_byte = bits.bytes.last().unwrap() & (0xff << (8 - bits.last_byte_len));
     let tot = self.last_byte_len + bits.last_byte_len;
 
-    if tot <= 8 {
-      *self.bytes.last_mut().unwrap() |= last_byte >> self.last_byte_len;
-      self.last_byte_len = tot % 8;
+    if self.last_byte_len == 0 {
+      // 自身按字节对齐时，有效位直接作为新的最后一个字节
+      self.bytes.push(last_byte);
+      self.last_byte_len = bits.last_byte_len;
     } else {
       let l_shift = 8 - self.last_byte_len;
+      let last = self.bytes.last_mut().unwrap();
+
+      *last = *last & (0xff << l_shift) | last_byte >> self.last_byte_len;
 
-      *self.bytes.last_mut().unwrap() |= last_byte >> self.last_byte_len;
-      self.bytes.push(last_byte << l_shift);
-      self.last_byte_len = tot - 8;
+      if tot > 8 {
+        self.bytes.push(last_byte << l_shift);
+      }
+
+      self.last_byte_len = tot % 8;
     }
   }
 
+  pub fn append_bit(&mut self, bit: bool) {
+    let index = self.len();
+
+    if self.last_byte_len == 0 {
+      self.bytes.push(0);
+    }
+
+    self.last_byte_len = (self.last_byte_len + 1) % 8;
+
+    // 新增的比特一定在范围内
+    self.set_bit(index, bit).unwrap();
+  }
+
+  pub fn get_bit(&self, index: u64) -> Option<bool> {
+    if index >= self.len() {
+      return None;
+    }
+
+    Some(self.bytes[(index / 8) as usize] >> (7 - index % 8) & 1 == 1)
+  }
+
+  pub fn set_bit(&mut self, index: u64, value: bool) -> Result<(), Error> {
+    if index >= self.len() {
+      return Err(Error::OutOfRange);
+    }
+
+    let mask = 0x80 >> (index % 8);
+    let byte = &mut self.bytes[(index / 8) as usize];
+
+    if value {
+      *byte |= mask;
+    } else {
+      *byte &= !mask;
+    }
+
+    Ok(())
+  }
+
+  pub fn bits(&self) -> BitIter<'_> {
+    BitIter { sequence: self, index: 0 }
+  }
+
   pub fn into_le_bytes(&self) -> Vec<u8> {
     if self.bytes.len() == 0 {
       return vec![];
@@ -146,12 +252,20 @@ impl BitSequence {
   }
 
   pub fn len(&self) -> u64 {
-    self.bytes.len() as u64 * 8 - 8 + self.last_byte_len as u64
+    if self.bytes.is_empty() {
+      return 0;
+    }
+
+    // last_byte_len 为 0 时，最后一个字节已填满
+    match self.last_byte_len {
+      0 => self.bytes.len() as u64 * 8,
+      last_byte_len => self.bytes.len() as u64 * 8 - 8 + last_byte_len as u64
+    }
   }
 
-  pub fn xor(&self, other: &Self) -> Result<Self, &'static str> {
+  pub fn xor(&self, other: &Self) -> Result<Self, Error> {
     if self.len() != other.len() {
-      return Err("Lengths of sequences to XOR must be equal");
+      return Err(Error::InvalidLength);
     }
 
     let mut result = self.clone();
@@ -163,9 +277,9 @@ impl BitSequence {
     Ok(result)
   }
 
-  pub fn xor_inplace(&mut self, other: &Self) -> Result<(), &'static str> {
+  pub fn xor_inplace(&mut self, other: &Self) -> Result<(), Error> {
     if self.len() != other.len() {
-      return Err("Lengths of sequences to XOR must be equal");
+      return Err(Error::InvalidLength);
     }
 
     for i in 0 .. self.bytes.len() {
@@ -175,44 +289,186 @@ impl BitSequence {
     Ok(())
   }
 
-  pub fn slice(&self, start: u64, end: u64) -> Result<Self, &'static str> {
-    if start > end || end >= self.len() {
-      return Err("Invalid slice");
+  pub fn slice(&self, start: u64, end: u64) -> Result<Self, Error> {
+    // 半开区间 [start, end)，允许 end == len 以取出序列尾部
+    if start > end || end > self.len() {
+      return Err(Error::OutOfRange);
     }
 
-    let mut current_byte = start / 8;
-    let byte_pos = start % 8;
-    let mut bits_remaining = end - start;
+    Ok(self.extract_bits(start, end))
+  }
 
-    let mut bytes = Vec::new();
+  pub fn split_at(&self, mid: u64) -> Result<(Self, Self), Error> {
+    if mid > self.len() {
+      return Err(Error::OutOfRange);
+    }
 
-    while bits_remaining >= 8 {
-      let mut byte = self.bytes[current_byte as usize] << byte_pos;
-      byte |= self.bytes[current_byte as usize + 1] >> (8 - byte_pos);
+    Ok((self.extract_bits(0, mid), self.extract_bits(mid, self.len())))
+  }
 
-      bytes.push(byte);
-      current_byte += 1;
-      bits_remaining -= 8;
+  pub fn pop_front_bits(&mut self, n: u64) -> Result<Self, Error> {
+    let (front, rest) = self.split_at(n)?;
+    *self = rest;
+
+    Ok(front)
+  }
+
+  /// # 提取 [`start`, `end`) 范围内的比特
+  ///
+  /// 调用者须保证 `start <= end <= self.len()`
+  fn extract_bits(&self, start: u64, end: u64) -> Self {
+    let offset = (start / 8) as usize;
+    let shift = (start % 8) as u32;
+    let len = end - start;
+
+    let bytes = (0 .. len.div_ceil(8) as usize)
+      .map(|i| {
+        let high = self.bytes[offset + i] << shift;
+        let low = match self.bytes.get(offset + i + 1) {
+          Some(next) if shift != 0 => next >> (8 - shift),
+          _ => 0
+        };
+
+        high | low
+      })
+      .collect();
+
+    let mut result = Self { bytes, last_byte_len: (len % 8) as u8 };
+    result.clear_invalid_bits();
+
+    result
+  }
+
+  pub fn first_bytes(&self, n: usize) -> Option<&[u8]> {
+    self.bytes.get(.. n)
+  }
+
+  pub fn last_bytes(&self, n: usize) -> Option<&[u8]> {
+    self.bytes.len().checked_sub(n).map(|start| &self.bytes[start ..])
+  }
+
+  /// # 按 256 位分块转换为 `U256` 序列
+  ///
+  /// 每个分块按大端序解释，即分块的第一个比特为 `U256`
+  /// 的最高位；分块按在序列中的顺序排列
+  ///
+  /// ## 返回值
+  ///
+  /// * `Result<Vec<U256>, Error>` - 转换结果，长度不是 256 的整数倍时返回错误
+  pub fn to_u256_chunks(&self) -> Result<Vec<U256>, Error> {
+    if !self.len().is_multiple_of(256) {
+      return Err(Error::InvalidLength);
+    }
+
+    Ok(
+      self
+        .bytes
+        .chunks_exact(32)
+        .map(|chunk| U256::from_be_bytes(chunk.try_into().unwrap()))
+        .collect()
+    )
+  }
+
+  pub fn shl_bits(&mut self, n: u64) {
+    self.clear_invalid_bits();
+
+    let byte_shift = (n / 8).min(self.bytes.len() as u64) as usize;
+    let bit_shift = (n % 8) as u32;
+    let len = self.bytes.len();
+
+    // 高位移出，低位补 0
+    for i in 0 .. len {
+      let high = self.bytes.get(i + byte_shift).copied().unwrap_or(0);
+      let low = self.bytes.get(i + byte_shift + 1).copied().unwrap_or(0);
+
+      self.bytes[i] =
+        if bit_shift == 0 { high } else { high << bit_shift | low >> (8 - bit_shift) };
+    }
+
+    self.clear_invalid_bits();
+  }
+
+  pub fn shr_bits(&mut self, n: u64) {
+    self.clear_invalid_bits();
+
+    let byte_shift = (n / 8).min(self.bytes.len() as u64) as usize;
+    let bit_shift = (n % 8) as u32;
+
+    // 低位移出，高位补 0；从后向前处理，避免覆盖尚未读取的字节
+    for i in (0 .. self.bytes.len()).rev() {
+      let low = if i >= byte_shift { self.bytes[i - byte_shift] } else { 0 };
+      let high = if i > byte_shift { self.bytes[i - byte_shift - 1] } else { 0 };
+
+      self.bytes[i] = if bit_shift == 0 { low } else { low >> bit_shift | high << (8 - bit_shift) };
+    }
+
+    self.clear_invalid_bits();
+  }
+
+  pub fn reverse_bits_in_bytes(&self) -> Self {
+    let mut result = self.clone();
+    result.clear_invalid_bits();
+
+    for byte in result.bytes.iter_mut() {
+      *byte = byte.reverse_bits();
     }
 
-    if bits_remaining > 0 {
-      let mut byte = if 8 - byte_pos <= bits_remaining {
-        bits_remaining -= 8 - byte_pos;
-        self.bytes[current_byte as usize] << byte_pos
-      } else {
-        let temp = 8 - bits_remaining;
-        bits_remaining = 0;
-        self.bytes[current_byte as usize] << temp
-      };
+    // 最后一个字节未填满时，只反转其中的有效位，并保持有效位在高位
+    if self.last_byte_len != 0 {
+      if let Some(last) = result.bytes.last_mut() {
+        *last <<= 8 - self.last_byte_len;
+      }
+    }
+
+    result
+  }
 
-      if bits_remaining > 0 {
-        byte |= self.bytes[current_byte as usize + 1] >> (8 - bits_remaining);
+  /// # 清除最后一个字节中的无效位
+  fn clear_invalid_bits(&mut self) {
+    if self.last_byte_len != 0 {
+      if let Some(last) = self.bytes.last_mut() {
+        *last &= 0xff << (8 - self.last_byte_len);
       }
+    }
+  }
+
+  #[cfg(feature = "std")]
+  pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+    w.write_all(&self.len().to_be_bytes())?;
+
+    if let Some((last, rest)) = self.bytes.split_last() {
+      let mask = if self.last_byte_len == 0 { 0xff } else { 0xff << (8 - self.last_byte_len) };
+
+      w.write_all(rest)?;
+      w.write_all(&[last & mask])?;
+    }
+
+    Ok(())
+  }
+
+  #[cfg(feature = "std")]
+  pub fn read_from<R: Read>(r: &mut R) -> io::Result<Self> {
+    let mut len_bytes = [0u8; 8];
+    r.read_exact(&mut len_bytes)?;
+
+    let len = u64::from_be_bytes(len_bytes);
+    let byte_len = len.div_ceil(8);
+
+    // 按实际读到的数据增长，避免按不可信的长度预先分配内存
+    let mut bytes = Vec::new();
+    r.take(byte_len).read_to_end(&mut bytes)?;
 
-      bytes.push(byte);
+    if bytes.len() as u64 != byte_len {
+      return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "Truncated bit sequence"));
     }
 
-    Ok(BitSequence { bytes, last_byte_len: ((end - start) % 8) as u8 })
+    let last_byte_len = (len % 8) as u8;
+
+    if last_byte_len != 0 {
+      *bytes.last_mut().unwrap() &= 0xff << (8 - last_byte_len);
+    }
+
+    Ok(Self { bytes, last_byte_len })
   }
 }
 
@@ -230,11 +486,361 @@ impl From<BitSequence> for Vec<u8> {
 
 impl PartialEq for BitSequence {
   fn eq(&self, other: &Self) -> bool {
-    self.last_byte_len == other.last_byte_len
-      && self.bytes.len() == other.bytes.len()
-      && self.bytes == other.bytes
+    self.cmp(other) == cmp::Ordering::Equal
   }
 }
 
 impl Eq for BitSequence {
 }
+
+impl cmp::PartialOrd for BitSequence {
+  fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl cmp::Ord for BitSequence {
+  fn cmp(&self, other: &Self) -> cmp::Ordering {
+    let ordering = self.len().cmp(&other.len());
+
+    if ordering != cmp::Ordering::Equal || self.bytes.is_empty() {
+      return ordering;
+    }
+
+    // 长度相同时，字节数与最后一个字节的长度均相同
+    let last = self.bytes.len() - 1;
+    let mask = if self.last_byte_len == 0 { 0xff } else { 0xff << (8 - self.last_byte_len) };
+
+    self.bytes[.. last]
+      .cmp(&other.bytes[.. last])
+      .then((self.bytes[last] & mask).cmp(&(other.bytes[last] & mask)))
+  }
+}
+
+/// # 比特序列的逐位迭代器
+///
+/// 由 `BitSequence::bits` 创建，按大端序产生恰好 `len()` 个比特
+///
+/// ## 成员
+///
+/// * `sequence` - 被迭代的比特序列
+/// * `index` - 下一个待产生的比特位置
+///
+/// ## 实现特征
+///
+/// * `Iterator<Item = bool>`
+/// * `ExactSizeIterator`
+#[derive(Clone)]
+pub struct BitIter<'a> {
+  sequence: &'a BitSequence,
+  index: u64
+}
+
+impl Iterator for BitIter<'_> {
+  type Item = bool;
+
+  fn next(&mut self) -> Option<bool> {
+    let bit = self.sequence.get_bit(self.index)?;
+    self.index += 1;
+
+    Some(bit)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let remaining = (self.sequence.len() - self.index) as usize;
+
+    (remaining, Some(remaining))
+  }
+}
+
+impl ExactSizeIterator for BitIter<'_> {
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn reverse_bits_in_bytes_reverses_each_byte() {
+    let bits = BitSequence::with_bytes(&[0x01, 0x80, 0xf0]);
+    let reversed = bits.reverse_bits_in_bytes();
+
+    assert_eq!(reversed.get_bytes(), &[0x80, 0x01, 0x0f]);
+    assert_eq!(reversed.len(), 24);
+    assert_eq!(reversed.reverse_bits_in_bytes().get_bytes(), bits.get_bytes());
+  }
+
+  #[test]
+  fn reverse_bits_in_bytes_keeps_partial_byte_aligned() {
+    // 最后一个字节只有 3 位有效位 110，其余位为无效位
+    let bits = BitSequence::new(vec![0xff, 0b1101_1111], 3);
+    let reversed = bits.reverse_bits_in_bytes();
+
+    assert_eq!(reversed.get_bytes(), &[0xff, 0b0110_0000]);
+    assert_eq!(reversed.get_last_byte_len(), 3);
+  }
+
+  #[test]
+  fn first_and_last_bytes_in_range() {
+    let bits = BitSequence::with_bytes(&[1, 2, 3, 4]);
+
+    assert_eq!(bits.first_bytes(0), Some(&[][..]));
+    assert_eq!(bits.first_bytes(3), Some(&[1, 2, 3][..]));
+    assert_eq!(bits.first_bytes(4), Some(&[1, 2, 3, 4][..]));
+    assert_eq!(bits.last_bytes(0), Some(&[][..]));
+    assert_eq!(bits.last_bytes(2), Some(&[3, 4][..]));
+    assert_eq!(bits.last_bytes(4), Some(&[1, 2, 3, 4][..]));
+  }
+
+  #[test]
+  fn first_and_last_bytes_out_of_range() {
+    let bits = BitSequence::with_bytes(&[1, 2, 3, 4]);
+
+    assert_eq!(bits.first_bytes(5), None);
+    assert_eq!(bits.last_bytes(5), None);
+    assert_eq!(BitSequence::new_empty().first_bytes(1), None);
+    assert_eq!(BitSequence::new_empty().last_bytes(1), None);
+  }
+
+  #[test]
+  fn pop_front_bits_splits_cipher_text_shaped_sequence() {
+    let mut bytes = vec![0x04];
+    bytes.extend((1 ..= 64).map(|i| i as u8));
+    bytes.extend([0xaa; 19]);
+    let mut bits = BitSequence::with_bytes(&bytes);
+
+    let c1 = bits.pop_front_bits(65 * 8).unwrap();
+    assert_eq!(c1.get_bytes(), &bytes[.. 65]);
+    assert_eq!(bits.get_bytes(), &[0xaa; 19]);
+
+    let rest = bits.pop_front_bits(bits.len()).unwrap();
+    assert_eq!(rest.len(), 19 * 8);
+    assert_eq!(bits.len(), 0);
+    assert!(bits.pop_front_bits(1).is_err());
+  }
+
+  #[test]
+  fn pop_front_bits_handles_unaligned_offsets() {
+    let mut bits = BitSequence::with_bytes(&[0b1011_0011, 0b1100_0000]);
+
+    let front = bits.pop_front_bits(3).unwrap();
+    assert_eq!(front.get_bytes(), &[0b1010_0000]);
+    assert_eq!(front.get_last_byte_len(), 3);
+    assert_eq!(bits.len(), 13);
+    assert_eq!(bits.get_bytes(), &[0b1001_1110, 0b0000_0000]);
+  }
+
+  #[test]
+  fn get_and_set_bit() {
+    let mut bits = BitSequence::try_with_bits(&[0b1010_0000, 0b1100_0000], 10).unwrap();
+
+    assert_eq!(bits.get_bit(0), Some(true));
+    assert_eq!(bits.get_bit(1), Some(false));
+    assert_eq!(bits.get_bit(9), Some(true));
+    assert_eq!(bits.get_bit(10), None);
+
+    bits.set_bit(1, true).unwrap();
+    bits.set_bit(9, false).unwrap();
+    assert_eq!(bits.get_bytes(), &[0b1110_0000, 0b1000_0000]);
+    assert_eq!(bits.set_bit(10, true), Err(Error::OutOfRange));
+  }
+
+  #[test]
+  fn append_bit_grows_the_sequence() {
+    let mut bits = BitSequence::new_empty();
+
+    for i in 0 .. 12 {
+      bits.append_bit(i % 3 == 0);
+      assert_eq!(bits.len(), i + 1);
+    }
+
+    assert_eq!(bits.get_bytes(), &[0b1001_0010, 0b0100_0000]);
+    assert_eq!(bits.get_last_byte_len(), 4);
+  }
+
+  #[test]
+  fn bits_iterates_valid_bits_in_order() {
+    let bits = BitSequence::try_with_bits(&[0b1010_0000, 0b1111_1111], 11).unwrap();
+    let iter = bits.bits();
+
+    assert_eq!(iter.len(), 11);
+    assert_eq!(
+      iter.collect::<Vec<_>>(),
+      [true, false, true, false, false, false, false, false, true, true, true]
+    );
+    assert_eq!(BitSequence::new_empty().bits().next(), None);
+
+    let mut rebuilt = BitSequence::new_empty();
+    bits.bits().for_each(|bit| rebuilt.append_bit(bit));
+    assert!(rebuilt == bits);
+  }
+
+  #[test]
+  #[cfg(feature = "std")]
+  fn write_to_and_read_from_round_trip() {
+    let sequences = [
+      BitSequence::new_empty(),
+      BitSequence::with_bytes(&[1, 2, 3]),
+      // 最后一个字节的无效位不写出
+      BitSequence::new(vec![0xab, 0b1011_1111], 3),
+      BitSequence::try_with_bits(&[0xff], 1).unwrap()
+    ];
+
+    for bits in sequences {
+      let mut buffer = Vec::new();
+      bits.write_to(&mut buffer).unwrap();
+      assert_eq!(buffer.len() as u64, 8 + bits.len().div_ceil(8));
+      assert_eq!(buffer[.. 8], bits.len().to_be_bytes());
+
+      let read = BitSequence::read_from(&mut io::Cursor::new(&buffer)).unwrap();
+      assert!(read == bits);
+      assert_eq!(read.len(), bits.len());
+      assert_eq!(read.get_last_byte_len(), bits.get_last_byte_len());
+    }
+
+    let mut buffer = Vec::new();
+    BitSequence::new(vec![0xab, 0b1011_1111], 3).write_to(&mut buffer).unwrap();
+    assert_eq!(buffer[8 ..], [0xab, 0b1010_0000]);
+  }
+
+  #[test]
+  #[cfg(feature = "std")]
+  fn read_from_rejects_truncated_input() {
+    let mut buffer = Vec::new();
+    BitSequence::new(vec![1, 2, 0b1100_0000], 2).write_to(&mut buffer).unwrap();
+
+    for len in [0, 4, 8, 9, buffer.len() - 1] {
+      let error = BitSequence::read_from(&mut io::Cursor::new(&buffer[.. len])).err().unwrap();
+
+      assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    // 长度前缀远大于实际数据时，同样报告截断
+    let mut buffer = u64::MAX.to_be_bytes().to_vec();
+    buffer.extend([0; 16]);
+    let error = BitSequence::read_from(&mut io::Cursor::new(&buffer)).err().unwrap();
+    assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+  }
+
+  #[test]
+  fn shifts_match_bitwise_reference() {
+    let sequences = [
+      BitSequence::try_with_bits(&[0b1011_0110, 0b0101_1100], 13).unwrap(),
+      BitSequence::try_with_bits(&[0xa5, 0x3c, 0xff], 21).unwrap(),
+      BitSequence::with_bytes(&[0x81, 0x42, 0x24])
+    ];
+
+    for bits in sequences {
+      let len = bits.len();
+      let original: Vec<bool> = bits.bits().collect();
+
+      for n in [0, 1, 3, 7, 8, 9, 11, 16, len - 1, len, len + 5, 100] {
+        let (mut left, mut right) = (bits.clone(), bits.clone());
+        left.shl_bits(n);
+        right.shr_bits(n);
+
+        // 左移时第 i 位取自原来的第 i + n 位，右移时取自第 i - n 位，越界补 0
+        let expected_left: Vec<bool> =
+          (0 .. len).map(|i| i + n < len && original[(i + n) as usize]).collect();
+        let expected_right: Vec<bool> =
+          (0 .. len).map(|i| i >= n && original[(i - n) as usize]).collect();
+
+        assert_eq!(left.len(), len);
+        assert_eq!(left.bits().collect::<Vec<_>>(), expected_left, "shl {n}");
+        assert_eq!(right.len(), len);
+        assert_eq!(right.bits().collect::<Vec<_>>(), expected_right, "shr {n}");
+      }
+    }
+  }
+
+  #[test]
+  fn shifts_clear_invalid_bits() {
+    // 无效位为 1 时不应移入有效位
+    let mut bits = BitSequence::new(vec![0x00, 0b1001_1111], 3);
+    bits.shl_bits(2);
+    assert_eq!(bits.get_bytes(), &[0b0000_0010, 0b0000_0000]);
+
+    let mut bits = BitSequence::new(vec![0x80, 0b1111_1111], 4);
+    bits.shr_bits(4);
+    assert_eq!(bits.get_bytes(), &[0x08, 0x00]);
+    assert_eq!(bits.len(), 12);
+  }
+
+  #[test]
+  fn to_u256_chunks_splits_whole_blocks() {
+    let bytes: Vec<u8> = (0 .. 64).collect();
+
+    let one = BitSequence::with_bytes(&bytes[.. 32]).to_u256_chunks().unwrap();
+    assert_eq!(one, [U256::from_be_bytes(bytes[.. 32].try_into().unwrap())]);
+
+    let two = BitSequence::with_bytes(&bytes).to_u256_chunks().unwrap();
+    assert_eq!(
+      two,
+      [
+        U256::from_be_bytes(bytes[.. 32].try_into().unwrap()),
+        U256::from_be_bytes(bytes[32 ..].try_into().unwrap())
+      ]
+    );
+    // 分块的第一个比特为最高位
+    assert_eq!(
+      BitSequence::with_bytes(&[0x80; 32]).to_u256_chunks().unwrap()[0].highest_bit(),
+      256
+    );
+
+    assert_eq!(BitSequence::new_empty().to_u256_chunks(), Ok(Vec::new()));
+  }
+
+  #[test]
+  fn to_u256_chunks_rejects_partial_blocks() {
+    let bytes = [0xff; 65];
+
+    assert_eq!(BitSequence::with_bytes(&bytes[.. 31]).to_u256_chunks(), Err(Error::InvalidLength));
+    assert_eq!(BitSequence::with_bytes(&bytes[.. 33]).to_u256_chunks(), Err(Error::InvalidLength));
+    assert_eq!(BitSequence::with_bytes(&bytes).to_u256_chunks(), Err(Error::InvalidLength));
+    assert_eq!(
+      BitSequence::try_with_bits(&bytes[.. 32], 255).unwrap().to_u256_chunks(),
+      Err(Error::InvalidLength)
+    );
+    assert_eq!(
+      BitSequence::try_with_bits(&bytes[.. 33], 257).unwrap().to_u256_chunks(),
+      Err(Error::InvalidLength)
+    );
+  }
+
+  #[test]
+  fn append_bits_covers_every_alignment() {
+    let aligned_target = BitSequence::with_bytes(&[0xa5, 0x0f]);
+    // 无效位置 1，检验其不会泄漏到结果中
+    let unaligned_target = BitSequence::new(vec![0xa5, 0b0101_1111], 3);
+    let aligned_source = BitSequence::with_bytes(&[0xc3]);
+    let unaligned_sources = [
+      BitSequence::new(vec![0b1101_1111], 2),
+      BitSequence::new(vec![0xff, 0b1011_1111], 5),
+      BitSequence::new(vec![0x3c, 0b0111_1111], 7)
+    ];
+
+    let sources = [aligned_source].into_iter().chain(unaligned_sources);
+
+    for source in sources {
+      for target in [&aligned_target, &unaligned_target] {
+        let mut result = target.clone();
+        result.append_bits(&source);
+
+        let expected: Vec<bool> = target.bits().chain(source.bits()).collect();
+
+        assert_eq!(result.len(), target.len() + source.len());
+        assert_eq!(result.bits().collect::<Vec<_>>(), expected);
+      }
+    }
+
+    // 两个未对齐序列拼接后恰好对齐
+    let mut result = BitSequence::new(vec![0b1110_0000], 3);
+    result.append_bits(&BitSequence::new(vec![0b0101_1000], 5));
+    assert_eq!(result.get_bytes(), &[0b1110_1011]);
+    assert_eq!(result.get_last_byte_len(), 0);
+
+    let mut result = aligned_target.clone();
+    result.append_bits(&BitSequence::new_empty());
+    assert!(result == aligned_target);
+  }
+}
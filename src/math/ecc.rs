@@ -1,6 +1,13 @@
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
 use {
-  super::{bytes::BitSequence, u256::U256},
-  std::{
+  super::{
+    bytes::{BitSequence, ByteOrder},
+    u256::U256
+  },
+  crate::Error,
+  alloc::{vec, vec::Vec},
+  core::{
     cmp,
     ops::{self}
   }
@@ -10,48 +17,61 @@ use {
 ///
 /// ## 方法
 ///
-/// * `mod_inv(self, modulus) -> Option<Self>` - 计算模逆
+/// * `mod_inv(self, modulus) -> Option<Self>` - 计算模逆，不可逆时返回 `None`
+/// * `mod_inv_prime(self, p) -> Self` - 由费马小定理计算素数模下的模逆
+///   self^(p-2)
 ///
 /// ## 注意事项
 ///
-/// 由于实际只需对 `U256` 实现，所以使用 `Sized` 约束
+/// * 由于实际只需对 `U256` 实现，所以使用 `Sized` 约束
+/// * `mod_inv` 的迭代次数与分支依赖于输入；`mod_inv_prime` 在 Montgomery
+///   形式下按公开的指数 p - 2 平方与相乘，运算次序只与 `p` 有关，
+///   适用于私钥等秘密值求逆
+/// * `mod_inv_prime` 要求 `p` 为素数，`self` 为 0 时返回 0，`self` 不小于 `p`
+///   时结果与先约减再求逆一致
 pub trait ModInv: Sized {
   fn mod_inv(self, modulus: Self) -> Option<Self>;
+  fn mod_inv_prime(self, p: Self) -> Self;
 }
 
 impl ModInv for U256 {
   fn mod_inv(self, modulus: Self) -> Option<Self> {
-    let mut a = self;
-    let mut modulus = modulus;
-    let (mut x0, mut x1) = (U256::C_0, U256::C_1);
-
-    if modulus == U256::C_1 {
+    if modulus.eq_u64(0) {
+      return None;
+    }
+    if modulus.eq_u64(1) {
       return Some(U256::C_0);
     }
 
-    while a > U256::C_1 {
-      if modulus == U256::C_0 {
-        return None;
-      }
-      let q = a / modulus;
-      let t = modulus;
-      modulus = a % modulus;
-      a = t;
-      let t = x0;
-      x0 = x1 - q * x0;
-      x1 = t;
-    }
+    // 扩展欧几里得算法，保持 r0 ≡ t0 * self、r1 ≡ t1 * self (mod modulus)；
+    // 系数始终约减到 [0, modulus)，用模减代替有符号运算，避免回绕
+    let (mut r0, mut r1) = (modulus, self.modded(modulus));
+    let (mut t0, mut t1) = (U256::C_0, U256::C_1);
 
-    if x1 > modulus {
-      x1 = x1 + modulus;
+    while !r1.eq_u64(0) {
+      let q = r0 / r1;
+      (r0, r1) = (r1, r0 % r1);
+      (t0, t1) = (t1, t0.mod_sub(q.mod_mul(t1, modulus), modulus));
     }
 
-    if a == U256::C_1 {
-      Some((x1 + modulus) % modulus)
+    if r0.eq_u64(1) {
+      Some(t0)
     } else {
       None
     }
   }
+
+  fn mod_inv_prime(self, p: Self) -> Self {
+    // a^(p-1) ≡ 1 (mod p)，故 a^(-1) ≡ a^(p-2)；唯一的偶素数 2 不能使用
+    // Montgomery 乘法
+    if !p.bit(0) {
+      return self.modpow(p - U256::C_2, p);
+    }
+
+    let ctx = MontgomeryCtx::new(p);
+
+    ctx.from_mont(ctx.mont_inv(ctx.to_mont(self)))
+  }
 }
 
 /// # 椭圆曲线参数结构体
@@ -64,15 +84,25 @@ impl ModInv for U256 {
 /// * `n` - 椭圆曲线参数 n
 /// * `g_x` - 椭圆曲线参数 Gx
 /// * `g_y` - 椭圆曲线参数 Gy
+///
 /// ## 构造方法
 ///
-/// * `EccParams { a, b, p, n, g_x, g_y }` - 初始化椭圆曲线参数结构体
+/// * `EccParams::new(a, b, p, n, g_x, g_y)` - 初始化椭圆曲线参数结构体，
+///   与结构体字面量等价，可用于常量上下文
 ///
 /// ## 实现特征
 ///
 /// * `PartialEq`
 /// * `Eq`
 ///
+/// ## 方法
+///
+/// * `p_minus_1(&self) -> U256` - 返回 p - 1
+/// * `p_plus_1_div_4(&self) -> U256` - 返回 (p + 1) / 4，p ≡ 3 (mod 4) 时用于模
+///   p 开平方
+/// * `n_minus_1(&self) -> U256` - 返回 n - 1
+/// * `is_sm2(&self) -> bool` - 判断是否为 SM2 推荐曲线参数，定义于 `sm_2` 模块
+///
 /// ## 注意事项
 ///
 /// 确保所有 `EccPoint` 的生命周期与 `EccParams` 一致，否则会出现生命周期问题
@@ -86,187 +116,1842 @@ pub struct EccParams {
   pub g_y: U256
 }
 
-/// # 椭圆曲线点结构体
+impl EccParams {
+  pub const fn new(a: U256, b: U256, p: U256, n: U256, g_x: U256, g_y: U256) -> Self {
+    Self { a, b, p, n, g_x, g_y }
+  }
+
+  pub const fn p_minus_1(&self) -> U256 {
+    sub_one(self.p)
+  }
+
+  pub const fn p_plus_1_div_4(&self) -> U256 {
+    // (p + 1) / 4 = ⌊p / 4⌋ + 1，p ≡ 3 (mod 4) 时成立，且不会溢出
+    add_one(shr_two(self.p))
+  }
+
+  pub const fn n_minus_1(&self) -> U256 {
+    sub_one(self.n)
+  }
+
+  /// # 模 p 乘法
+  ///
+  /// SM2 推荐曲线使用专用的快速约减，其余曲线使用通用的 `mod_mul`
+  ///
+  /// ## 参数
+  ///
+  /// * `a` - 被乘数
+  /// * `b` - 乘数
+  ///
+  /// ## 返回值
+  ///
+  /// * `U256` - `a * b mod p`
+  fn mul_mod_p(&self, a: U256, b: U256) -> U256 {
+    if self.is_sm2() {
+      reduce_sm2_p(widening_product(a, b), self.p)
+    } else {
+      a.mod_mul(b, self.p)
+    }
+  }
+
+  /// # 曲线方程右侧 x^3 + ax + b (mod p)
+  ///
+  /// ## 参数
+  ///
+  /// * `x` - 横坐标
+  ///
+  /// ## 返回值
+  ///
+  /// * `U256` - `x^3 + ax + b mod p`
+  fn curve_rhs(&self, x: U256) -> U256 {
+    self
+      .mul_mod_p(self.mul_mod_p(x, x), x)
+      .mod_add(self.mul_mod_p(x, self.a), self.p)
+      .mod_add(self.b.modded(self.p), self.p)
+  }
+
+  /// # 模 p 平方根
+  ///
+  /// p ≡ 3 (mod 4) 时，v 的平方根为 v^((p + 1) / 4) mod p
+  ///
+  /// ## 参数
+  ///
+  /// * `v` - 被开方数
+  ///
+  /// ## 返回值
+  ///
+  /// * `Ok(Some(U256))` - 平方根之一
+  /// * `Ok(None)` - v 不是模 p 的二次剩余
+  /// * `Err(Error)` - p 不满足 p ≡ 3 (mod 4)
+  fn sqrt_mod_p(&self, v: U256) -> Result<Option<U256>, Error> {
+    if self.p.into_le_u64_array()[0] & 3 != 3 {
+      return Err(Error::UnsupportedCurve);
+    }
+
+    // 平方-乘算法，从高位起处理指数
+    let exponent = self.p_plus_1_div_4().into_le_u64_array();
+    let mut root = U256::C_1;
+
+    for i in (0 .. 256).rev() {
+      root = self.mul_mod_p(root, root);
+
+      if (exponent[i / 64] >> (i % 64)) & 1 == 1 {
+        root = self.mul_mod_p(root, v);
+      }
+    }
+
+    if self.mul_mod_p(root, root) == v.modded(self.p) {
+      Ok(Some(root))
+    } else {
+      Ok(None)
+    }
+  }
+}
+
+/// # 256 位乘法，返回完整的 512 位乘积
+///
+/// ## 参数
+///
+/// * `a` - 被乘数
+/// * `b` - 乘数
+///
+/// ## 返回值
+///
+/// * `[u64; 8]` - 小端序的 512 位乘积
+fn widening_product(a: U256, b: U256) -> [u64; 8] {
+  let a = a.into_le_u64_array();
+  let b = b.into_le_u64_array();
+  let mut result = [0u64; 8];
+
+  for i in 0 .. 4 {
+    let mut carry = 0u128;
+
+    for j in 0 .. 4 {
+      let t = a[i] as u128 * b[j] as u128 + result[i + j] as u128 + carry;
+
+      result[i + j] = t as u64;
+      carry = t >> 64;
+    }
+
+    result[i + 4] = carry as u64;
+  }
+
+  result
+}
+
+/// # SM2 素数域快速约减
+///
+/// 利用 p = 2^256 - 2^224 - 2^96 + 2^64 - 1，即 2^256 ≡ 2^224 + 2^96 - 2^64 + 1
+/// (mod p)，反复将高 256 位折叠到低 256 位，最后至多减去一次 p
+///
+/// ## 参数
+///
+/// * `product` - 小端序的 512 位被约减数
+/// * `p` - SM2 素数 p
+///
+/// ## 返回值
+///
+/// * `U256` - `product mod p`
+fn reduce_sm2_p(product: [u64; 8], p: U256) -> U256 {
+  const MASK: i128 = u64::MAX as i128;
+
+  // 每个元素保存一个 64 位的字，使用 i128 容纳折叠过程中的进位和借位
+  let mut t = [0i128; 10];
+  for i in 0 .. 8 {
+    t[i] = product[i] as i128;
+  }
+
+  while t[4 ..].iter().any(|limb| *limb != 0) {
+    let mut acc = [0i128; 10];
+    acc[.. 4].copy_from_slice(&t[.. 4]);
+
+    for j in 0 .. 6 {
+      let h = t[4 + j];
+
+      // h * (2^224 + 2^96 - 2^64 + 1)
+      acc[j] += h;
+      acc[j + 1] -= h;
+      acc[j + 1] += (h << 32) & MASK;
+      acc[j + 2] += h >> 32;
+      acc[j + 3] += (h << 32) & MASK;
+      acc[j + 4] += h >> 32;
+    }
+
+    // 进位传播，使每个字回到 [0, 2^64)
+    for i in 0 .. 9 {
+      let carry = acc[i] >> 64;
+      acc[i] -= carry << 64;
+      acc[i + 1] += carry;
+    }
+
+    t = acc;
+  }
+
+  let result = U256::from_le_u64_array(&[t[0] as u64, t[1] as u64, t[2] as u64, t[3] as u64]);
+
+  if result >= p {
+    result - p
+  } else {
+    result
+  }
+}
+
+/// # Montgomery 乘法上下文
+///
+/// 对奇数模 p 预计算 Montgomery 乘法所需的常量，R = 2^256
 ///
 /// ## 成员
 ///
-/// * `x` - 椭圆曲线点 x 坐标；无穷远点为 0
-/// * `y` - 椭圆曲线点 y 坐标；无穷远点为 0
-/// * `params` - 椭圆曲线参数结构体的引用
-/// * `infinity` - 椭圆曲线点是否为无穷远点
+/// * `p` - 模数，须为奇数
+/// * `r2` - R^2 mod p，用于转换为 Montgomery 形式
+/// * `n0_inv` - -p^(-1) mod 2^64
 ///
 /// ## 构造方法
 ///
-/// * `let new_ecc_point = old_ecc_point` - 复制一个椭圆曲线点
-/// * `EccPoint { x, y, params, infinity }` - 直接初始化，不推荐
-/// * `EccPoint::new(x, y, params, infinity)` - 创建一个椭圆曲线点
-/// * `EccPoint::infinity(params)` - 创建一个无穷远点
-/// * `EccPoint::new_simple(x, y, params)` - 创建一个非无穷远椭圆曲线点
+/// * `MontgomeryCtx::new(p)` - 由奇数模 p 创建
 ///
 /// ## 实现特征
 ///
 /// * `Clone`
 /// * `Copy`
-/// * `PartialEq`
-/// * `Eq`
-/// * `EccOps` - 椭圆曲线相关运算
-/// * `From<EccPoint<'a>> -> Vec<u8>`
-/// * `From<EccPoint<'a>> -> BitSequence`
 ///
 /// ## 方法
 ///
-/// * `from_bytes(bytes: &[u8; 65], params: &'a EccParams) -> Self` -
-///   从字节序列构造椭圆曲线点
-/// * `validate_on_curve(self) -> bool` - 验证椭圆曲线点是否在曲线上
-#[derive(Clone, Copy, PartialEq, Eq)]
-pub struct EccPoint<'a> {
-  pub x: U256,
-  pub y: U256,
-  pub params: &'a EccParams,
-  pub infinity: bool
+/// * `mont_mul(&self, a: U256, b: U256) -> U256` - Montgomery 乘法，返回 a * b
+///   * R^(-1) mod p
+/// * `to_mont(&self, a: U256) -> U256` - 转换为 Montgomery 形式 a * R mod p
+/// * `from_mont(&self, a: U256) -> U256` - 由 Montgomery 形式转换回 a
+///
+/// ## 注意事项
+///
+/// * `mont_mul` 的输入须小于 p
+#[derive(Clone, Copy)]
+pub struct MontgomeryCtx {
+  p: U256,
+  r2: U256,
+  n0_inv: u64
 }
 
-impl<'a> EccPoint<'a> {
-  pub fn new(x: U256, y: U256, params: &'a EccParams, infinity: bool) -> Self {
-    if infinity {
-      Self { x: U256::C_0, y: U256::C_0, params, infinity }
-    } else {
-      Self { x, y, params, infinity: false }
+impl MontgomeryCtx {
+  pub fn new(p: U256) -> Self {
+    // R mod p = (2^256 - p) mod p
+    let r = (U256::C_0 - p).modded(p);
+
+    // R^2 mod p = R * 2^256 mod p，通过 256 次模倍加得到
+    let mut r2 = r;
+    for _ in 0 .. 256 {
+      r2 = r2.mod_add(r2, p);
     }
-  }
 
-  pub fn infinity(params: &'a EccParams) -> Self {
-    Self { x: U256::C_0, y: U256::C_0, params, infinity: true }
-  }
+    // 牛顿迭代求 p^(-1) mod 2^64，每次迭代有效位数翻倍
+    let p0 = p.into_le_u64_array()[0];
+    let mut inv = 1u64;
+    for _ in 0 .. 6 {
+      inv = inv.wrapping_mul(2u64.wrapping_sub(p0.wrapping_mul(inv)));
+    }
 
-  pub fn new_simple(x: U256, y: U256, params: &'a EccParams) -> Self {
-    Self::new(x, y, params, false)
+    Self { p, r2, n0_inv: inv.wrapping_neg() }
   }
 
-  pub fn from_bytes(bytes: &[u8; 65], params: &'a EccParams) -> Self {
-    let x = U256::from_le_bytes(&bytes[1 .. 33].try_into().unwrap());
-    let y = U256::from_le_bytes(&bytes[33 .. 65].try_into().unwrap());
+  pub fn mont_mul(&self, a: U256, b: U256) -> U256 {
+    let a = a.into_le_u64_array();
+    let b = b.into_le_u64_array();
+    let p = self.p.into_le_u64_array();
+
+    // CIOS：每轮累加 a * b[i]，再加上 m * p 使最低字为 0 并右移一个字
+    let mut t = [0u64; 6];
+
+    for b_i in b {
+      let mut carry = 0u128;
+      for j in 0 .. 4 {
+        let v = t[j] as u128 + a[j] as u128 * b_i as u128 + carry;
+        t[j] = v as u64;
+        carry = v >> 64;
+      }
+      let v = t[4] as u128 + carry;
+      t[4] = v as u64;
+      t[5] = (v >> 64) as u64;
+
+      let m = t[0].wrapping_mul(self.n0_inv);
+
+      let mut carry = (t[0] as u128 + m as u128 * p[0] as u128) >> 64;
+      for j in 1 .. 4 {
+        let v = t[j] as u128 + m as u128 * p[j] as u128 + carry;
+        t[j - 1] = v as u64;
+        carry = v >> 64;
+      }
+      let v = t[4] as u128 + carry;
+      t[3] = v as u64;
+      t[4] = t[5] + (v >> 64) as u64;
+    }
+
+    // 结果小于 2p，至多减去一次 p；以掩码选择，运算不依赖结果的大小
+    let result = U256::from_le_u64_array(&[t[0], t[1], t[2], t[3]]);
+    let (reduced, borrow) = sub_with_borrow(result, self.p);
 
-    Self::new_simple(x, y, params)
+    conditional_select(result, reduced, t[4] | (borrow ^ 1))
   }
 
-  pub fn validate_on_curve(self) -> bool {
-    // y^2 = x^3 + ax + b (mod p)
-    self.infinity
-      || self.y.mod_mul(self.y, self.params.p)
-        == self
-          .x
-          .mod_mul(self.x, self.params.p)
-          .mod_mul(self.x, self.params.p)
-          .mod_add(self.x.mod_mul(self.params.a, self.params.p), self.params.p)
-          .mod_add(self.params.b.modded(self.params.p), self.params.p)
+  pub fn to_mont(&self, a: U256) -> U256 {
+    self.mont_mul(a, self.r2)
   }
 
-  pub fn validate_on_given_curve(self, params: &EccParams) -> bool {
-    self.params == params && self.validate_on_curve()
+  pub fn from_mont(&self, a: U256) -> U256 {
+    self.mont_mul(a, U256::C_1)
   }
-}
 
-/// # 椭圆曲线相关运算
-///
-/// ## 方法
-///
-/// * `ecc_add(self, other: Self, params: &'a EccParams) -> Self` - 椭圆曲线加法
-/// * `ecc_mul(self, k: U256, params: &'a EccParams) -> Self` - 椭圆曲线数乘
-pub trait EccOps<'a> {
-  /// # 椭圆曲线加法
-  ///
-  /// ## 参数
-  ///
-  /// * `self` - 椭圆曲线点
-  /// * `other` - 椭圆曲线点
-  /// * `params` - 椭圆曲线参数结构体的引用
+  /// # Montgomery 形式下的求逆
   ///
-  /// ## 返回值
-  ///
-  /// * `Self` - 椭圆曲线点
-  ///
-  /// ## 特殊情况
-  ///
-  /// * 当两个椭圆曲线点参数不兼容时，崩溃
-  /// * 当 `self` 和 `other` 中有一个为无穷远点时，返回另一个点
-  fn ecc_add(self, other: Self, params: &'a EccParams) -> Self;
-
-  /// # 椭圆曲线数乘
+  /// 由费马小定理，a^(-1) = a^(p-2)；指数 p - 2 是公开的，
+  /// 平方与乘法的次序与 a 无关
   ///
   /// ## 参数
   ///
-  /// * `self` - 椭圆曲线点
-  /// * `k` - 数乘因子
-  /// * `params` - 椭圆曲线参数结构体的引用
+  /// * `a` - Montgomery 形式的元素，p 须为素数
   ///
   /// ## 返回值
   ///
-  /// * `Self` - 椭圆曲线点
-  ///
-  /// ## 特殊情况
-  ///
-  /// * 当 `k` 为 0 或 `self` 为无穷远点时，返回无穷远点
-  fn ecc_mul(self, k: U256, params: &'a EccParams) -> Self;
+  /// * `U256` - Montgomery 形式的逆元，a 为 0 时返回 0
+  fn mont_inv(&self, a: U256) -> U256 {
+    let exponent = (self.p - U256::C_2).into_le_u64_array();
+    let mut result = self.to_mont(U256::C_1);
+
+    for i in (0 .. 256).rev() {
+      result = self.mont_mul(result, result);
+
+      if (exponent[i / 64] >> (i % 64)) & 1 == 1 {
+        result = self.mont_mul(result, a);
+      }
+    }
+
+    result
+  }
 }
 
-impl<'a> EccOps<'a> for EccPoint<'a> {
-  fn ecc_add(self, other: Self, params: &'a EccParams) -> Self {
-    if self.params != other.params {
-      panic!("Incompatible elliptic curve parameters");
+/// # SM2 素数域的 Montgomery 乘法上下文，首次使用时计算
+#[cfg(feature = "std")]
+static SM2_MONTGOMERY: OnceLock<MontgomeryCtx> = OnceLock::new();
+
+/// # 获取 SM2 素数域的 Montgomery 乘法上下文
+///
+/// 启用 `std` 特性时缓存于 `SM2_MONTGOMERY`
+#[cfg(feature = "std")]
+fn sm2_montgomery(p: U256) -> MontgomeryCtx {
+  *SM2_MONTGOMERY.get_or_init(|| MontgomeryCtx::new(p))
+}
+
+/// # 获取 SM2 素数域的 Montgomery 乘法上下文
+///
+/// 未启用 `std` 特性时没有可用的全局缓存，每次重新计算
+#[cfg(not(feature = "std"))]
+fn sm2_montgomery(p: U256) -> MontgomeryCtx {
+  MontgomeryCtx::new(p)
+}
+
+/// # Barrett 约减上下文
+///
+/// 对模数 m 预计算 mu = floor(2^(2k) / m)，其中 k 为 m 的位数，
+/// 以乘法和移位估计商，再做至多两次减法修正，避免逐位相减的约减循环
+///
+/// ## 成员
+///
+/// * `modulus` - 模数，不能为 0
+/// * `mu` - floor(2^(2k) / modulus)，至多 257 位，小端序 u64 数组
+/// * `k` - 模数的位数
+///
+/// ## 构造方法
+///
+/// * `BarrettReducer::new(modulus)` - 由非零模数创建
+///
+/// ## 实现特征
+///
+/// * `Clone`
+/// * `Copy`
+///
+/// ## 方法
+///
+/// * `modulus(&self) -> U256` - 获取模数
+/// * `reduce(&self, product: (U256, U256)) -> U256` - 将 `widening_mul` 得到的
+///   512 位乘积约减到 [0, modulus)
+///
+/// ## 注意事项
+///
+/// * `reduce` 的输入须小于 2^(2k)；两个小于模数的数之积总满足该条件， 模数为
+///   256 位时任意 512 位输入均可
+#[derive(Clone, Copy)]
+pub struct BarrettReducer {
+  modulus: U256,
+  mu: [u64; 5],
+  k: usize
+}
+
+impl BarrettReducer {
+  pub fn new(modulus: U256) -> Self {
+    assert!(!modulus.eq_u64(0), "modulus must be nonzero");
+
+    let k = modulus.highest_bit();
+
+    // 逐位长除法求 floor(2^(2k) / modulus)，余数始终小于 modulus
+    let mut mu = [0u64; 5];
+    let mut rem = U256::C_0;
+
+    for i in (0 ..= 2 * k).rev() {
+      let (doubled, carry) = rem.overflowing_add(rem);
+      rem = if i == 2 * k { doubled + U256::C_1 } else { doubled };
+
+      if carry || rem >= modulus {
+        rem -= modulus;
+        mu[i / 64] |= 1 << (i % 64);
+      }
     }
 
-    if self == other {
-      return self.ecc_mul(U256::C_2, params);
+    Self { modulus, mu, k }
+  }
+
+  pub fn modulus(&self) -> U256 {
+    self.modulus
+  }
+
+  pub fn reduce(&self, product: (U256, U256)) -> U256 {
+    let (low, high) = (product.0.into_le_u64_array(), product.1.into_le_u64_array());
+    let x = [low[0], low[1], low[2], low[3], high[0], high[1], high[2], high[3]];
+    let m = self.modulus.into_le_u64_array();
+    let m = [m[0], m[1], m[2], m[3], 0];
+
+    // q1 = floor(x / 2^(k-1))，q3 = floor(q1 * mu / 2^(k+1))，q3 比真实的商至多小 2
+    let q1: [u64; 5] = limbs_shr(&x, self.k - 1);
+    let q3: [u64; 5] = limbs_shr(&limbs_mul(&q1, &self.mu), self.k + 1);
+
+    // r = x - q3 * m 的真实值小于 3m，只需在低 320 位上计算
+    let q3m = limbs_mul(&q3, &m);
+    let mut r =
+      limbs_sub(&[x[0], x[1], x[2], x[3], x[4]], &[q3m[0], q3m[1], q3m[2], q3m[3], q3m[4]]);
+
+    while r.iter().rev().cmp(m.iter().rev()) != cmp::Ordering::Less {
+      r = limbs_sub(&r, &m);
     }
 
-    match (self.infinity, other.infinity) {
-      (true, _) => return other,
-      (_, true) => return self,
-      (false, false) => {}
+    U256::from_le_u64_array(&[r[0], r[1], r[2], r[3]])
+  }
+}
+
+/// # 小端序 u64 数组右移
+///
+/// 结果只保留低 `M` 个字，超出部分舍弃
+fn limbs_shr<const N: usize, const M: usize>(x: &[u64; N], shift: usize) -> [u64; M] {
+  let (words, bits) = (shift / 64, shift % 64);
+  let mut result = [0u64; M];
+
+  for (i, limb) in result.iter_mut().enumerate() {
+    let lo = x.get(i + words).copied().unwrap_or(0);
+    let hi = x.get(i + words + 1).copied().unwrap_or(0);
+
+    *limb = if bits == 0 { lo } else { lo >> bits | hi << (64 - bits) };
+  }
+
+  result
+}
+
+/// # 5 个字的小端序 u64 数组相乘，返回完整的 10 个字乘积
+fn limbs_mul(a: &[u64; 5], b: &[u64; 5]) -> [u64; 10] {
+  let mut result = [0u64; 10];
+
+  for i in 0 .. 5 {
+    let mut carry = 0u128;
+
+    for j in 0 .. 5 {
+      let v = (a[i] as u128) * (b[j] as u128) + result[i + j] as u128 + carry;
+
+      result[i + j] = v as u64;
+      carry = v >> 64;
     }
 
-    let num = other.y.mod_add(params.p - self.y, params.p);
-    let denom = other.x.mod_add(params.p - self.x, params.p);
+    result[i + 5] = carry as u64;
+  }
+
+  result
+}
+
+/// # 5 个字的小端序 u64 数组相减，结果对 2^320 取模
+fn limbs_sub(a: &[u64; 5], b: &[u64; 5]) -> [u64; 5] {
+  let mut result = [0u64; 5];
+  let mut borrow = false;
+
+  for i in 0 .. 5 {
+    let (v, b1) = a[i].overflowing_sub(b[i]);
+    let (v, b2) = v.overflowing_sub(borrow as u64);
+
+    result[i] = v;
+    borrow = b1 || b2;
+  }
+
+  result
+}
+
+/// # Jacobian 坐标运算使用的域
+///
+/// p 为奇数时在 Montgomery 形式下运算，SM2 曲线的上下文全局缓存；
+/// 否则使用 `mul_mod_p`
+///
+/// ## 成员
+///
+/// * `params` - 曲线参数
+/// * `montgomery` - Montgomery 乘法上下文，p 为偶数时为 `None`
+/// * `a` - 曲线参数 a，与坐标使用相同的表示形式
+/// * `one` - 1 在该表示形式下的值
+struct JacobianField<'a> {
+  params: &'a EccParams,
+  montgomery: Option<MontgomeryCtx>,
+  a: U256,
+  one: U256
+}
+
+impl<'a> JacobianField<'a> {
+  fn new(params: &'a EccParams) -> Self {
+    if params.is_sm2() {
+      Self::with_montgomery(params, sm2_montgomery(params.p))
+    } else if params.p.bit(0) {
+      Self::with_montgomery(params, MontgomeryCtx::new(params.p))
+    } else {
+      Self { params, montgomery: None, a: params.a, one: U256::C_1 }
+    }
+  }
+
+  fn with_montgomery(params: &'a EccParams, ctx: MontgomeryCtx) -> Self {
+    Self { params, montgomery: Some(ctx), a: ctx.to_mont(params.a), one: ctx.to_mont(U256::C_1) }
+  }
+
+  fn mul(&self, a: U256, b: U256) -> U256 {
+    match self.montgomery {
+      Some(ctx) => ctx.mont_mul(a, b),
+      None => self.params.mul_mod_p(a, b)
+    }
+  }
+
+  /// # 模 p 求逆，输入须非 0
+  fn inv(&self, a: U256) -> U256 {
+    match self.montgomery {
+      Some(ctx) => ctx.mont_inv(a),
+      None => a.mod_inv(self.params.p).unwrap()
+    }
+  }
+
+  /// # 由普通表示转换为该域的表示形式
+  fn encode(&self, a: U256) -> U256 {
+    match self.montgomery {
+      Some(ctx) => ctx.to_mont(a),
+      None => a
+    }
+  }
+
+  /// # 由该域的表示形式转换回普通表示
+  fn decode(&self, a: U256) -> U256 {
+    match self.montgomery {
+      Some(ctx) => ctx.from_mont(a),
+      None => a
+    }
+  }
+
+  /// # 模 p 加法，输入须小于 p
+  ///
+  /// 先相加再试减 p，按进位与借位以掩码选择，不含依赖数值的分支
+  fn add(&self, a: U256, b: U256) -> U256 {
+    let (sum, carry) = add_with_carry(a, b);
+    let (reduced, borrow) = sub_with_borrow(sum, self.params.p);
+
+    conditional_select(sum, reduced, carry | (borrow ^ 1))
+  }
+
+  /// # 模 p 减法，输入须小于 p
+  ///
+  /// 相减产生借位时加回 p，加数以掩码选择
+  fn sub(&self, a: U256, b: U256) -> U256 {
+    let (diff, borrow) = sub_with_borrow(a, b);
+
+    add_with_carry(diff, conditional_select(U256::C_0, self.params.p, borrow)).0
+  }
+}
+
+/// # Jacobian 射影坐标的椭圆曲线点
+///
+/// (X, Y, Z) 表示仿射坐标 (X / Z^2, Y / Z^3)，Z = 0 表示无穷远点
+///
+/// 点加与倍点均不需要求逆，仅在 `to_affine` 转换回仿射坐标时求逆一次
+#[derive(Clone, Copy)]
+struct JacobianPoint {
+  x: U256,
+  y: U256,
+  z: U256
+}
+
+impl JacobianPoint {
+  const INFINITY: Self = Self { x: U256::C_0, y: U256::C_0, z: U256::C_0 };
+
+  /// # 由仿射坐标转换
+  fn from_affine(point: EccPoint, field: &JacobianField) -> Self {
+    if point.infinity {
+      return Self::INFINITY;
+    }
+
+    Self { x: field.encode(point.x), y: field.encode(point.y), z: field.one }
+  }
+
+  /// # 负元
+  ///
+  /// -(X, Y, Z) = (X, -Y, Z)
+  fn negate(self, field: &JacobianField) -> Self {
+    Self { x: self.x, y: self.y.mod_neg(field.params.p), z: self.z }
+  }
+
+  /// # 转换回仿射坐标
+  ///
+  /// x = X / Z^2，y = Y / Z^3，只需对 Z 求逆一次
+  fn to_affine<'a>(self, field: &JacobianField<'a>) -> EccPoint<'a> {
+    if self.z.eq_u64(0) {
+      return EccPoint::infinity(field.params);
+    }
+
+    self.to_affine_with_z_inv(field.inv(self.z), field)
+  }
+
+  /// # 以已求得的 Z 的逆转换回仿射坐标
+  fn to_affine_with_z_inv<'a>(self, z_inv: U256, field: &JacobianField<'a>) -> EccPoint<'a> {
+    let z_inv_squared = field.mul(z_inv, z_inv);
+    let x = field.mul(self.x, z_inv_squared);
+    let y = field.mul(self.y, field.mul(z_inv_squared, z_inv));
+
+    EccPoint::new_simple(field.decode(x), field.decode(y), field.params)
+  }
+
+  /// # 批量转换回仿射坐标
+  ///
+  /// 使用 Montgomery 技巧：先求各点 Z 的前缀积，对总积求逆一次，
+  /// 再由后向前依次还原每个 Z 的逆，n 个点只需一次求逆和约 3n 次乘法
+  ///
+  /// 无穷远点不参与乘积，直接转换为仿射坐标的无穷远点
+  fn batch_to_affine<'a>(points: &[Self], field: &JacobianField<'a>) -> Vec<EccPoint<'a>> {
+    let mut prefix = Vec::with_capacity(points.len());
+    let mut product = field.one;
+
+    for point in points {
+      prefix.push(product);
+
+      if !point.z.eq_u64(0) {
+        product = field.mul(product, point.z);
+      }
+    }
+
+    // inv 始终为前 i + 1 个非无穷远点 Z 之积的逆
+    let mut inv = field.inv(product);
+    let mut res = vec![EccPoint::infinity(field.params); points.len()];
+
+    for (i, point) in points.iter().enumerate().rev() {
+      if point.z.eq_u64(0) {
+        continue;
+      }
+
+      res[i] = point.to_affine_with_z_inv(field.mul(inv, prefix[i]), field);
+      inv = field.mul(inv, point.z);
+    }
+
+    res
+  }
+
+  /// # 掩码选择
+  ///
+  /// `choice` 为 1 时选择 `b`，为 0 时选择 `a`
+  fn select(a: Self, b: Self, choice: u64) -> Self {
+    Self {
+      x: conditional_select(a.x, b.x, choice),
+      y: conditional_select(a.y, b.y, choice),
+      z: conditional_select(a.z, b.z, choice)
+    }
+  }
+
+  /// # 掩码交换
+  ///
+  /// `swap` 为 1 时交换两点，为 0 时不变
+  fn conditional_swap(a: &mut Self, b: &mut Self, swap: u64) {
+    let (new_a, new_b) = (Self::select(*a, *b, swap), Self::select(*b, *a, swap));
+
+    *a = new_a;
+    *b = new_b;
+  }
+
+  /// # 倍点
+  ///
+  /// 无穷远点和 y 为 0 的点直接返回无穷远点
+  fn jacobian_double(self, field: &JacobianField) -> Self {
+    if self.z.eq_u64(0) || self.y.eq_u64(0) {
+      return Self::INFINITY;
+    }
+
+    self.jacobian_double_ct(field)
+  }
+
+  /// # 不含分支的倍点
+  ///
+  /// M = 3X^2 + aZ^4，S = 4XY^2，X' = M^2 - 2S，Y' = M(S - X') - 8Y^4，Z' = 2YZ
+  ///
+  /// Z 或 Y 为 0 时 Z' = 2YZ = 0，公式本身即得到无穷远点
+  fn jacobian_double_ct(self, field: &JacobianField) -> Self {
+    let xx = field.mul(self.x, self.x);
+    let yy = field.mul(self.y, self.y);
+    let yyyy = field.mul(yy, yy);
+    let zz = field.mul(self.z, self.z);
+
+    let xyy = field.mul(self.x, yy);
+    let s = field.add(xyy, xyy);
+    let s = field.add(s, s);
+    let m = field.add(field.add(xx, xx), xx);
+    let m = field.add(m, field.mul(field.a, field.mul(zz, zz)));
+
+    let x3 = field.sub(field.mul(m, m), field.add(s, s));
+    let yyyy_8 = field.add(yyyy, yyyy);
+    let yyyy_8 = field.add(yyyy_8, yyyy_8);
+    let yyyy_8 = field.add(yyyy_8, yyyy_8);
+    let y3 = field.sub(field.mul(m, field.sub(s, x3)), yyyy_8);
+    let yz = field.mul(self.y, self.z);
+    let z3 = field.add(yz, yz);
+
+    Self { x: x3, y: y3, z: z3 }
+  }
+
+  /// # 点加
+  ///
+  /// U1 = X1 Z2^2，U2 = X2 Z1^2，S1 = Y1 Z2^3，S2 = Y2 Z1^3
+  ///
+  /// H = U2 - U1，R = S2 - S1
+  ///
+  /// X3 = R^2 - H^3 - 2 U1 H^2，Y3 = R(U1 H^2 - X3) - S1 H^3，Z3 = Z1 Z2 H
+  fn jacobian_add(self, other: Self, field: &JacobianField) -> Self {
+    if self.z.eq_u64(0) {
+      return other;
+    }
+    if other.z.eq_u64(0) {
+      return self;
+    }
+
+    let (sum, h, r) = self.add_formula(other, field);
+
+    if h.eq_u64(0) {
+      // P + P 为倍点，P + (-P) = O
+      return if r.eq_u64(0) { self.jacobian_double(field) } else { Self::INFINITY };
+    }
+
+    sum
+  }
+
+  /// # 不含分支的点加
+  ///
+  /// 对所有输入计算通用公式，再以掩码选择特殊情况的结果：一方为无穷远点时
+  /// 为另一方，两点相等时为 `self_doubled`；两点互为负元时 H = 0，公式得到的
+  /// Z3 = 0 即为无穷远点
+  ///
+  /// ## 参数
+  ///
+  /// * `other` - 另一个点
+  /// * `self_doubled` - `self` 的倍点，由调用者预先计算
+  /// * `field` - 坐标所在的域
+  fn jacobian_add_ct(self, other: Self, self_doubled: Self, field: &JacobianField) -> Self {
+    let (sum, h, r) = self.add_formula(other, field);
+
+    let sum = Self::select(sum, self_doubled, h.ct_is_zero() & r.ct_is_zero() & 1);
+    let sum = Self::select(sum, self, other.z.ct_is_zero() & 1);
+
+    Self::select(sum, other, self.z.ct_is_zero() & 1)
+  }
+
+  /// # 点加的通用公式
+  ///
+  /// ## 返回值
+  ///
+  /// * `(Self, U256, U256)` - 通用公式的结果以及 H、R，两点均不是无穷远点且 H ≠
+  ///   0 时结果正确
+  fn add_formula(self, other: Self, field: &JacobianField) -> (Self, U256, U256) {
+    let z1z1 = field.mul(self.z, self.z);
+    let z2z2 = field.mul(other.z, other.z);
+    let u1 = field.mul(self.x, z2z2);
+    let u2 = field.mul(other.x, z1z1);
+    let s1 = field.mul(self.y, field.mul(other.z, z2z2));
+    let s2 = field.mul(other.y, field.mul(self.z, z1z1));
+
+    let h = field.sub(u2, u1);
+    let r = field.sub(s2, s1);
+    let hh = field.mul(h, h);
+    let hhh = field.mul(h, hh);
+    let v = field.mul(u1, hh);
+
+    let x3 = field.sub(field.sub(field.mul(r, r), hhh), field.add(v, v));
+    let y3 = field.sub(field.mul(r, field.sub(v, x3)), field.mul(s1, hhh));
+    let z3 = field.mul(field.mul(self.z, other.z), h);
+
+    (Self { x: x3, y: y3, z: z3 }, h, r)
+  }
+}
+
+/// # 常量求值的减 1
+///
+/// ## 参数
+///
+/// * `v` - 256 位无符号整数，不应为 0
+///
+/// ## 返回值
+///
+/// * `U256` - v - 1
+const fn sub_one(v: U256) -> U256 {
+  let mut limbs = v.into_le_u64_array();
+
+  let mut i = 0;
+  while i < 4 {
+    let (limb, borrow) = limbs[i].overflowing_sub(1);
+    limbs[i] = limb;
+
+    if !borrow {
+      break;
+    }
+
+    i += 1;
+  }
+
+  U256::from_le_u64_array(&limbs)
+}
+
+/// # 常量求值的加 1
+///
+/// ## 参数
+///
+/// * `v` - 256 位无符号整数，不应为 2^256 - 1
+///
+/// ## 返回值
+///
+/// * `U256` - v + 1
+const fn add_one(v: U256) -> U256 {
+  let mut limbs = v.into_le_u64_array();
+
+  let mut i = 0;
+  while i < 4 {
+    let (limb, carry) = limbs[i].overflowing_add(1);
+    limbs[i] = limb;
+
+    if !carry {
+      break;
+    }
+
+    i += 1;
+  }
+
+  U256::from_le_u64_array(&limbs)
+}
+
+/// # 常量求值的右移 2 位
+///
+/// ## 参数
+///
+/// * `v` - 256 位无符号整数
+///
+/// ## 返回值
+///
+/// * `U256` - ⌊v / 4⌋
+const fn shr_two(v: U256) -> U256 {
+  let limbs = v.into_le_u64_array();
+  let mut result = [0u64; 4];
+
+  let mut i = 0;
+  while i < 4 {
+    result[i] = limbs[i] >> 2;
+
+    if i < 3 {
+      result[i] |= limbs[i + 1] << 62;
+    }
+
+    i += 1;
+  }
+
+  U256::from_le_u64_array(&result)
+}
+
+/// # 椭圆曲线点结构体
+///
+/// ## 成员
+///
+/// * `x` - 椭圆曲线点 x 坐标；无穷远点为 0
+/// * `y` - 椭圆曲线点 y 坐标；无穷远点为 0
+/// * `params` - 椭圆曲线参数结构体的引用
+/// * `infinity` - 椭圆曲线点是否为无穷远点
+///
+/// ## 构造方法
+///
+/// * `let new_ecc_point = old_ecc_point` - 复制一个椭圆曲线点
+/// * `EccPoint { x, y, params, infinity }` - 直接初始化，不推荐
+/// * `EccPoint::new(x, y, params, infinity)` - 创建一个椭圆曲线点
+/// * `EccPoint::infinity(params)` - 创建一个无穷远点
+/// * `EccPoint::new_simple(x, y, params)` - 创建一个非无穷远椭圆曲线点
+///
+/// ## 实现特征
+///
+/// * `Clone`
+/// * `Copy`
+/// * `PartialEq`
+/// * `Eq`
+/// * `EccOps` - 椭圆曲线相关运算
+/// * `Add` - 等价于 `ecc_add`，使用自身携带的参数
+/// * `AddAssign`
+/// * `Sub` - 等价于 `ecc_sub`，使用自身携带的参数
+/// * `SubAssign`
+/// * `Neg` - 等价于 `negate`
+/// * `Mul<U256>` - 等价于 `ecc_mul`，使用自身携带的参数
+/// * `MulAssign<U256>`
+/// * `From<EccPoint<'a>> -> Vec<u8>`
+/// * `From<EccPoint<'a>> -> BitSequence`
+/// * `Serialize` - 启用 `serde` 特性时可用
+///
+/// ## 方法
+///
+/// * `from_bytes(bytes: &[u8; 65], params: &'a EccParams) -> Result<Self,
+///   Error>` - 从大端序的未压缩编码 0x04 || x || y 构造椭圆曲线点，前缀字节不为
+///   0x04 时返回错误
+/// * `try_from_bytes(bytes: &[u8], params: &'a EccParams) -> Result<Self,
+///   Error>` - 从任意长度的字节序列构造椭圆曲线点，支持无穷远点的单字节 `0x00`
+///   编码
+/// * `try_from_bytes_with_order(bytes: &[u8], params: &'a EccParams, order:
+///   ByteOrder) -> Result<Self, Error>` - 同上，坐标使用指定字节序
+/// * `to_bytes_with_order(self, order: ByteOrder) -> Vec<u8>` -
+///   编码为字节序列，坐标使用指定字节序；`From<EccPoint> -> Vec<u8>` 使用大端序
+/// * `validate_on_curve(self) -> bool` - 验证椭圆曲线点是否在曲线上
+/// * `to_compressed_bytes(self) -> [u8; 33]` - 编码为 0x02/0x03 || x
+///   的压缩形式，无穷远点编码为 33 个 0x00
+/// * `from_compressed_bytes(bytes: &[u8; 33], params: &'a EccParams) ->
+///   Result<Self, Error>` - 从压缩形式恢复椭圆曲线点，要求 p ≡ 3 (mod 4)
+/// * `double(self) -> Self` - 倍点运算，`y` 为 0 时返回无穷远点
+/// * `negate(self) -> Self` - 负元 (x, p - y)，无穷远点不变
+/// * `order(&self) -> U256` - 返回曲线参数中生成元的阶 n
+/// * `msm(points: &[EccPoint<'a>], scalars: &[U256], params: &'a EccParams) ->
+///   Result<Self, Error>` - 多标量乘法 Σ [k_i]P_i，项数较多时使用 Pippenger
+///   算法，长度不一致或参数不兼容时返回错误
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct EccPoint<'a> {
+  pub x: U256,
+  pub y: U256,
+  pub params: &'a EccParams,
+  pub infinity: bool
+}
+
+impl<'a> EccPoint<'a> {
+  pub fn new(x: U256, y: U256, params: &'a EccParams, infinity: bool) -> Self {
+    if infinity {
+      Self { x: U256::C_0, y: U256::C_0, params, infinity }
+    } else {
+      Self { x, y, params, infinity: false }
+    }
+  }
+
+  pub fn infinity(params: &'a EccParams) -> Self {
+    Self { x: U256::C_0, y: U256::C_0, params, infinity: true }
+  }
+
+  pub fn new_simple(x: U256, y: U256, params: &'a EccParams) -> Self {
+    Self::new(x, y, params, false)
+  }
+
+  pub fn negate(self) -> Self {
+    if self.infinity {
+      return self;
+    }
+
+    // -(x, y) = (x, p - y)
+    Self::new_simple(self.x, self.y.mod_neg(self.params.p), self.params)
+  }
+
+  pub fn from_bytes(bytes: &[u8; 65], params: &'a EccParams) -> Result<Self, Error> {
+    Self::from_bytes_with_order(bytes, params, ByteOrder::BigEndian)
+  }
+
+  pub fn try_from_bytes(bytes: &[u8], params: &'a EccParams) -> Result<Self, Error> {
+    Self::try_from_bytes_with_order(bytes, params, ByteOrder::BigEndian)
+  }
+
+  pub fn try_from_bytes_with_order(
+    bytes: &[u8],
+    params: &'a EccParams,
+    order: ByteOrder
+  ) -> Result<Self, Error> {
+    match bytes {
+      [0x00] => Ok(Self::infinity(params)),
+      _ => {
+        match bytes.try_into() {
+          Ok(bytes) => Self::from_bytes_with_order(bytes, params, order),
+          Err(_) => Err(Error::InvalidLength)
+        }
+      },
+    }
+  }
+
+  pub fn to_bytes_with_order(self, order: ByteOrder) -> Vec<u8> {
+    // 无穷远点编码为单字节 0x00
+    if self.infinity {
+      return vec![0x00];
+    }
+
+    let mut result = Vec::with_capacity(65);
+
+    result.push(0x04);
+    result.extend_from_slice(&self.x.into_bytes_with_order(order));
+    result.extend_from_slice(&self.y.into_bytes_with_order(order));
+
+    result
+  }
+
+  fn from_bytes_with_order(
+    bytes: &[u8; 65],
+    params: &'a EccParams,
+    order: ByteOrder
+  ) -> Result<Self, Error> {
+    // 仅接受未压缩编码，压缩编码应使用 from_compressed_bytes
+    if bytes[0] != 0x04 {
+      return Err(Error::InvalidPoint);
+    }
+
+    let x = U256::from_bytes_with_order(bytes[1 .. 33].try_into().unwrap(), order);
+    let y = U256::from_bytes_with_order(bytes[33 .. 65].try_into().unwrap(), order);
+
+    Ok(Self::new_simple(x, y, params))
+  }
+
+  pub fn validate_on_curve(self) -> bool {
+    // y^2 = x^3 + ax + b (mod p)
+    let params = self.params;
+
+    self.infinity || params.mul_mod_p(self.y, self.y) == params.curve_rhs(self.x)
+  }
+
+  pub fn to_compressed_bytes(self) -> [u8; 33] {
+    let mut result = [0u8; 33];
+
+    // 无穷远点编码为 33 个 0x00
+    if self.infinity {
+      return result;
+    }
+
+    // 0x02 || x 表示 y 为偶数，0x03 || x 表示 y 为奇数
+    result[0] = 0x02 | (self.y.into_le_u64_array()[0] & 1) as u8;
+    result[1 ..].copy_from_slice(&self.x.into_be_bytes());
+
+    result
+  }
+
+  pub fn from_compressed_bytes(bytes: &[u8; 33], params: &'a EccParams) -> Result<Self, Error> {
+    let y_is_odd = match bytes[0] {
+      0x00 if bytes[1 ..].iter().all(|b| *b == 0) => return Ok(Self::infinity(params)),
+      0x02 => false,
+      0x03 => true,
+      _ => return Err(Error::InvalidPoint)
+    };
+
+    let x = U256::from_be_bytes(bytes[1 ..].try_into().unwrap());
+
+    if x >= params.p {
+      return Err(Error::InvalidPoint);
+    }
+
+    let y = params.sqrt_mod_p(params.curve_rhs(x))?.ok_or(Error::InvalidPoint)?;
+
+    // 两个平方根互为相反数，奇偶性相反；选择与前缀一致的一个
+    let y = if (y.into_le_u64_array()[0] & 1 == 1) == y_is_odd {
+      y
+    } else if y.eq_u64(0) {
+      return Err(Error::InvalidPoint);
+    } else {
+      params.p - y
+    };
+
+    Ok(Self::new_simple(x, y, params))
+  }
+
+  pub fn double(self) -> Self {
+    let params = self.params;
+
+    // 无穷远点的倍点为自身；y = 0 时切线垂直于 x 轴，倍点为无穷远点
+    if self.infinity || self.y.eq_u64(0) {
+      return EccPoint::infinity(params);
+    }
+
+    // λ = (3 * x1 ^ 2 + a) / (2 * y1)
+    let x_squared = params.mul_mod_p(self.x, self.x);
+    let num = x_squared
+      .mod_add(x_squared, params.p)
+      .mod_add(x_squared, params.p)
+      .mod_add(params.a, params.p);
+    let denom = self.y.mod_add(self.y, params.p);
+
+    let lambda = params.mul_mod_p(num, denom.mod_inv(params.p).unwrap());
+
+    self.chord_tangent_result(self.x, lambda)
+  }
+
+  /// # 由斜率计算点加结果
+  ///
+  /// x3 = λ ^ 2 - x1 - x2，y3 = λ * (x1 - x3) - y1
+  ///
+  /// ## 参数
+  ///
+  /// * `other_x` - 另一个点的 x 坐标，倍点时为自身的 x 坐标
+  /// * `lambda` - 割线或切线的斜率
+  ///
+  /// ## 返回值
+  ///
+  /// * `Self` - 椭圆曲线点
+  fn chord_tangent_result(self, other_x: U256, lambda: U256) -> Self {
+    let params = self.params;
+
+    let x3 = params
+      .mul_mod_p(lambda, lambda)
+      .mod_add(self.x.mod_neg(params.p), params.p)
+      .mod_add(other_x.mod_neg(params.p), params.p);
+    let y3 = params
+      .mul_mod_p(lambda, self.x.mod_add(x3.mod_neg(params.p), params.p))
+      .mod_add(self.y.mod_neg(params.p), params.p);
+
+    EccPoint::new_simple(x3, y3, params)
+  }
+
+  pub fn order(&self) -> U256 {
+    self.params.n
+  }
+
+  pub fn validate_on_given_curve(self, params: &EccParams) -> bool {
+    self.params == params && self.validate_on_curve()
+  }
+
+  pub fn msm(
+    points: &[EccPoint<'a>],
+    scalars: &[U256],
+    params: &'a EccParams
+  ) -> Result<Self, Error> {
+    if points.len() != scalars.len() {
+      return Err(Error::InvalidLength);
+    }
+
+    if points.iter().any(|point| point.params != params) {
+      return Err(Error::IncompatibleParams);
+    }
+
+    if points.len() < MSM_PIPPENGER_THRESHOLD {
+      Ok(Self::msm_interleaved(points, scalars, params))
+    } else {
+      Ok(Self::msm_pippenger(points, scalars, params))
+    }
+  }
+}
+
+/// # 计算数乘因子的宽度为 w 的 NAF 表示
+///
+/// 每一位为 0 或绝对值小于 2^(w-1) 的奇数，任意 w 个相邻位中至多一位非零
+///
+/// ## 参数
+///
+/// * `k` - 数乘因子
+/// * `window` - 窗口宽度 w
+///
+/// ## 返回值
+///
+/// * `Vec<i8>` - 由低位到高位的各位，至多 257 位
+fn wnaf_digits(k: U256, window: usize) -> Vec<i8> {
+  // 多留一个字，取负数位时 k 加上 2^w - d 可能向第 257 位进位
+  let mut limbs = [0u64; 5];
+  limbs[.. 4].copy_from_slice(&k.into_le_u64_array());
+  let width = 1u64 << window;
+  let mut digits = Vec::with_capacity(257);
+
+  while limbs.iter().any(|&limb| limb != 0) {
+    let mut digit = 0i8;
+
+    if limbs[0] & 1 == 1 {
+      let low = limbs[0] & (width - 1);
+
+      if low >= width / 2 {
+        digit = low as i8 - width as i8;
+        let mut carry = width - low;
+        for limb in limbs.iter_mut() {
+          let (sum, overflow) = limb.overflowing_add(carry);
+          *limb = sum;
+          carry = overflow as u64;
+        }
+      } else {
+        digit = low as i8;
+        limbs[0] -= low;
+      }
+    }
+
+    digits.push(digit);
+
+    for i in 0 .. 4 {
+      limbs[i] = limbs[i] >> 1 | limbs[i + 1] << 63;
+    }
+    limbs[4] >>= 1;
+  }
+
+  digits
+}
+
+/// # 多标量乘法改用 Pippenger 算法的最小项数
+///
+/// 项数较少时，桶的累加开销超过节省的倍点运算，使用逐位交错的数乘更快
+const MSM_PIPPENGER_THRESHOLD: usize = 8;
+
+/// # 椭圆曲线相关运算
+///
+/// ## 方法
+///
+/// * `ecc_add(self, other: Self, params: &'a EccParams) -> Self` - 椭圆曲线加法
+/// * `ecc_sub(self, other: Self, params: &'a EccParams) -> Self` - 椭圆曲线减法
+/// * `ecc_mul(self, k: U256, params: &'a EccParams) -> Self` - 椭圆曲线数乘
+/// * `ecc_mul_signed(self, k: U256, negate: bool, params: &'a EccParams) ->
+///   Self` - 带符号的椭圆曲线数乘
+/// * `ecc_mul_with(self, k: U256, params: &'a EccParams, strategy: MulStrategy)
+///   -> Self` - 使用指定算法的椭圆曲线数乘
+/// * `ecc_mul_ct(self, k: U256, params: &'a EccParams) -> Self` -
+///   常数时间的椭圆曲线数乘
+/// * `ecc_mul_double(self, a: U256, q: Self, b: U256, params: &'a EccParams) ->
+///   Self` - 双标量乘法 [a]P + [b]Q
+/// * `ecc_mul_wnaf(self, k: U256, params: &'a EccParams, window: usize) ->
+///   Self`
+///   - w-NAF 椭圆曲线数乘
+pub trait EccOps<'a> {
+  /// # 椭圆曲线加法
+  ///
+  /// ## 参数
+  ///
+  /// * `self` - 椭圆曲线点
+  /// * `other` - 椭圆曲线点
+  /// * `params` - 椭圆曲线参数结构体的引用
+  ///
+  /// ## 返回值
+  ///
+  /// * `Self` - 椭圆曲线点
+  ///
+  /// ## 特殊情况
+  ///
+  /// * 当两个椭圆曲线点参数不兼容时，崩溃
+  /// * 当 `self` 和 `other` 中有一个为无穷远点时，返回另一个点
+  /// * 当 `self` 与 `other` 相同时，进行倍点运算；`y` 为 0 时返回无穷远点
+  /// * 当 `self` 与 `other` 互为负元时，返回无穷远点
+  fn ecc_add(self, other: Self, params: &'a EccParams) -> Self;
+
+  /// # 椭圆曲线减法
+  ///
+  /// 计算 `self + (-other)`
+  ///
+  /// ## 参数
+  ///
+  /// * `self` - 椭圆曲线点
+  /// * `other` - 椭圆曲线点
+  /// * `params` - 椭圆曲线参数结构体的引用
+  ///
+  /// ## 返回值
+  ///
+  /// * `Self` - 椭圆曲线点
+  ///
+  /// ## 特殊情况
+  ///
+  /// * 当两个椭圆曲线点参数不兼容时，崩溃
+  /// * 当 `self` 与 `other` 相同时，返回无穷远点
+  fn ecc_sub(self, other: Self, params: &'a EccParams) -> Self;
+
+  /// # 椭圆曲线数乘
+  ///
+  /// ## 参数
+  ///
+  /// * `self` - 椭圆曲线点
+  /// * `k` - 数乘因子
+  /// * `params` - 椭圆曲线参数结构体的引用
+  ///
+  /// ## 返回值
+  ///
+  /// * `Self` - 椭圆曲线点
+  ///
+  /// ## 特殊情况
+  ///
+  /// * 当 `k` 为 0 或 `self` 为无穷远点时，返回无穷远点
+  /// * `k` 不做模 `n` 约减，但由于 `[n]P` 为无穷远点，结果与 `[k mod n]P` 一致
+  fn ecc_mul(self, k: U256, params: &'a EccParams) -> Self;
+
+  /// # 带符号的椭圆曲线数乘
+  ///
+  /// 计算 `[k]P`，当 `negate` 为真时计算 `[-k]P`，即 `[k]P` 的负元
+  ///
+  /// ## 参数
+  ///
+  /// * `self` - 椭圆曲线点
+  /// * `k` - 数乘因子的绝对值
+  /// * `negate` - 数乘因子是否为负
+  /// * `params` - 椭圆曲线参数结构体的引用
+  ///
+  /// ## 返回值
+  ///
+  /// * `Self` - 椭圆曲线点
+  ///
+  /// ## 特殊情况
+  ///
+  /// * 当 `[k]P` 为无穷远点时，返回无穷远点
+  fn ecc_mul_signed(self, k: U256, negate: bool, params: &'a EccParams) -> Self;
+
+  /// # 使用指定算法的椭圆曲线数乘
+  ///
+  /// ## 参数
+  ///
+  /// * `self` - 椭圆曲线点
+  /// * `k` - 数乘因子
+  /// * `params` - 椭圆曲线参数结构体的引用
+  /// * `strategy` - 数乘算法
+  ///
+  /// ## 返回值
+  ///
+  /// * `Self` - 椭圆曲线点，与 `ecc_mul` 的结果一致
+  fn ecc_mul_with(self, k: U256, params: &'a EccParams, strategy: MulStrategy) -> Self;
+
+  /// # 常数时间的椭圆曲线数乘
+  ///
+  /// 在 Jacobian 坐标下使用 Montgomery 阶梯，每一位都进行一次点加和一次倍点，
+  /// 以掩码交换选择累加点，点加的特殊情况同样以掩码选择；转换回仿射坐标时
+  /// 使用指数公开的费马求逆，运算次序与数乘因子无关，适用于私钥、
+  /// 随机数等秘密数乘因子
+  ///
+  /// ## 参数
+  ///
+  /// * `self` - 椭圆曲线点
+  /// * `k` - 数乘因子
+  /// * `params` - 椭圆曲线参数结构体的引用
+  ///
+  /// ## 返回值
+  ///
+  /// * `Self` - 椭圆曲线点，与 `ecc_mul` 的结果一致
+  fn ecc_mul_ct(self, k: U256, params: &'a EccParams) -> Self;
+
+  /// # 双标量乘法
+  ///
+  /// 使用 Shamir 技巧计算 `[a]P + [b]Q`，两个数乘共享同一串倍点运算，
+  /// 并预计算 `P + Q`，每一位至多进行一次点加；运算在 Jacobian
+  /// 坐标下进行，最后只求逆一次
+  ///
+  /// ## 参数
+  ///
+  /// * `self` - 椭圆曲线点 P
+  /// * `a` - P 的数乘因子
+  /// * `q` - 椭圆曲线点 Q
+  /// * `b` - Q 的数乘因子
+  /// * `params` - 椭圆曲线参数结构体的引用
+  ///
+  /// ## 返回值
+  ///
+  /// * `Self` - 椭圆曲线点，与 `[a]P` 和 `[b]Q` 分别数乘后相加的结果一致
+  ///
+  /// ## 特殊情况
+  ///
+  /// * P、Q 为无穷远点或 `a`、`b` 为 0 时，对应的项为无穷远点
+  fn ecc_mul_double(self, a: U256, q: Self, b: U256, params: &'a EccParams) -> Self;
+
+  /// # w-NAF 椭圆曲线数乘
+  ///
+  /// 预计算奇数倍点 `[1]P, [3]P, ..., [2^(w-1) - 1]P`，将数乘因子转换为宽度为 w
+  /// 的 NAF 表示后从高位起处理，相邻非零位之间至少间隔 w - 1 个零，
+  /// 点加次数约为 256 / (w + 1)
+  ///
+  /// 逐位的 `ecc_mul` 平均约需 128 次点加，w = 4 时约 51 次，w = 6 时约 37 次
+  ///
+  /// 预计算表另需 1 次倍点和 2^(w-2) - 1 次点加，w = 4 时合计节省约 70 次点加
+  ///
+  /// ## 参数
+  ///
+  /// * `self` - 椭圆曲线点
+  /// * `k` - 数乘因子
+  /// * `params` - 椭圆曲线参数结构体的引用
+  /// * `window` - 窗口宽度 w，取值范围为 2 至 6
+  ///
+  /// ## 返回值
+  ///
+  /// * `Self` - 椭圆曲线点，与 `ecc_mul` 的结果一致
+  ///
+  /// ## 特殊情况
+  ///
+  /// * 当 `window` 不在 2 至 6 之间时，崩溃
+  fn ecc_mul_wnaf(self, k: U256, params: &'a EccParams, window: usize) -> Self;
+}
+
+/// # 椭圆曲线数乘算法
+///
+/// ## 变体
+///
+/// * `Windowed` - 4 位固定窗口法，速度较快，但运算过程与数乘因子相关，
+///   仅适用于公开的数乘因子，如签名验证
+/// * `ConstantTimeLadder` - Montgomery 阶梯，每一位均进行一次点加和一次倍点，
+///   使用掩码交换代替分支，适用于私钥等秘密的数乘因子
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MulStrategy {
+  Windowed,
+  ConstantTimeLadder
+}
+
+impl<'a> EccOps<'a> for EccPoint<'a> {
+  fn ecc_add(self, other: Self, params: &'a EccParams) -> Self {
+    if self.params != other.params {
+      panic!("Incompatible elliptic curve parameters");
+    }
+
+    match (self.infinity, other.infinity) {
+      (true, _) => return other,
+      (_, true) => return self,
+      (false, false) => {}
+    }
+
+    if self.x == other.x {
+      // P + P 为倍点，P + (-P) = O
+      return if self.y == other.y { self.double() } else { EccPoint::infinity(params) };
+    }
+
+    // λ = (y2 - y1) / (x2 - x1)
+    let num = other.y.mod_add(self.y.mod_neg(params.p), params.p);
+    let denom = other.x.mod_add(self.x.mod_neg(params.p), params.p);
+
+    let lambda = params.mul_mod_p(num, denom.mod_inv(params.p).unwrap());
+
+    self.chord_tangent_result(other.x, lambda)
+  }
+
+  fn ecc_sub(self, other: Self, params: &'a EccParams) -> Self {
+    self.ecc_add(other.negate(), params)
+  }
+
+  fn ecc_mul(self, k: U256, params: &'a EccParams) -> Self {
+    if k.eq_u64(0) || self.infinity {
+      return EccPoint::infinity(params);
+    }
+
+    let field = JacobianField::new(params);
+    let mut res = JacobianPoint::INFINITY;
+    let mut addend = JacobianPoint::from_affine(self, &field);
+    let k_words = k.into_le_u64_array();
+
+    for i in 0 .. 256 {
+      if (k_words[i / 64] >> (i % 64)) & 1 == 1 {
+        res = res.jacobian_add(addend, &field);
+      }
+      addend = addend.jacobian_double(&field);
+    }
+
+    res.to_affine(&field)
+  }
+
+  fn ecc_mul_signed(self, k: U256, negate: bool, params: &'a EccParams) -> Self {
+    let res = self.ecc_mul(k, params);
+
+    if negate {
+      res.negate()
+    } else {
+      res
+    }
+  }
+
+  fn ecc_mul_with(self, k: U256, params: &'a EccParams, strategy: MulStrategy) -> Self {
+    match strategy {
+      MulStrategy::Windowed => self.ecc_mul_windowed(k, params),
+      MulStrategy::ConstantTimeLadder => self.ecc_mul_ladder(k, params)
+    }
+  }
+
+  fn ecc_mul_ct(self, k: U256, params: &'a EccParams) -> Self {
+    self.ecc_mul_ladder(k, params)
+  }
+
+  fn ecc_mul_double(self, a: U256, q: Self, b: U256, params: &'a EccParams) -> Self {
+    let field = JacobianField::new(params);
+    let p = JacobianPoint::from_affine(self, &field);
+    let q = JacobianPoint::from_affine(q, &field);
+
+    // 预计算 P + Q，每一位至多做一次加法
+    let table = [p, q, p.jacobian_add(q, &field)];
+    let a_words = a.into_le_u64_array();
+    let b_words = b.into_le_u64_array();
+    let mut res = JacobianPoint::INFINITY;
+
+    for i in (0 .. 256).rev() {
+      res = res.jacobian_double(&field);
+
+      let bit_a = (a_words[i / 64] >> (i % 64)) & 1;
+      let bit_b = (b_words[i / 64] >> (i % 64)) & 1;
+
+      match bit_a | bit_b << 1 {
+        0 => {},
+        index => res = res.jacobian_add(table[index as usize - 1], &field)
+      }
+    }
+
+    res.to_affine(&field)
+  }
+
+  fn ecc_mul_wnaf(self, k: U256, params: &'a EccParams, window: usize) -> Self {
+    assert!((2 ..= 6).contains(&window), "window must be between 2 and 6");
+
+    if k.eq_u64(0) || self.infinity {
+      return EccPoint::infinity(params);
+    }
+
+    let field = JacobianField::new(params);
+
+    // table[i] = [2i + 1]P
+    let point = JacobianPoint::from_affine(self, &field);
+    let twice = point.jacobian_double(&field);
+    let mut table = vec![point; 1 << (window - 2)];
+    for i in 1 .. table.len() {
+      table[i] = table[i - 1].jacobian_add(twice, &field);
+    }
+
+    let mut res = JacobianPoint::INFINITY;
+
+    for digit in wnaf_digits(k, window).into_iter().rev() {
+      res = res.jacobian_double(&field);
+
+      if digit > 0 {
+        res = res.jacobian_add(table[digit as usize / 2], &field);
+      } else if digit < 0 {
+        res = res.jacobian_add(table[digit.unsigned_abs() as usize / 2].negate(&field), &field);
+      }
+    }
+
+    res.to_affine(&field)
+  }
+}
+
+impl<'a> EccPoint<'a> {
+  /// # 4 位固定窗口法数乘
+  ///
+  /// 预计算 `[0]P` 至 `[15]P`，从高位起每次处理 4 位，在 Jacobian 坐标下累加
+  fn ecc_mul_windowed(self, k: U256, params: &'a EccParams) -> Self {
+    let field = JacobianField::new(params);
+    let point = JacobianPoint::from_affine(self, &field);
+    let mut table = [JacobianPoint::INFINITY; 16];
+
+    for i in 1 .. 16 {
+      table[i] = table[i - 1].jacobian_add(point, &field);
+    }
+
+    let k_words = k.into_le_u64_array();
+    let mut res = JacobianPoint::INFINITY;
+
+    for i in (0 .. 64).rev() {
+      for _ in 0 .. 4 {
+        res = res.jacobian_double(&field);
+      }
+
+      let window = (k_words[i / 16] >> (i % 16 * 4)) & 0xf;
+      res = res.jacobian_add(table[window as usize], &field);
+    }
+
+    res.to_affine(&field)
+  }
+
+  /// # 逐位交错的多标量乘法
+  ///
+  /// 即 Shamir 技巧的推广，所有项共享同一串倍点运算，只需 256 次倍点
+  fn msm_interleaved(points: &[Self], scalars: &[U256], params: &'a EccParams) -> Self {
+    let field = JacobianField::new(params);
+    let points: Vec<_> =
+      points.iter().map(|point| JacobianPoint::from_affine(*point, &field)).collect();
+    let scalar_words: Vec<[u64; 4]> = scalars.iter().map(|k| k.into_le_u64_array()).collect();
+    let mut res = JacobianPoint::INFINITY;
+
+    for i in (0 .. 256).rev() {
+      res = res.jacobian_double(&field);
+
+      for (point, words) in points.iter().zip(&scalar_words) {
+        if (words[i / 64] >> (i % 64)) & 1 == 1 {
+          res = res.jacobian_add(*point, &field);
+        }
+      }
+    }
+
+    res.to_affine(&field)
+  }
+
+  /// # Pippenger 桶算法的多标量乘法
+  ///
+  /// 将数乘因子按 c 位分窗，每个窗口内把点按窗口值放入 2^c - 1 个桶中，
+  /// 再通过累加和一次性求出 Σ j * B_j；窗口之间以 c 次倍点连接，
+  /// 桶与累加和均保存为 Jacobian 坐标
+  fn msm_pippenger(points: &[Self], scalars: &[U256], params: &'a EccParams) -> Self {
+    let c = points.len().ilog2() as usize;
+    let field = JacobianField::new(params);
+    let points: Vec<_> =
+      points.iter().map(|point| JacobianPoint::from_affine(*point, &field)).collect();
+    let scalar_words: Vec<[u64; 4]> = scalars.iter().map(|k| k.into_le_u64_array()).collect();
+    let mut res = JacobianPoint::INFINITY;
+
+    for window in (0 .. 256usize.div_ceil(c)).rev() {
+      for _ in 0 .. c {
+        res = res.jacobian_double(&field);
+      }
+
+      let mut buckets = vec![JacobianPoint::INFINITY; (1 << c) - 1];
+
+      for (point, words) in points.iter().zip(&scalar_words) {
+        let digit = window_digit(words, window * c, c);
+
+        if digit != 0 {
+          buckets[digit - 1] = buckets[digit - 1].jacobian_add(*point, &field);
+        }
+      }
+
+      // 从最高的桶向下累加：running 为 B_j 至 B_max 之和，window_sum 累计得到 Σ j *
+      // B_j
+      let mut running = JacobianPoint::INFINITY;
+      let mut window_sum = JacobianPoint::INFINITY;
+
+      for bucket in buckets.into_iter().rev() {
+        running = running.jacobian_add(bucket, &field);
+        window_sum = window_sum.jacobian_add(running, &field);
+      }
+
+      res = res.jacobian_add(window_sum, &field);
+    }
+
+    res.to_affine(&field)
+  }
+
+  /// # Montgomery 阶梯数乘
+  ///
+  /// 在 Jacobian 坐标下始终保持 `r1 - r0 = P`，每一位根据该位的值以掩码交换两者
+  ///
+  /// 点加与倍点使用不含分支的公式，最后只求逆一次，运算次序与数乘因子无关
+  fn ecc_mul_ladder(self, k: U256, params: &'a EccParams) -> Self {
+    let field = JacobianField::new(params);
+    let mut r0 = JacobianPoint::INFINITY;
+    let mut r1 = JacobianPoint::from_affine(self, &field);
+    let k_words = k.into_le_u64_array();
+
+    for i in (0 .. 256).rev() {
+      let bit = (k_words[i / 64] >> (i % 64)) & 1;
+
+      // bit 为 1 时交换，使下面的运算统一为 r1 = r0 + r1，r0 = 2r0
+      JacobianPoint::conditional_swap(&mut r0, &mut r1, bit);
+      let doubled = r0.jacobian_double_ct(&field);
+      r1 = r0.jacobian_add_ct(r1, doubled, &field);
+      r0 = doubled;
+      JacobianPoint::conditional_swap(&mut r0, &mut r1, bit);
+    }
+
+    r0.to_affine(&field)
+  }
+
+  /// # 构造固定基点的预计算表
+  ///
+  /// 第 i 行第 j 项为 [j * 2^(8i)]P，在 Jacobian 坐标下逐项累加，
+  /// 再以批量求逆统一转换为仿射坐标，整张表只求逆一次
+  ///
+  /// ## 参数
+  ///
+  /// * `params` - 椭圆曲线参数结构体的引用
+  ///
+  /// ## 返回值
+  ///
+  /// * `Vec<[Self; 256]>` - 32 行预计算表，每行第 0 项为无穷远点
+  pub(crate) fn fixed_base_table(self, params: &'a EccParams) -> Vec<[Self; 256]> {
+    let field = JacobianField::new(params);
+    let mut base = JacobianPoint::from_affine(self, &field);
+    let mut points = Vec::with_capacity(32 * 256);
+
+    for _ in 0 .. 32 {
+      let mut entry = JacobianPoint::INFINITY;
+
+      for _ in 0 .. 256 {
+        points.push(entry);
+        entry = entry.jacobian_add(base, &field);
+      }
+
+      // 循环结束时 entry 为 [256]base，即下一行的基点
+      base = entry;
+    }
+
+    JacobianPoint::batch_to_affine(&points, &field)
+      .chunks_exact(256)
+      .map(|row| row.try_into().unwrap())
+      .collect()
+  }
+
+  /// # 常数时间的固定基点数乘
+  ///
+  /// 数乘因子按字节拆分，每行遍历全部 256 项并以掩码选出对应的一项，
+  /// 再以不含分支的点加累加，访存模式与运算次序均与数乘因子无关
+  ///
+  /// ## 参数
+  ///
+  /// * `table` - 由 `fixed_base_table` 构造的预计算表
+  /// * `k` - 数乘因子
+  /// * `params` - 椭圆曲线参数结构体的引用
+  ///
+  /// ## 返回值
+  ///
+  /// * `Self` - 椭圆曲线点，与基点 `ecc_mul` 的结果一致
+  pub(crate) fn fixed_base_mul(table: &[[Self; 256]], k: U256, params: &'a EccParams) -> Self {
+    let field = JacobianField::new(params);
+    let mut res = JacobianPoint::INFINITY;
+
+    for (row, byte) in table.iter().zip(k.into_le_bytes()) {
+      let (mut x, mut y) = (U256::C_0, U256::C_0);
+
+      for (j, entry) in row.iter().enumerate() {
+        // j 与 byte 相等时异或为 0，减 1 后最高位为 1
+        let choice = (j as u64 ^ byte as u64).wrapping_sub(1) >> 63;
+        x = conditional_select(x, entry.x, choice);
+        y = conditional_select(y, entry.y, choice);
+      }
+
+      // 第 0 项为无穷远点，以 Z = 0 表示
+      let is_zero = (byte as u64).wrapping_sub(1) >> 63;
+      let point = JacobianPoint {
+        x: field.encode(x),
+        y: field.encode(y),
+        z: conditional_select(field.one, U256::C_0, is_zero)
+      };
+      let doubled = res.jacobian_double_ct(&field);
+      res = res.jacobian_add_ct(point, doubled, &field);
+    }
+
+    res.to_affine(&field)
+  }
+}
+
+/// # 取数乘因子中的一个窗口
+///
+/// ## 参数
+///
+/// * `words` - 小端序的数乘因子
+/// * `start` - 窗口最低位的位置
+/// * `width` - 窗口宽度，不超过 64
+///
+/// ## 返回值
+///
+/// * `usize` - 第 `start` 位起 `width` 位的值，超出 256 位的部分视为 0
+fn window_digit(words: &[u64; 4], start: usize, width: usize) -> usize {
+  let mut digit = 0;
+
+  for bit in (start .. (start + width).min(256)).rev() {
+    digit = digit << 1 | ((words[bit / 64] >> (bit % 64)) & 1) as usize;
+  }
+
+  digit
+}
+
+/// # `U256` 的掩码选择
+///
+/// ## 参数
+///
+/// * `a` - 候选值
+/// * `b` - 候选值
+/// * `choice` - 为 1 时选择 `b`，为 0 时选择 `a`
+///
+/// ## 返回值
+///
+/// * `U256` - 选择结果
+fn conditional_select(a: U256, b: U256, choice: u64) -> U256 {
+  let mask = choice.wrapping_neg();
+  let a_words = a.into_le_u64_array();
+  let b_words = b.into_le_u64_array();
+  let mut words = [0u64; 4];
+
+  for i in 0 .. 4 {
+    words[i] = a_words[i] ^ ((a_words[i] ^ b_words[i]) & mask);
+  }
+
+  U256::from_le_u64_array(&words)
+}
+
+/// # `U256` 带进位加法
+///
+/// ## 返回值
+///
+/// * `(U256, u64)` - 和对 2^256 取模的结果，以及进位（0 或 1）
+fn add_with_carry(a: U256, b: U256) -> (U256, u64) {
+  let a_words = a.into_le_u64_array();
+  let b_words = b.into_le_u64_array();
+  let mut words = [0u64; 4];
+  let mut carry = 0u64;
+
+  for i in 0 .. 4 {
+    let v = a_words[i] as u128 + b_words[i] as u128 + carry as u128;
+    words[i] = v as u64;
+    carry = (v >> 64) as u64;
+  }
+
+  (U256::from_le_u64_array(&words), carry)
+}
+
+/// # `U256` 带借位减法
+///
+/// ## 返回值
+///
+/// * `(U256, u64)` - 差对 2^256 取模的结果，以及借位（0 或 1）
+fn sub_with_borrow(a: U256, b: U256) -> (U256, u64) {
+  let a_words = a.into_le_u64_array();
+  let b_words = b.into_le_u64_array();
+  let mut words = [0u64; 4];
+  let mut borrow = 0u64;
+
+  for i in 0 .. 4 {
+    let v = (a_words[i] as u128).wrapping_sub(b_words[i] as u128 + borrow as u128);
+    words[i] = v as u64;
+    borrow = (v >> 127) as u64;
+  }
+
+  (U256::from_le_u64_array(&words), borrow)
+}
+
+impl<'a> ops::Add for EccPoint<'a> {
+  type Output = Self;
+
+  fn add(self, other: Self) -> Self {
+    self.ecc_add(other, self.params)
+  }
+}
 
-    let denom_inv = denom.mod_inv(params.p).unwrap();
+impl<'a> ops::AddAssign for EccPoint<'a> {
+  fn add_assign(&mut self, other: Self) {
+    *self = *self + other;
+  }
+}
 
-    let lambda = num.mod_mul(denom_inv, params.p);
+impl<'a> ops::Sub for EccPoint<'a> {
+  type Output = Self;
 
-    let x3 = lambda
-      .mod_mul(lambda, params.p)
-      .mod_add(params.p - self.x, params.p)
-      .mod_add(params.p - other.x, params.p);
-    let y3 = lambda
-      .mod_mul(self.x.mod_add(params.p - x3, params.p), params.p)
-      .mod_add(params.p - self.y, params.p);
+  fn sub(self, other: Self) -> Self {
+    self.ecc_sub(other, self.params)
+  }
+}
 
-    EccPoint::new_simple(x3, y3, params)
+impl<'a> ops::SubAssign for EccPoint<'a> {
+  fn sub_assign(&mut self, other: Self) {
+    *self = *self - other;
   }
+}
 
-  fn ecc_mul(self, k: U256, params: &'a EccParams) -> Self {
-    if k == U256::C_0 || self.infinity {
-      return EccPoint::infinity(params);
-    }
+impl<'a> ops::Neg for EccPoint<'a> {
+  type Output = Self;
 
-    let mut res: Option<Self> = None;
-    let mut addend = self;
-    let k_words = k.into_le_u64_array();
+  fn neg(self) -> Self {
+    self.negate()
+  }
+}
 
-    for i in 0 .. 256 {
-      if (k_words[i / 64] >> (i % 64)) & 1 == 1 {
-        res = match res {
-          None => Some(addend.clone()),
-          Some(r) => Some(r.ecc_add(addend, params))
-        };
-      }
-      addend = addend.ecc_add(addend, params);
-    }
+impl<'a> ops::Mul<U256> for EccPoint<'a> {
+  type Output = Self;
 
-    match res {
-      Some(r) => r,
-      None => EccPoint::infinity(params)
-    }
+  fn mul(self, k: U256) -> Self {
+    self.ecc_mul(k, self.params)
+  }
+}
+
+impl<'a> ops::MulAssign<U256> for EccPoint<'a> {
+  fn mul_assign(&mut self, k: U256) {
+    *self = *self * k;
   }
 }
 
@@ -278,13 +1963,18 @@ impl<'a> EccOps<'a> for EccPoint<'a> {
 ///
 /// * `mod_add(self, other: Self, modulus: Self) -> Self` - 模加
 /// * `mod_mul(self, other: Self, modulus: Self) -> Self` - 模乘
-/// * `mod_sub(self, other: Self, modulus: Self) -> Self` - 模减，占位
-/// * `mod_div(self, other: Self, modulus: Self) -> Self` - 模除，占位
+/// * `mod_sub(self, other: Self, modulus: Self) -> Self` - 模减
+/// * `mod_div(self, other: Self, modulus: Self) -> Option<Self>` - 模除
+/// * `mod_neg(self, modulus: Self) -> Self` - 模负元
 /// * `modded(self, modulus: Self) -> Self` - 取模
+/// * `modpow(self, exp: Self, modulus: Self) -> Self` - 模幂
+/// * `mod_sqrt(self, modulus: Self) -> Option<Self>` - 模平方根，要求 modulus ≡
+///   3 (mod 4)
 pub trait ModOps: Sized {
   /// # 模加
   ///
-  /// 对两个数进行模加运算，确保返回正确的取模后结果，即便加法结果溢出
+  /// 对两个数进行模加运算，确保返回正确的取模后结果，即便加法结果溢出；
+  /// 操作数不必已对模约减
   ///
   /// ## 参数
   ///
@@ -314,8 +2004,8 @@ pub trait ModOps: Sized {
 
   /// # 模减
   ///
-  /// 对两个数进行模减运算，确保返回正确的取模后结果；由于减法操作一定不会溢出，
-  /// 所以等价于 `(self - other) % modulus`
+  /// 对两个数进行模减运算，确保返回正确的取模后结果；操作数无需预先约减，
+  /// 被减数小于减数时不会因回绕而出错
   ///
   /// ## 参数
   ///
@@ -330,8 +2020,7 @@ pub trait ModOps: Sized {
 
   /// # 模除
   ///
-  /// 对两个数进行模除运算，确保返回正确的取模后结果；由于除法操作一定不会溢出，
-  /// 所以等价于 `(self / other) % modulus`
+  /// 计算域内除法 `self * other^(-1) mod modulus`，而非整数除法
   ///
   /// ## 参数
   ///
@@ -341,8 +2030,22 @@ pub trait ModOps: Sized {
   ///
   /// ## 返回值
   ///
-  /// * `Self` - 结果
-  fn mod_div(self, other: Self, modulus: Self) -> Self;
+  /// * `Option<Self>` - 结果；当 `other` 在模下不可逆时返回 `None`
+  fn mod_div(self, other: Self, modulus: Self) -> Option<Self>;
+
+  /// # 模负元
+  ///
+  /// 计算 `-self mod modulus`，`self` 不必小于 `modulus`
+  ///
+  /// ## 参数
+  ///
+  /// * `self` - 待取负元的数
+  /// * `modulus` - 模
+  ///
+  /// ## 返回值
+  ///
+  /// * `Self` - 结果，`self` 为 `modulus` 的倍数时为 0
+  fn mod_neg(self, modulus: Self) -> Self;
 
   /// # 取模
   ///
@@ -357,6 +2060,37 @@ pub trait ModOps: Sized {
   ///
   /// * `Self` - 结果
   fn modded(self, modulus: Self) -> Self;
+
+  /// # 模幂
+  ///
+  /// 使用平方-乘算法计算 `self^exp mod modulus`
+  ///
+  /// ## 参数
+  ///
+  /// * `self` - 底数
+  /// * `exp` - 指数
+  /// * `modulus` - 模
+  ///
+  /// ## 返回值
+  ///
+  /// * `Self` - 结果
+  fn modpow(self, exp: Self, modulus: Self) -> Self;
+
+  /// # 模平方根
+  ///
+  /// 对 modulus ≡ 3 (mod 4) 的素数模，计算 `self^((modulus + 1) / 4) mod
+  /// modulus` 作为候选平方根，并验证其平方等于 `self`
+  ///
+  /// ## 参数
+  ///
+  /// * `self` - 被开方数
+  /// * `modulus` - 素数模，须满足 modulus ≡ 3 (mod 4)
+  ///
+  /// ## 返回值
+  ///
+  /// * `Option<Self>` - 平方根之一；`self` 不是二次剩余或模不满足要求时返回
+  ///   `None`
+  fn mod_sqrt(self, modulus: Self) -> Option<Self>;
 }
 
 /// # 512 位无符号整数辅助结构体，小端序
@@ -415,7 +2149,7 @@ impl U512Helper {
   }
 
   pub fn leading_zeros(self) -> usize {
-    for i in 7 ..= 1 {
+    for i in (1 ..= 7).rev() {
       if self.0[i] != 0 {
         return (7 - i) * 64 + self.0[i].leading_zeros() as usize;
       }
@@ -447,19 +2181,20 @@ impl From<U512Helper> for U256 {
 
 impl cmp::PartialOrd for U512Helper {
   fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
-    for i in 7 ..= 0 {
-      if self.0[i] != other.0[i] {
-        return Some(self.0[i].cmp(&other.0[i]));
-      }
-    }
-
-    Some(cmp::Ordering::Equal)
+    Some(self.cmp(other))
   }
 }
 
 impl cmp::Ord for U512Helper {
   fn cmp(&self, other: &Self) -> cmp::Ordering {
-    self.partial_cmp(other).unwrap()
+    // 从最高位的 u64 开始比较
+    for i in (0 ..= 7).rev() {
+      if self.0[i] != other.0[i] {
+        return self.0[i].cmp(&other.0[i]);
+      }
+    }
+
+    cmp::Ordering::Equal
   }
 }
 
@@ -523,11 +2258,11 @@ impl ops::Shl<u32> for U512Helper {
       result[i + blocks_shift as usize] = self.0[i];
     }
 
-    let mut carry = 0u64;
-    for i in 0 .. 8 {
-      result[i] = result[i] << bits_shift | carry;
+    // 从高位向低位处理，使低位的 u64 在被移动前移入高位；整 64 位移动时无需进位
+    for i in (0 .. 8).rev() {
+      let carry = if bits_shift == 0 || i == 0 { 0 } else { result[i - 1] >> (64 - bits_shift) };
 
-      carry = result[i] >> (64 - bits_shift);
+      result[i] = result[i] << bits_shift | carry;
     }
 
     Self(result)
@@ -551,11 +2286,11 @@ impl ops::Shr<u32> for U512Helper {
       result[i] = self.0[i + blocks_shift as usize];
     }
 
-    let mut carry = 0u64;
+    // 从低位向高位处理，使高位的 u64 在被移动前移入低位；整 64 位移动时无需进位
     for i in 0 .. 8 {
-      result[i] = result[i] >> bits_shift | carry;
+      let carry = if bits_shift == 0 || i == 7 { 0 } else { result[i + 1] << (64 - bits_shift) };
 
-      carry = result[i] << (64 - bits_shift);
+      result[i] = result[i] >> bits_shift | carry;
     }
 
     Self(result)
@@ -564,7 +2299,9 @@ impl ops::Shr<u32> for U512Helper {
 
 impl ModOps for U256 {
   fn mod_add(self, other: Self, modulus: Self) -> Self {
-    let (result, carry) = self.overflowing_add(other);
+    // 两个操作数均小于模时，和小于 2 * modulus，只需减去一次模
+    let reduce = |v: Self| if v >= modulus { v.modded(modulus) } else { v };
+    let (result, carry) = reduce(self).overflowing_add(reduce(other));
 
     if carry || result >= modulus {
       result - modulus
@@ -590,7 +2327,11 @@ impl ModOps for U256 {
     let mut dividend = result;
     let divisor = modulus;
 
-    for i in (dividend.highest_bit() - divisor.highest_bit()) as u32 ..= 0 {
+    if dividend < U512Helper::from(divisor) {
+      return dividend.into();
+    }
+
+    for i in (0 ..= (dividend.highest_bit() - divisor.highest_bit()) as u32).rev() {
       let r = U512Helper::from(divisor) << i;
 
       if dividend >= r {
@@ -602,28 +2343,77 @@ impl ModOps for U256 {
   }
 
   fn mod_sub(self, other: Self, modulus: Self) -> Self {
-    (self - other).modded(modulus)
+    let a = self.modded(modulus);
+    let b = other.modded(modulus);
+
+    // a < b 时直接相减会绕回 2^256，改为 modulus - (b - a)
+    if a >= b {
+      a - b
+    } else {
+      modulus - (b - a)
+    }
+  }
+
+  fn mod_div(self, other: Self, modulus: Self) -> Option<Self> {
+    Some(self.mod_mul(other.mod_inv(modulus)?, modulus))
   }
 
-  fn mod_div(self, other: Self, modulus: Self) -> Self {
-    (self / other).modded(modulus)
+  fn mod_neg(self, modulus: Self) -> Self {
+    let reduced = self.modded(modulus);
+
+    if reduced.eq_u64(0) {
+      reduced
+    } else {
+      modulus - reduced
+    }
   }
 
   fn modded(self, modulus: Self) -> Self {
     self % modulus
   }
-}
 
-impl<'a> From<EccPoint<'a>> for Vec<u8> {
-  fn from(point: EccPoint<'a>) -> Self {
-    let mut result = Vec::with_capacity(65);
+  fn modpow(self, exp: Self, modulus: Self) -> Self {
+    // 任何数模 1 均为 0，包括指数为 0 时的 1
+    if modulus == U256::C_1 {
+      return U256::C_0;
+    }
 
-    result.push(0x04);
-    result.extend_from_slice(&point.x.into_le_bytes());
-    result.extend_from_slice(&point.y.into_le_bytes());
+    let base = self.modded(modulus);
+    let exponent = exp.into_le_u64_array();
+    let mut result = U256::C_1;
+
+    // 从高位起处理指数，每一位都计算乘积并按位掩码选择，避免依赖指数的分支
+    for i in (0 .. 256).rev() {
+      result = result.mod_mul(result, modulus);
+
+      let product = result.mod_mul(base, modulus);
+      let bit = (exponent[i / 64] >> (i % 64)) & 1;
+      result = conditional_select(result, product, bit);
+    }
 
     result
   }
+
+  fn mod_sqrt(self, modulus: Self) -> Option<Self> {
+    if modulus.into_le_u64_array()[0] & 3 != 3 {
+      return None;
+    }
+
+    // modulus ≡ 3 (mod 4) 时 modulus + 1 不会溢出
+    let root = self.modpow((modulus >> 2) + U256::C_1, modulus);
+
+    if root.mod_mul(root, modulus) == self.modded(modulus) {
+      Some(root)
+    } else {
+      None
+    }
+  }
+}
+
+impl<'a> From<EccPoint<'a>> for Vec<u8> {
+  fn from(point: EccPoint<'a>) -> Self {
+    point.to_bytes_with_order(ByteOrder::BigEndian)
+  }
 }
 
 impl<'a> From<EccPoint<'a>> for BitSequence {
@@ -631,3 +2421,544 @@ impl<'a> From<EccPoint<'a>> for BitSequence {
     Vec::<u8>::from(point)[..].into()
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// # SM2 推荐曲线参数，以结构体字面量构造
+  static SM2: EccParams = EccParams {
+    a: U256::from_be_u64_array(&[
+      0xfffffffeffffffff,
+      0xffffffffffffffff,
+      0xffffffff00000000,
+      0xfffffffffffffffc
+    ]),
+    b: U256::from_be_u64_array(&[
+      0x28e9fa9e9d9f5e34,
+      0x4d5a9e4bcf6509a7,
+      0xf39789f515ab8f92,
+      0xddbcbd414d940e93
+    ]),
+    p: U256::from_be_u64_array(&[
+      0xfffffffeffffffff,
+      0xffffffffffffffff,
+      0xffffffff00000000,
+      0xffffffffffffffff
+    ]),
+    n: U256::from_be_u64_array(&[
+      0xfffffffeffffffff,
+      0xffffffffffffffff,
+      0x7203df6b21c6052b,
+      0x53bbf40939d54123
+    ]),
+    g_x: U256::from_be_u64_array(&[
+      0x32c4ae2c1f198119,
+      0x5f9904466a39c994,
+      0x8fe30bbff2660be1,
+      0x715a4589334c74c7
+    ]),
+    g_y: U256::from_be_u64_array(&[
+      0xbc3736a2f4f6779c,
+      0x59bdcee36b692153,
+      0xd0a9877cc62a4740,
+      0x02df32e52139f0a0
+    ])
+  };
+
+  #[test]
+  fn derived_constants() {
+    assert_eq!(
+      SM2.p_plus_1_div_4(),
+      U256::from_be_u64_array(&[
+        0x3fffffffbfffffff,
+        0xffffffffffffffff,
+        0xffffffffc0000000,
+        0x4000000000000000
+      ])
+    );
+    assert_eq!(SM2.p_minus_1() + U256::C_1, SM2.p);
+    assert_eq!(SM2.n_minus_1() + U256::C_1, SM2.n);
+    assert!(EccParams::new(SM2.a, SM2.b, SM2.p, SM2.n, SM2.g_x, SM2.g_y) == SM2);
+  }
+
+  #[test]
+  fn sqrt_mod_p_recovers_generator_y() {
+    let root = SM2.sqrt_mod_p(SM2.curve_rhs(SM2.g_x)).unwrap().unwrap();
+
+    assert!(root == SM2.g_y || root == SM2.g_y.mod_neg(SM2.p));
+  }
+
+  #[test]
+  fn mod_add_stays_below_modulus() {
+    let p = SM2.p;
+
+    assert_eq!(p.mod_add(p, p), U256::C_0);
+    assert_eq!(SM2.p_minus_1().mod_add(SM2.p_minus_1(), p), p - U256::C_2);
+    assert_eq!(U256::MAX.mod_add(U256::MAX, p), (U256::MAX % p).mod_add(U256::MAX % p, p));
+    // 未按模 n 约减的操作数
+    assert_eq!(SM2.n_minus_1().mod_add(SM2.p_minus_1(), SM2.n), SM2.p - U256::C_2 - SM2.n);
+  }
+
+  /// # 由种子生成确定的测试用数乘因子
+  fn scalar(seed: u64) -> U256 {
+    U256::from_be_bytes(&crate::sm_3::hash_bytes(&seed.to_be_bytes())) % SM2.n
+  }
+
+  fn generator() -> EccPoint<'static> {
+    EccPoint::new_simple(SM2.g_x, SM2.g_y, &SM2)
+  }
+
+  #[test]
+  fn msm_matches_naive_sum() {
+    for count in [5u64, 10, 100] {
+      let points: Vec<_> =
+        (0 .. count).map(|i| generator().ecc_mul(scalar(1000 + i), &SM2)).collect();
+      let scalars: Vec<_> = (0 .. count).map(scalar).collect();
+
+      let expected =
+        points.iter().zip(&scalars).fold(EccPoint::infinity(&SM2), |acc, (point, k)| {
+          acc.ecc_add(point.ecc_mul(*k, &SM2), &SM2)
+        });
+
+      assert!(EccPoint::msm(&points, &scalars, &SM2).unwrap() == expected);
+    }
+
+    assert!(matches!(EccPoint::msm(&[generator()], &[], &SM2), Err(Error::InvalidLength)));
+  }
+
+  #[test]
+  fn u512_shifts_by_whole_limbs() {
+    let x = U512Helper([1, 2, 3, 4, 5, 6, 7, 8]);
+
+    assert_eq!((x << 64).0, [0, 1, 2, 3, 4, 5, 6, 7]);
+    assert_eq!((x >> 192).0, [4, 5, 6, 7, 8, 0, 0, 0]);
+    assert_eq!((x << 512).0, [0; 8]);
+    assert_eq!((U512Helper([1 << 63, 0, 0, 0, 0, 0, 0, 0]) << 1).0, [0, 1, 0, 0, 0, 0, 0, 0]);
+  }
+
+  #[test]
+  fn ecc_mul_ct_matches_ecc_mul() {
+    let g = generator();
+    let edge_cases =
+      [U256::C_0, U256::C_1, U256::C_2, SM2.n_minus_1(), SM2.n, SM2.n + U256::C_1, U256::MAX];
+
+    for k in edge_cases.into_iter().chain((0 .. 16).map(scalar)) {
+      assert!(g.ecc_mul_ct(k, &SM2) == g.ecc_mul(k, &SM2));
+    }
+
+    assert!(g.ecc_mul_ct(SM2.n, &SM2).infinity);
+    assert!(EccPoint::infinity(&SM2).ecc_mul_ct(U256::C_2, &SM2).infinity);
+  }
+
+  #[test]
+  fn montgomery_inverse_matches_euclidean_inverse() {
+    let ctx = MontgomeryCtx::new(SM2.p);
+
+    for seed in 0 .. 8 {
+      let a = scalar(seed);
+      let inverse = ctx.from_mont(ctx.mont_inv(ctx.to_mont(a)));
+
+      assert_eq!(inverse, a.mod_inv(SM2.p).unwrap());
+    }
+  }
+
+  #[test]
+  fn ecc_mul_double_matches_two_ecc_mul() {
+    let g = generator();
+    let q = g.ecc_mul(scalar(100), &SM2);
+    let edge_cases = [(U256::C_0, U256::C_0), (U256::C_1, U256::C_0), (U256::C_0, SM2.n_minus_1())];
+
+    for (a, b) in edge_cases.into_iter().chain((0 .. 8).map(|i| (scalar(2 * i), scalar(2 * i + 1))))
+    {
+      let expected = g.ecc_mul(a, &SM2).ecc_add(q.ecc_mul(b, &SM2), &SM2);
+
+      assert!(g.ecc_mul_double(a, q, b, &SM2) == expected);
+    }
+
+    // P = Q 时预计算的 P + Q 为倍点，P = -Q 时为无穷远点
+    assert!(
+      g.ecc_mul_double(U256::from(5u64), g, U256::from(7u64), &SM2)
+        == g.ecc_mul(U256::from(12u64), &SM2)
+    );
+    assert!(g.ecc_mul_double(U256::C_2, g.negate(), U256::C_1, &SM2) == g);
+    assert!(EccPoint::infinity(&SM2).ecc_mul_double(U256::C_1, g, U256::C_1, &SM2) == g);
+  }
+
+  /// # 仅使用仿射坐标的加法与倍点的参考数乘
+  fn affine_mul(point: EccPoint<'static>, k: U256) -> EccPoint<'static> {
+    let mut res = EccPoint::infinity(&SM2);
+    let mut addend = point;
+
+    for i in 0 .. 256 - k.leading_zeros() {
+      if k.bit(i) {
+        res = res.ecc_add(addend, &SM2);
+      }
+      addend = addend.double();
+    }
+
+    res
+  }
+
+  /// # 由种子生成确定的 64 位数乘因子，使仿射参考数乘不至过慢
+  fn short_scalar(seed: u64) -> U256 {
+    U256::from(scalar(seed).into_le_u64_array()[0])
+  }
+
+  #[test]
+  fn jacobian_mul_matches_affine_mul() {
+    let g = generator();
+    let edge_cases = [U256::C_0, U256::C_1, U256::C_2, SM2.n_minus_1(), SM2.n];
+
+    for k in edge_cases.into_iter().chain((0 .. 4).map(short_scalar)) {
+      let expected = affine_mul(g, k);
+
+      assert!(g.ecc_mul(k, &SM2) == expected);
+      assert!(g.ecc_mul_with(k, &SM2, MulStrategy::Windowed) == expected);
+      assert!(g.ecc_mul_wnaf(k, &SM2, 4) == expected);
+    }
+  }
+
+  #[test]
+  fn jacobian_msm_matches_affine_sum() {
+    let points: Vec<_> = (0 .. 8).map(|i| generator().ecc_mul(scalar(2000 + i), &SM2)).collect();
+    let scalars: Vec<_> = (0 .. 8).map(short_scalar).collect();
+    let expected = points
+      .iter()
+      .zip(&scalars)
+      .fold(EccPoint::infinity(&SM2), |acc, (point, k)| acc.ecc_add(affine_mul(*point, *k), &SM2));
+
+    assert!(EccPoint::msm_interleaved(&points, &scalars, &SM2) == expected);
+    assert!(EccPoint::msm_pippenger(&points, &scalars, &SM2) == expected);
+  }
+
+  #[test]
+  fn mod_inv_prime_matches_euclidean_inverse() {
+    for modulus in [SM2.p, SM2.n] {
+      for seed in 0 .. 8 {
+        let a = scalar(seed);
+
+        assert_eq!(a.mod_inv_prime(modulus), a.mod_inv(modulus).unwrap());
+      }
+
+      assert_eq!(U256::C_0.mod_inv_prime(modulus), U256::C_0);
+      assert_eq!(U256::C_1.mod_inv_prime(modulus), U256::C_1);
+      assert_eq!(U256::MAX.mod_inv_prime(modulus), U256::MAX.mod_inv(modulus).unwrap());
+    }
+
+    assert_eq!(U256::from(3u64).mod_inv_prime(U256::from(7u64)), U256::from(5u64));
+    assert_eq!(U256::from(3u64).mod_inv_prime(U256::C_2), U256::C_1);
+  }
+
+  /// # 由种子生成确定的、未经约减的 256 位数
+  fn wide(seed: u64) -> U256 {
+    U256::from_be_bytes(&crate::sm_3::hash_bytes(&seed.to_le_bytes()))
+  }
+
+  #[test]
+  fn barrett_matches_mod_mul() {
+    for modulus in [SM2.p, SM2.n] {
+      let reducer = BarrettReducer::new(modulus);
+      assert_eq!(reducer.modulus(), modulus);
+
+      for seed in 0 .. 16 {
+        let (a, b) = (wide(2 * seed), wide(2 * seed + 1));
+
+        assert_eq!(reducer.reduce(a.widening_mul(b)), a.mod_mul(b, modulus));
+      }
+
+      assert_eq!(
+        reducer.reduce(U256::MAX.widening_mul(U256::MAX)),
+        U256::MAX.mod_mul(U256::MAX, modulus)
+      );
+      assert_eq!(reducer.reduce((U256::C_0, U256::C_0)), U256::C_0);
+      assert_eq!(reducer.reduce((modulus, U256::C_0)), U256::C_0);
+      assert_eq!(reducer.reduce((U256::MAX, U256::C_0)), U256::MAX % modulus);
+    }
+  }
+
+  #[test]
+  fn barrett_handles_small_moduli() {
+    // 模数为 1 时 k = 1，输入须小于 2^2
+    let reducer = BarrettReducer::new(U256::C_1);
+    for x in 0 .. 4u64 {
+      assert_eq!(reducer.reduce((U256::from(x), U256::C_0)), U256::C_0);
+    }
+
+    for m in [2u64, 3, 7, 255, 256] {
+      let modulus = U256::from(m);
+      let reducer = BarrettReducer::new(modulus);
+
+      for a in 0 .. m {
+        let b = m - 1 - a / 2;
+
+        assert_eq!(
+          reducer.reduce(U256::from(a).widening_mul(U256::from(b))),
+          U256::from(a * b % m)
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn wnaf_matches_ecc_mul_for_all_windows() {
+    let g = generator();
+    let scalars = [SM2.n_minus_1(), U256::MAX, wide(100), wide(101), scalar(102)];
+
+    for window in 2 ..= 6 {
+      for k in scalars {
+        assert!(g.ecc_mul_wnaf(k, &SM2, window) == g.ecc_mul(k, &SM2));
+      }
+    }
+  }
+
+  #[test]
+  fn wnaf_needs_fewer_additions() {
+    // 非零位数即主循环中的点加次数，逐位数乘的点加次数为 1 的个数
+    let scalars: Vec<_> = (0 .. 32).map(wide).collect();
+    let binary: usize = scalars.iter().map(|k| k.count_ones()).sum();
+
+    for window in 2 ..= 6 {
+      let wnaf: usize = scalars
+        .iter()
+        .map(|&k| wnaf_digits(k, window).iter().filter(|&&digit| digit != 0).count())
+        .sum();
+
+      // 平均约为 256 / (w + 1) 次，远少于逐位处理的约 128 次
+      assert!(wnaf * (window + 1) < 300 * scalars.len());
+      assert!(wnaf < binary);
+    }
+  }
+
+  #[test]
+  fn from_bytes_rejects_other_prefixes() {
+    let mut bytes: [u8; 65] = Vec::from(generator()).try_into().unwrap();
+    assert!(EccPoint::from_bytes(&bytes, &SM2).unwrap() == generator());
+
+    for prefix in [0x00, 0x02, 0x03, 0x05, 0xff] {
+      bytes[0] = prefix;
+
+      assert_eq!(EccPoint::from_bytes(&bytes, &SM2).err(), Some(Error::InvalidPoint));
+      assert_eq!(EccPoint::try_from_bytes(&bytes, &SM2).err(), Some(Error::InvalidPoint));
+    }
+
+    assert_eq!(EccPoint::from_bytes(&[0; 65], &SM2).err(), Some(Error::InvalidPoint));
+    assert_eq!(EccPoint::try_from_bytes(&[0; 65], &SM2).err(), Some(Error::InvalidPoint));
+  }
+
+  #[test]
+  fn infinity_round_trips_through_bytes() {
+    let infinity = EccPoint::infinity(&SM2);
+
+    let bytes = Vec::from(infinity);
+    assert_eq!(bytes, [0x00]);
+    assert!(EccPoint::try_from_bytes(&bytes, &SM2).unwrap().infinity);
+
+    let bits = BitSequence::from(infinity);
+    assert_eq!(bits.len(), 8);
+    assert!(EccPoint::try_from_bytes(bits.get_bytes(), &SM2).unwrap().infinity);
+
+    assert_eq!(infinity.to_bytes_with_order(ByteOrder::LittleEndian), [0x00]);
+    assert!(
+      EccPoint::try_from_bytes_with_order(&[0x00], &SM2, ByteOrder::LittleEndian).unwrap().infinity
+    );
+  }
+
+  #[test]
+  fn compressed_bytes_round_trip_both_parities() {
+    let points = [generator(), generator().negate(), generator().ecc_mul(scalar(200), &SM2)];
+
+    for point in points {
+      let bytes = point.to_compressed_bytes();
+
+      assert!(EccPoint::from_compressed_bytes(&bytes, &SM2).unwrap() == point);
+    }
+
+    // G 与 -G 的 y 奇偶性相反
+    let prefixes = [points[0].to_compressed_bytes()[0], points[1].to_compressed_bytes()[0]];
+    assert!(prefixes == [0x02, 0x03] || prefixes == [0x03, 0x02]);
+  }
+
+  #[test]
+  fn compressed_bytes_rejects_invalid_input() {
+    let mut bytes = generator().to_compressed_bytes();
+
+    for prefix in [0x01, 0x04, 0xff] {
+      bytes[0] = prefix;
+      assert_eq!(EccPoint::from_compressed_bytes(&bytes, &SM2).err(), Some(Error::InvalidPoint));
+    }
+
+    for x in [SM2.p, U256::MAX] {
+      bytes[1 ..].copy_from_slice(&x.into_be_bytes());
+
+      for prefix in [0x02, 0x03] {
+        bytes[0] = prefix;
+        assert_eq!(EccPoint::from_compressed_bytes(&bytes, &SM2).err(), Some(Error::InvalidPoint));
+      }
+    }
+
+    // x^3 + ax + b 不是二次剩余的 x 不对应曲线上的点
+    let x = (0u64 ..)
+      .map(U256::from)
+      .find(|&x| SM2.sqrt_mod_p(SM2.curve_rhs(x)).unwrap().is_none())
+      .unwrap();
+    bytes[0] = 0x02;
+    bytes[1 ..].copy_from_slice(&x.into_be_bytes());
+    assert_eq!(EccPoint::from_compressed_bytes(&bytes, &SM2).err(), Some(Error::InvalidPoint));
+  }
+
+  #[test]
+  fn mod_sub_wraps_and_reduces_operands() {
+    let small = |v: u64| U256::from(v);
+    let p = SM2.p;
+
+    assert_eq!(small(3).mod_sub(small(5), small(7)), small(5));
+    assert_eq!(small(5).mod_sub(small(3), small(7)), small(2));
+    assert_eq!(small(12).mod_sub(small(5), small(7)), U256::C_0);
+
+    // 未约减的操作数
+    assert_eq!(U256::MAX.mod_sub(U256::C_0, p), U256::MAX - p);
+    assert_eq!(U256::C_0.mod_sub(U256::MAX, p), p - (U256::MAX - p));
+    assert_eq!((p + U256::C_1).mod_sub(SM2.p_minus_1(), p), U256::C_2);
+
+    for seed in 0 .. 8 {
+      let (a, b) = (wide(300 + 2 * seed), wide(301 + 2 * seed));
+
+      assert_eq!(a.mod_sub(b, p).mod_add(b, p), a % p);
+    }
+  }
+
+  #[test]
+  fn mod_div_inverts_mod_mul() {
+    for modulus in [SM2.p, SM2.n] {
+      for seed in 0 .. 8 {
+        let (a, b) = (scalar(400 + 2 * seed), scalar(401 + 2 * seed));
+        let quotient = a.mod_div(b, modulus).unwrap();
+
+        assert_eq!(quotient.mod_mul(b, modulus), a % modulus);
+      }
+
+      assert_eq!(scalar(420).mod_div(U256::C_0, modulus), None);
+      assert_eq!(scalar(420).mod_div(modulus, modulus), None);
+      assert_eq!(U256::C_0.mod_div(scalar(421), modulus), Some(U256::C_0));
+    }
+
+    assert_eq!(
+      U256::from(3u64).mod_div(U256::from(5u64), U256::from(7u64)),
+      Some(U256::from(2u64))
+    );
+  }
+
+  #[test]
+  fn mod_sqrt_finds_residues_only() {
+    let p = SM2.p;
+
+    for seed in 0 .. 8 {
+      let root = scalar(500 + seed) % p;
+      let square = root.mod_mul(root, p);
+      let found = square.mod_sqrt(p).unwrap();
+
+      assert!(found == root || found == root.mod_neg(p));
+    }
+
+    assert_eq!(U256::C_0.mod_sqrt(p), Some(U256::C_0));
+    assert_eq!(U256::C_1.mod_sqrt(p).map(|r| r.mod_mul(r, p)), Some(U256::C_1));
+
+    // p ≡ 3 (mod 4) 时 -1 不是二次剩余
+    assert_eq!(SM2.p_minus_1().mod_sqrt(p), None);
+    assert_eq!(U256::from(3u64).mod_sqrt(U256::from(7u64)), None);
+    assert_eq!(U256::from(2u64).mod_sqrt(U256::from(7u64)), Some(U256::from(4u64)));
+
+    // 13 ≡ 1 (mod 4)，不支持
+    assert_eq!(U256::from(4u64).mod_sqrt(U256::from(13u64)), None);
+  }
+
+  #[test]
+  fn modpow_matches_repeated_mul() {
+    let p = SM2.p;
+    let base = scalar(510);
+
+    assert_eq!(base.modpow(U256::C_0, p), U256::C_1);
+    assert_eq!(base.modpow(U256::C_1, p), base);
+    assert_eq!(base.modpow(U256::from(3u64), p), base.mod_mul(base, p).mod_mul(base, p));
+    // 费马小定理
+    assert_eq!(base.modpow(SM2.p_minus_1(), p), U256::C_1);
+    assert_eq!(U256::from(3u64).modpow(U256::from(4u64), U256::from(7u64)), U256::from(4u64));
+    assert_eq!(base.modpow(U256::C_0, U256::C_1), U256::C_0);
+  }
+
+  #[test]
+  fn ecc_mul_signed_negates_the_product() {
+    let g = generator();
+
+    for k in [U256::C_1, U256::C_2, SM2.n_minus_1(), scalar(600), scalar(601)] {
+      let product = g.ecc_mul(k, &SM2);
+
+      assert!(g.ecc_mul_signed(k, true, &SM2) == product.negate());
+      assert!(g.ecc_mul_signed(k, false, &SM2) == product);
+    }
+
+    assert!(g.ecc_mul_signed(U256::C_0, true, &SM2).infinity);
+  }
+
+  #[test]
+  fn operators_match_methods() {
+    let g = generator();
+    let (s, t) = (scalar(700), scalar(701));
+    let pk = g.ecc_mul(scalar(702), &SM2);
+    let expected = g.ecc_mul(s, &SM2).ecc_add(pk.ecc_mul(t, &SM2), &SM2);
+
+    assert!(g * s + pk * t == expected);
+    assert!(g * s - pk * t == g.ecc_mul(s, &SM2).ecc_add(pk.ecc_mul(t, &SM2).negate(), &SM2));
+    assert!(-pk == pk.negate());
+
+    let mut sum = g * s;
+    sum += pk * t;
+    assert!(sum == expected);
+    sum -= pk * t;
+    assert!(sum == g.ecc_mul(s, &SM2));
+
+    let mut product = pk;
+    product *= t;
+    assert!(product == pk.ecc_mul(t, &SM2));
+  }
+
+  #[test]
+  fn subtracting_a_point_from_itself_gives_infinity() {
+    let infinity = EccPoint::infinity(&SM2);
+
+    for point in [generator(), generator().ecc_mul(scalar(800), &SM2)] {
+      assert!((point - point).infinity);
+      assert!((point + (-point)).infinity);
+      assert!(point + infinity == point);
+      assert!(point - infinity == point);
+    }
+
+    assert!(infinity.negate().infinity);
+    assert!((-infinity).infinity);
+    assert!((infinity - infinity).infinity);
+  }
+
+  #[test]
+  fn mod_neg_handles_zero_multiples_and_unreduced_values() {
+    let small = |v: u64| U256::from(v);
+    let p = SM2.p;
+
+    assert_eq!(U256::C_0.mod_neg(p), U256::C_0);
+    assert_eq!(p.mod_neg(p), U256::C_0);
+    assert_eq!(small(21).mod_neg(small(7)), U256::C_0);
+    assert_eq!(U256::C_1.mod_neg(p), SM2.p_minus_1());
+    assert_eq!(small(3).mod_neg(small(7)), small(4));
+
+    // 未约减的值
+    assert_eq!(small(10).mod_neg(small(7)), small(4));
+    assert_eq!((p + U256::C_1).mod_neg(p), SM2.p_minus_1());
+    assert_eq!(U256::MAX.mod_neg(p), p - (U256::MAX - p));
+
+    for seed in 0 .. 8 {
+      let a = wide(900 + seed);
+
+      assert_eq!(a.mod_neg(p).mod_add(a, p), U256::C_0);
+    }
+  }
+}
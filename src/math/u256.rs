@@ -1,6 +1,13 @@
 use {
-  rand::CryptoRng,
-  std::{cmp, ops}
+  super::bytes::{BitSequence, ByteOrder},
+  crate::Error,
+  alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec
+  },
+  core::{cmp, error, fmt, ops},
+  rand::CryptoRng
 };
 
 /// # 256 位无符号整数
@@ -34,6 +41,15 @@ use {
 /// * `U256::from_le_u64_array(array)` - 从小端序 u64 数组创建 256
 ///   位无符号整数，用于实现 `From<[u64; 4]>`
 /// * `U256::from_be_u64_array(array)` - 从大端序 u64 数组创建 256 位无符号整数
+/// * `U256::from_bytes_with_order(bytes, order)` - 从指定字节序的字节数组创建
+///   256 位无符号整数
+/// * `U256::from_minimal_bits(bits)` - 从最短比特序列创建 256 位无符号整数
+/// * `U256::from_le_iter(iter)` - 从恰好产生 32 个字节的小端序迭代器创建 256
+///   位无符号整数
+/// * `U256::from_be_iter(iter)` - 从恰好产生 32 个字节的大端序迭代器创建 256
+///   位无符号整数
+/// * `U256::from_hex(s)` - 从十六进制字符串创建 256 位无符号整数
+/// * `U256::from_dec_str(s)` - 从十进制字符串创建 256 位无符号整数
 ///
 /// ## 实现特征
 ///
@@ -43,6 +59,13 @@ use {
 /// * `Eq`
 /// * `PartialOrd`
 /// * `Ord`
+/// * `Debug` - 输出带 `0x` 前缀的 64 位大端序十六进制
+/// * `LowerHex` - 支持 `{:#x}` 前缀与 `{:064x}` 宽度
+/// * `UpperHex` - 同 `LowerHex`，使用大写字母
+/// * `Serialize` - 启用 `serde` 特性时可用
+/// * `Deserialize` - 启用 `serde` 特性时可用
+/// * `Zeroize` - 启用 `zeroize` 特性时可用；由于实现了
+///   `Copy`，丢弃时不会自动清零
 /// * `Shl`
 /// * `ShlAssign`
 /// * `Shl<u32>`
@@ -59,8 +82,11 @@ use {
 /// * `MulAssign`
 /// * `Div`
 /// * `DivAssign`
+/// * `Div<u64>`
+/// * `DivAssign<u64>`
 /// * `Rem`
 /// * `RemAssign`
+/// * `Rem<u64>` - 余数为 `u64`
 /// * `Not`
 /// * `Neg`
 /// * `BitAnd`
@@ -89,19 +115,40 @@ use {
 /// * `TryFrom<&Vec<u8>>`
 /// * `TryFrom<Vec<u64>>`
 /// * `TryFrom<&Vec<u64>>`
+/// * `TryFrom<&str>`
 ///
 /// ## 方法
 ///
 /// * `u256.overflowing_add(other: Self) -> (Self, bool)` -
 ///   无符号整数加法，返回结果对 2^256 取模的结果和是否溢出
+/// * `u256.checked_add(other: Self) -> Option<Self>` - 检查溢出的加法
+/// * `u256.checked_sub(other: Self) -> Option<Self>` - 检查溢出的减法
+/// * `u256.checked_mul(other: Self) -> Option<Self>` - 检查溢出的乘法
+/// * `u256.widening_mul(other: Self) -> (Self, Self)` - 完整宽度乘法，返回 512
+///   位乘积的低、高 256 位
+/// * `u256.eq_u64(v: u64) -> bool` - 与 64 位无符号整数判等
+/// * `u256.cmp_u64(v: u64) -> cmp::Ordering` - 与 64 位无符号整数比较
+/// * `u256.ct_is_zero() -> u64` - 常数时间判断是否为 0，返回掩码
+/// * `u256.ct_eq(other: Self) -> bool` - 常数时间判等
 /// * `u256.leading_zeros() -> usize` - 返回前导 0 的个数
 /// * `u256.highest_bit() -> usize` - 返回最高位的位置，通过 `256 -
 ///   u256.leading_zeros()` 计算
+/// * `u256.trailing_zeros() -> usize` - 返回末尾 0 的个数
+/// * `u256.count_ones() -> usize` - 返回 1 的个数
+/// * `u256.bit(i: usize) -> bool` - 读取第 `i` 位
+/// * `u256.set_bit(i: usize, v: bool)` - 设置第 `i` 位
 /// * `u256.into_le_bytes() -> [u8; 32]` - 返回小端序字节数组
 /// * `u256.into_be_bytes() -> [u8; 32]` - 返回大端序字节数组
+/// * `u256.into_bytes_with_order(order: ByteOrder) -> [u8; 32]` -
+///   返回指定字节序的字节数组
 /// * `u256.into_le_u64_array() -> [u64; 4]` - 返回小端序 u64 数组
 /// * `u256.into_be_u64_array() -> [u64; 4]` - 返回大端序 u64 数组
+/// * `u256.to_hex() -> String` - 返回 64 位小写大端序十六进制字符串
+/// * `u256.to_minimal_bits() -> BitSequence` - 返回恰好 `highest_bit()`
+///   位的大端序比特序列
 /// * `u256.random(rng: &mut impl CryptoRng) -> Self` - 返回一个随机数
+/// * `u256.random_below(rng: &mut impl CryptoRng, bound) -> Option<Self>` -
+///   返回一个在 [0, `bound`) 范围内均匀分布的随机数，`bound` 为 0 时返回 `None`
 /// * `u256.random_in_range(rng: &mut impl CryptoRng, min: Self, max: Self) ->
 ///   Self` - 返回一个在 [`min`, `max`) 范围内的随机数
 #[derive(Copy, Clone, PartialEq, Eq)]
@@ -235,6 +282,167 @@ impl U256 {
     ])
   }
 
+  /// # `U256` 从小端序字节迭代器创建 256 位无符号整数
+  ///
+  /// ## 参数
+  ///
+  /// * `iter` - 小端序字节迭代器，必须恰好产生 32 个字节
+  ///
+  /// ## 返回值
+  ///
+  /// * `Ok(Self)` - 返回一个 256 位无符号整数
+  /// * `Err(Error)` - 迭代器产生的字节数不为 32
+  pub fn from_le_iter(iter: impl IntoIterator<Item = u8>) -> Result<Self, Error> {
+    Ok(Self::from_le_bytes(&Self::collect_bytes(iter)?))
+  }
+
+  /// # `U256` 从大端序字节迭代器创建 256 位无符号整数
+  ///
+  /// ## 参数
+  ///
+  /// * `iter` - 大端序字节迭代器，必须恰好产生 32 个字节
+  ///
+  /// ## 返回值
+  ///
+  /// * `Ok(Self)` - 返回一个 256 位无符号整数
+  /// * `Err(Error)` - 迭代器产生的字节数不为 32
+  pub fn from_be_iter(iter: impl IntoIterator<Item = u8>) -> Result<Self, Error> {
+    Ok(Self::from_be_bytes(&Self::collect_bytes(iter)?))
+  }
+
+  /// # `U256` 从迭代器读取恰好 32 个字节
+  ///
+  /// ## 参数
+  ///
+  /// * `iter` - 字节迭代器
+  ///
+  /// ## 返回值
+  ///
+  /// * `Ok([u8; 32])` - 读取到的字节数组
+  /// * `Err(Error)` - 迭代器产生的字节数不为 32
+  fn collect_bytes(iter: impl IntoIterator<Item = u8>) -> Result<[u8; 32], Error> {
+    let mut iter = iter.into_iter();
+    let mut bytes = [0u8; 32];
+
+    for byte in bytes.iter_mut() {
+      *byte = iter.next().ok_or(Error::InvalidLength)?;
+    }
+
+    if iter.next().is_some() {
+      return Err(Error::InvalidLength);
+    }
+
+    Ok(bytes)
+  }
+
+  /// # `U256` 从十六进制字符串创建 256 位无符号整数
+  ///
+  /// ## 参数
+  ///
+  /// * `s` - 大端序的十六进制字符串，可带 `0x` 或 `0X` 前缀，不区分大小写；
+  ///   不足 64 位时视为左侧补零
+  ///
+  /// ## 返回值
+  ///
+  /// * `Ok(Self)` - 返回一个 256 位无符号整数
+  /// * `Err(ParseU256Error)` - 字符串为空、含有非十六进制字符或超过 64 位
+  pub fn from_hex(s: &str) -> Result<Self, ParseU256Error> {
+    let digits = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")).unwrap_or(s);
+
+    // 即便高位全为 0，超过 64 位的字符串也视为过长
+    if digits.len() > 64 {
+      return Err(ParseU256Error::Overflow);
+    }
+
+    Self::parse_radix(digits, 16)
+  }
+
+  /// # `U256` 转换为十六进制字符串
+  ///
+  /// ## 返回值
+  ///
+  /// * `String` - 不带前缀、左侧补零的 64 位小写大端序十六进制字符串，可由
+  ///   `from_hex` 解析回原值
+  pub fn to_hex(self) -> String {
+    format!("{:064x}", self)
+  }
+
+  /// # `U256` 从十进制字符串创建 256 位无符号整数
+  ///
+  /// ## 参数
+  ///
+  /// * `s` - 十进制字符串
+  ///
+  /// ## 返回值
+  ///
+  /// * `Ok(Self)` - 返回一个 256 位无符号整数
+  /// * `Err(ParseU256Error)` - 字符串为空、含有非十进制字符或数值超出 256 位
+  pub fn from_dec_str(s: &str) -> Result<Self, ParseU256Error> {
+    Self::parse_radix(s, 10)
+  }
+
+  /// # `U256` 按指定进制解析字符串
+  ///
+  /// 逐位计算 `result * radix + digit`，任何一步产生进位即视为溢出
+  ///
+  /// ## 参数
+  ///
+  /// * `digits` - 不含前缀的数字字符串
+  /// * `radix` - 进制，为 10 或 16
+  ///
+  /// ## 返回值
+  ///
+  /// * `Ok(Self)` - 返回一个 256 位无符号整数
+  /// * `Err(ParseU256Error)` - 解析失败的原因
+  fn parse_radix(digits: &str, radix: u32) -> Result<Self, ParseU256Error> {
+    if digits.is_empty() {
+      return Err(ParseU256Error::Empty);
+    }
+
+    let mut result = [0u64; 4];
+
+    for c in digits.chars() {
+      let mut carry = c.to_digit(radix).ok_or(ParseU256Error::InvalidChar)? as u128;
+
+      for limb in result.iter_mut() {
+        let v = (*limb as u128) * (radix as u128) + carry;
+
+        *limb = v as u64;
+        carry = v >> 64;
+      }
+
+      if carry != 0 {
+        return Err(ParseU256Error::Overflow);
+      }
+    }
+
+    Ok(Self(result))
+  }
+
+  /// # 不带前缀、不补零的大端序十六进制数字
+  ///
+  /// ## 参数
+  ///
+  /// * `upper` - 是否使用大写字母
+  ///
+  /// ## 返回值
+  ///
+  /// * `String` - 十六进制数字，0 时为 `"0"`
+  fn hex_digits(self, upper: bool) -> String {
+    let digits: String = self
+      .into_be_u64_array()
+      .iter()
+      .map(|limb| if upper { format!("{:016X}", limb) } else { format!("{:016x}", limb) })
+      .collect();
+    let trimmed = digits.trim_start_matches('0');
+
+    if trimmed.is_empty() {
+      "0".to_string()
+    } else {
+      trimmed.to_string()
+    }
+  }
+
   /// # `U256` 返回小端序字节数组
   ///
   /// ## 返回值
@@ -367,6 +575,108 @@ impl U256 {
     [self.0[3], self.0[2], self.0[1], self.0[0]]
   }
 
+  /// # `U256` 从指定字节序的字节数组创建 256 位无符号整数
+  ///
+  /// ## 参数
+  ///
+  /// * `bytes` - 字节数组
+  /// * `order` - 字节序
+  ///
+  /// ## 返回值
+  ///
+  /// * `Self` - 返回一个 256 位无符号整数
+  pub const fn from_bytes_with_order(bytes: &[u8; 32], order: ByteOrder) -> Self {
+    match order {
+      ByteOrder::BigEndian => Self::from_be_bytes(bytes),
+      ByteOrder::LittleEndian => Self::from_le_bytes(bytes)
+    }
+  }
+
+  /// # `U256` 返回指定字节序的字节数组
+  ///
+  /// ## 参数
+  ///
+  /// * `order` - 字节序
+  ///
+  /// ## 返回值
+  ///
+  /// * `[u8; 32]` - 返回一个 256 位无符号整数的字节数组
+  pub const fn into_bytes_with_order(self, order: ByteOrder) -> [u8; 32] {
+    match order {
+      ByteOrder::BigEndian => self.into_be_bytes(),
+      ByteOrder::LittleEndian => self.into_le_bytes()
+    }
+  }
+
+  /// # `U256` 返回最短比特序列
+  ///
+  /// 以大端序输出恰好 `highest_bit()` 位，不含前导 0；0 输出空序列
+  ///
+  /// ## 返回值
+  ///
+  /// * `BitSequence` - 返回一个长度为 `highest_bit()` 的比特序列
+  pub fn to_minimal_bits(self) -> BitSequence {
+    let bit_len = self.highest_bit();
+
+    if bit_len == 0 {
+      return BitSequence::new_empty();
+    }
+
+    let be_bytes = self.into_be_bytes();
+    let skip_bytes = (256 - bit_len) / 8;
+    let skip_bits = (256 - bit_len) % 8;
+
+    let mut bytes = Vec::with_capacity(bit_len.div_ceil(8));
+    for i in skip_bytes .. skip_bytes + bit_len.div_ceil(8) {
+      let mut byte = be_bytes[i] << skip_bits;
+
+      if skip_bits > 0 && i + 1 < 32 {
+        byte |= be_bytes[i + 1] >> (8 - skip_bits);
+      }
+
+      bytes.push(byte);
+    }
+
+    BitSequence::new(bytes, (bit_len % 8) as u8)
+  }
+
+  /// # `U256` 从最短比特序列创建 256 位无符号整数
+  ///
+  /// 与 `to_minimal_bits` 对应，按大端序解析；允许输入包含前导 0
+  ///
+  /// ## 参数
+  ///
+  /// * `bits` - 大端序比特序列，长度不超过 256 位
+  ///
+  /// ## 返回值
+  ///
+  /// * `Ok(Self)` - 返回一个 256 位无符号整数
+  /// * `Err(Error)` - 比特序列长度超过 256 位
+  pub fn from_minimal_bits(bits: &BitSequence) -> Result<Self, Error> {
+    if bits.get_bytes().is_empty() {
+      return Ok(Self::C_0);
+    }
+
+    if bits.len() > 256 {
+      return Err(Error::InvalidLength);
+    }
+
+    let bytes = bits.get_bytes();
+    let last_byte_len = bits.get_last_byte_len() as u32;
+    let full_bytes = if last_byte_len == 0 { bytes.len() } else { bytes.len() - 1 };
+
+    let mut result = Self::C_0;
+    for byte in &bytes[.. full_bytes] {
+      result = result << 8 | Self::from(*byte);
+    }
+
+    if last_byte_len > 0 {
+      result = result << last_byte_len | Self::from(bytes[full_bytes] >> (8 - last_byte_len));
+    }
+
+    Ok(result)
+  }
+
   /// # `U256` 无符号整数加法，返回结果对 2^256 取模的结果和是否溢出
   ///
   /// ## 参数
@@ -391,13 +701,192 @@ impl U256 {
     (Self(result), carry)
   }
 
+  /// # `U256` 检查溢出的加法
+  ///
+  /// ## 参数
+  ///
+  /// * `other` - 另一个 256 位无符号整数
+  ///
+  /// ## 返回值
+  ///
+  /// * `Option<Self>` - 返回和；最高位产生进位时返回 `None`
+  pub fn checked_add(self, other: Self) -> Option<Self> {
+    match self.overflowing_add(other) {
+      (result, false) => Some(result),
+      (_, true) => None
+    }
+  }
+
+  /// # `U256` 检查溢出的减法
+  ///
+  /// ## 参数
+  ///
+  /// * `other` - 减数
+  ///
+  /// ## 返回值
+  ///
+  /// * `Option<Self>` - 返回差；`self` 小于 `other` 产生借位时返回 `None`
+  pub fn checked_sub(self, other: Self) -> Option<Self> {
+    if self < other {
+      None
+    } else {
+      Some(self - other)
+    }
+  }
+
+  /// # `U256` 检查溢出的乘法
+  ///
+  /// ## 参数
+  ///
+  /// * `other` - 另一个 256 位无符号整数
+  ///
+  /// ## 返回值
+  ///
+  /// * `Option<Self>` - 返回积；完整乘积超过 256 位时返回 `None`
+  pub fn checked_mul(self, other: Self) -> Option<Self> {
+    match self.widening_mul(other) {
+      (low, high) if high.eq_u64(0) => Some(low),
+      _ => None
+    }
+  }
+
+  /// # `U256` 完整宽度乘法
+  ///
+  /// 逐个 u64 相乘并以 u128 累加，得到完整的 512 位乘积
+  ///
+  /// ## 参数
+  ///
+  /// * `other` - 另一个 256 位无符号整数
+  ///
+  /// ## 返回值
+  ///
+  /// * `(Self, Self)` - 返回乘积的低 256 位和高 256 位
+  pub fn widening_mul(self, other: Self) -> (Self, Self) {
+    let mut result = [0u64; 8];
+
+    for i in 0 .. 4 {
+      let mut carry = 0u128;
+
+      for j in 0 .. 4 {
+        let v = (self.0[i] as u128) * (other.0[j] as u128) + result[i + j] as u128 + carry;
+
+        result[i + j] = v as u64;
+        carry = v >> 64;
+      }
+
+      result[i + 4] = carry as u64;
+    }
+
+    (
+      Self([result[0], result[1], result[2], result[3]]),
+      Self([result[4], result[5], result[6], result[7]])
+    )
+  }
+
+  /// # `U256` 除以 `u64`，同时返回商和余数
+  ///
+  /// 从最高位的 u64 开始逐个相除，余数带入下一个 u64，无需构造 `U256` 除数
+  ///
+  /// ## 参数
+  ///
+  /// * `divisor` - 64 位无符号整数除数
+  ///
+  /// ## 返回值
+  ///
+  /// * `(Self, u64)` - 商和余数
+  ///
+  /// ## 注意事项
+  ///
+  /// * `divisor` 为 0 时 panic
+  fn div_rem_u64(self, divisor: u64) -> (Self, u64) {
+    let mut quotient = [0u64; 4];
+    let mut remainder = 0u64;
+
+    for i in (0 .. 4).rev() {
+      let dividend = ((remainder as u128) << 64) | self.0[i] as u128;
+
+      quotient[i] = (dividend / divisor as u128) as u64;
+      remainder = (dividend % divisor as u128) as u64;
+    }
+
+    (Self(quotient), remainder)
+  }
+
+  /// # `U256` 与 `u64` 判等
+  ///
+  /// 先检查高位是否全为 0，无需构造 `U256`
+  ///
+  /// ## 参数
+  ///
+  /// * `v` - 64 位无符号整数
+  ///
+  /// ## 返回值
+  ///
+  /// * `bool` - 是否相等
+  pub fn eq_u64(self, v: u64) -> bool {
+    self.0[1] == 0 && self.0[2] == 0 && self.0[3] == 0 && self.0[0] == v
+  }
+
+  /// # `U256` 与 `u64` 比较
+  ///
+  /// 先检查高位是否全为 0，无需构造 `U256`
+  ///
+  /// ## 参数
+  ///
+  /// * `v` - 64 位无符号整数
+  ///
+  /// ## 返回值
+  ///
+  /// * `cmp::Ordering` - 比较结果
+  pub fn cmp_u64(self, v: u64) -> cmp::Ordering {
+    if self.0[1] != 0 || self.0[2] != 0 || self.0[3] != 0 {
+      return cmp::Ordering::Greater;
+    }
+
+    self.0[0].cmp(&v)
+  }
+
+  /// # `U256` 常数时间判断是否为 0
+  ///
+  /// 不含分支，运算时间与数值无关
+  ///
+  /// ## 返回值
+  ///
+  /// * `u64` - 为 0 时返回全 1 掩码，否则返回 0
+  pub fn ct_is_zero(self) -> u64 {
+    let acc = self.0[0] | self.0[1] | self.0[2] | self.0[3];
+
+    // acc 为 0 时 acc | -acc 的最高位为 0，否则为 1
+    ((acc | acc.wrapping_neg()) >> 63).wrapping_sub(1)
+  }
+
+  /// # `U256` 常数时间判等
+  ///
+  /// 累积所有字的差异，不提前返回
+  ///
+  /// ## 参数
+  ///
+  /// * `other` - 另一个 256 位无符号整数
+  ///
+  /// ## 返回值
+  ///
+  /// * `bool` - 是否相等
+  pub fn ct_eq(self, other: Self) -> bool {
+    let acc = (self.0[0] ^ other.0[0])
+      | (self.0[1] ^ other.0[1])
+      | (self.0[2] ^ other.0[2])
+      | (self.0[3] ^ other.0[3]);
+
+    acc == 0
+  }
+
   /// # `U256` 返回前导 0 的个数
   ///
   /// ## 返回值
   ///
   /// * `usize` - 返回前导 0 的个数
   pub fn leading_zeros(self) -> usize {
-    for i in 3 ..= 1 {
+    for i in (1 ..= 3).rev() {
       if self.0[i] != 0 {
         return (3 - i) * 64 + self.0[i].leading_zeros() as usize;
       }
@@ -415,6 +904,71 @@ impl U256 {
     256 - self.leading_zeros()
   }
 
+  /// # `U256` 返回末尾 0 的个数
+  ///
+  /// ## 返回值
+  ///
+  /// * `usize` - 返回末尾 0 的个数，值为 0 时返回 256
+  pub fn trailing_zeros(self) -> usize {
+    for i in 0 .. 4 {
+      if self.0[i] != 0 {
+        return i * 64 + self.0[i].trailing_zeros() as usize;
+      }
+    }
+
+    256
+  }
+
+  /// # `U256` 返回二进制表示中 1 的个数
+  ///
+  /// ## 返回值
+  ///
+  /// * `usize` - 返回 1 的个数
+  pub fn count_ones(self) -> usize {
+    self.0.iter().map(|limb| limb.count_ones() as usize).sum()
+  }
+
+  /// # `U256` 读取指定位
+  ///
+  /// ## 参数
+  ///
+  /// * `i` - 位的下标，0 为最低位
+  ///
+  /// ## 返回值
+  ///
+  /// * `bool` - 该位是否为 1
+  ///
+  /// ## 特殊情况
+  ///
+  /// * 当 `i` 不小于 256 时，崩溃
+  pub fn bit(self, i: usize) -> bool {
+    assert!(i < 256, "bit index out of range");
+
+    (self.0[i / 64] >> (i % 64)) & 1 == 1
+  }
+
+  /// # `U256` 设置指定位
+  ///
+  /// ## 参数
+  ///
+  /// * `i` - 位的下标，0 为最低位
+  /// * `v` - 该位的新值
+  ///
+  /// ## 特殊情况
+  ///
+  /// * 当 `i` 不小于 256 时，崩溃
+  pub fn set_bit(&mut self, i: usize, v: bool) {
+    assert!(i < 256, "bit index out of range");
+
+    let mask = 1u64 << (i % 64);
+
+    if v {
+      self.0[i / 64] |= mask;
+    } else {
+      self.0[i / 64] &= !mask;
+    }
+  }
+
   /// # `U256` 返回一个随机数
   ///
   /// ## 参数
@@ -431,6 +985,40 @@ impl U256 {
     bytes.into()
   }
 
+  /// # `U256` 返回一个在 [0, `bound`) 范围内的随机数
+  ///
+  /// 先将随机数高于 `bound` 最高位的比特清零，再拒绝不小于 `bound`
+  /// 的值；每次采样被接受的概率不低于 1/2，且结果均匀分布
+  ///
+  /// ## 参数
+  ///
+  /// * `rng` - 随机数生成器
+  /// * `bound` - 上界，不包含
+  ///
+  /// ## 返回值
+  ///
+  /// * `Some(Self)` - 返回一个在 [0, `bound`) 范围内的随机数
+  /// * `None` - `bound` 为 0，范围为空
+  pub fn random_below<R: CryptoRng>(rng: &mut R, bound: Self) -> Option<Self> {
+    if bound.eq_u64(0) {
+      return None;
+    }
+
+    let mask = if bound.highest_bit() == 256 {
+      !Self::C_0
+    } else {
+      (Self::C_1 << bound.highest_bit() as u32) - Self::C_1
+    };
+
+    loop {
+      let r = Self::random(rng) & mask;
+
+      if r < bound {
+        break Some(r);
+      }
+    }
+  }
+
   /// # `U256` 返回一个在 [`min`, `max`) 范围内的随机数
   ///
   /// ## 参数
@@ -442,14 +1030,14 @@ impl U256 {
   /// ## 返回值
   ///
   /// * `Self` - 返回一个在 [`min`, `max`) 范围内的随机数
+  ///
+  /// ## 注意事项
+  ///
+  /// `min` 不小于 `max` 时范围为空，会导致 panic
   pub fn random_in_range<R: CryptoRng>(rng: &mut R, min: Self, max: Self) -> Self {
-    loop {
-      let r = Self::random(rng);
+    assert!(min < max, "Empty range");
 
-      if r >= min && r < max {
-        break r;
-      }
-    }
+    min + Self::random_below(rng, max - min).unwrap()
   }
 }
 
@@ -543,19 +1131,20 @@ impl ops::BitXorAssign for U256 {
 
 impl cmp::PartialOrd for U256 {
   fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
-    for i in 3 ..= 0 {
-      if self.0[i] != other.0[i] {
-        return Some(self.0[i].cmp(&other.0[i]));
-      }
-    }
-
-    Some(cmp::Ordering::Equal)
+    Some(self.cmp(other))
   }
 }
 
 impl cmp::Ord for U256 {
   fn cmp(&self, other: &Self) -> cmp::Ordering {
-    self.partial_cmp(other).unwrap()
+    // 从最高位的 u64 开始比较
+    for i in (0 ..= 3).rev() {
+      if self.0[i] != other.0[i] {
+        return self.0[i].cmp(&other.0[i]);
+      }
+    }
+
+    cmp::Ordering::Equal
   }
 }
 
@@ -576,11 +1165,11 @@ impl ops::Shl<u32> for U256 {
       result[i + blocks_shift as usize] = self.0[i];
     }
 
-    let mut carry = 0u64;
-    for i in 0 .. 4 {
-      result[i] = result[i] << bits_shift | carry;
+    // 从高位向低位处理，使低位的 u64 在被移动前移入高位；整 64 位移动时无需进位
+    for i in (0 .. 4).rev() {
+      let carry = if bits_shift == 0 || i == 0 { 0 } else { result[i - 1] >> (64 - bits_shift) };
 
-      carry = result[i] >> (64 - bits_shift);
+      result[i] = result[i] << bits_shift | carry;
     }
 
     Self(result)
@@ -628,11 +1217,11 @@ impl ops::Shr<u32> for U256 {
       result[i] = self.0[i + blocks_shift as usize];
     }
 
-    let mut carry = 0u64;
+    // 从低位向高位处理，使高位的 u64 在被移动前移入低位；整 64 位移动时无需进位
     for i in 0 .. 4 {
-      result[i] = result[i] >> bits_shift | carry;
+      let carry = if bits_shift == 0 || i == 3 { 0 } else { result[i + 1] << (64 - bits_shift) };
 
-      carry = result[i] << (64 - bits_shift);
+      result[i] = result[i] >> bits_shift | carry;
     }
 
     Self(result)
@@ -695,20 +1284,21 @@ impl ops::Mul for U256 {
   type Output = Self;
 
   fn mul(self, other: Self) -> Self {
-    let mut multiplier = self;
-    let mut multiplicand = other;
-    let mut result = Self::C_0;
+    let mut result = [0u64; 4];
 
-    while multiplicand > Self::C_0 {
-      if multiplicand.0[0] & 1 == 1 {
-        result = result + multiplier;
-      }
+    // 逐个 u64 相乘并累加，只保留低 256 位
+    for i in 0 .. 4 {
+      let mut carry = 0u128;
 
-      multiplier = multiplier << 1;
-      multiplicand = multiplicand >> 1;
+      for j in 0 .. 4 - i {
+        let v = (self.0[i] as u128) * (other.0[j] as u128) + result[i + j] as u128 + carry;
+
+        result[i + j] = v as u64;
+        carry = v >> 64;
+      }
     }
 
-    result
+    Self(result)
   }
 }
 
@@ -726,7 +1316,12 @@ impl ops::Div for U256 {
     let divisor = other;
     let mut quotient = Self::C_0;
 
-    for i in (dividend.highest_bit() - divisor.highest_bit()) as u32 ..= 0 {
+    if dividend < divisor {
+      return quotient;
+    }
+
+    // 从最大的移位量开始，逐位试减
+    for i in (0 ..= (dividend.highest_bit() - divisor.highest_bit()) as u32).rev() {
       let r = divisor << i;
 
       if dividend >= r {
@@ -752,7 +1347,12 @@ impl ops::Rem for U256 {
     let mut dividend = self;
     let divisor = other;
 
-    for i in (dividend.highest_bit() - divisor.highest_bit()) as u32 ..= 0 {
+    if dividend < divisor {
+      return dividend;
+    }
+
+    // 从最大的移位量开始，逐位试减
+    for i in (0 ..= (dividend.highest_bit() - divisor.highest_bit()) as u32).rev() {
       let r = divisor << i;
 
       if dividend >= r {
@@ -764,6 +1364,28 @@ impl ops::Rem for U256 {
   }
 }
 
+impl ops::Div<u64> for U256 {
+  type Output = Self;
+
+  fn div(self, other: u64) -> Self {
+    self.div_rem_u64(other).0
+  }
+}
+
+impl ops::DivAssign<u64> for U256 {
+  fn div_assign(&mut self, other: u64) {
+    *self = *self / other;
+  }
+}
+
+impl ops::Rem<u64> for U256 {
+  type Output = u64;
+
+  fn rem(self, other: u64) -> u64 {
+    self.div_rem_u64(other).1
+  }
+}
+
 impl From<u8> for U256 {
   fn from(value: u8) -> Self {
     Self([value as u64, 0, 0, 0])
@@ -819,67 +1441,80 @@ impl From<&[u8; 32]> for U256 {
 }
 
 impl TryFrom<&[u8]> for U256 {
-  type Error = &'static str;
+  type Error = Error;
 
   fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
     match value.try_into() {
       Ok(bytes) => Ok(Self::from_le_bytes(&bytes)),
-      Err(_) => Err("Invalid length")
+      Err(_) => Err(Error::InvalidLength)
     }
   }
 }
 
 impl TryFrom<&[u64]> for U256 {
-  type Error = &'static str;
+  type Error = Error;
 
   fn try_from(value: &[u64]) -> Result<Self, Self::Error> {
     match value.try_into() {
       Ok(bytes) => Ok(Self(bytes)),
-      Err(_) => Err("Invalid length")
+      Err(_) => Err(Error::InvalidLength)
     }
   }
 }
 
 impl TryFrom<Vec<u8>> for U256 {
-  type Error = &'static str;
+  type Error = Error;
 
   fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
     match value.try_into() {
       Ok(bytes) => Ok(Self::from_le_bytes(&bytes)),
-      Err(_) => Err("Invalid length")
+      Err(_) => Err(Error::InvalidLength)
     }
   }
 }
 
 impl TryFrom<&Vec<u8>> for U256 {
-  type Error = &'static str;
+  type Error = Error;
 
   fn try_from(value: &Vec<u8>) -> Result<Self, Self::Error> {
     match value.clone().try_into() {
       Ok(bytes) => Ok(Self::from_le_bytes(&bytes)),
-      Err(_) => Err("Invalid length")
+      Err(_) => Err(Error::InvalidLength)
     }
   }
 }
 
 impl TryFrom<Vec<u64>> for U256 {
-  type Error = &'static str;
+  type Error = Error;
 
   fn try_from(value: Vec<u64>) -> Result<Self, Self::Error> {
     match value.try_into() {
       Ok(bytes) => Ok(Self(bytes)),
-      Err(_) => Err("Invalid length")
+      Err(_) => Err(Error::InvalidLength)
     }
   }
 }
 
 impl TryFrom<&Vec<u64>> for U256 {
-  type Error = &'static str;
+  type Error = Error;
 
   fn try_from(value: &Vec<u64>) -> Result<Self, Self::Error> {
     match value.clone().try_into() {
       Ok(bytes) => Ok(Self(bytes)),
-      Err(_) => Err("Invalid length")
+      Err(_) => Err(Error::InvalidLength)
+    }
+  }
+}
+
+impl TryFrom<&str> for U256 {
+  type Error = ParseU256Error;
+
+  /// 带 `0x` 或 `0X` 前缀时按十六进制解析，否则按十进制解析
+  fn try_from(value: &str) -> Result<Self, Self::Error> {
+    if value.starts_with("0x") || value.starts_with("0X") {
+      Self::from_hex(value)
+    } else {
+      Self::from_dec_str(value)
     }
   }
 }
@@ -907,3 +1542,416 @@ impl From<U256> for Vec<u64> {
     value.into_le_u64_array().to_vec()
   }
 }
+
+impl fmt::LowerHex for U256 {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.pad_integral(true, "0x", &self.hex_digits(false))
+  }
+}
+
+impl fmt::UpperHex for U256 {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.pad_integral(true, "0x", &self.hex_digits(true))
+  }
+}
+
+impl fmt::Debug for U256 {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{:#066x}", self)
+  }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for U256 {
+  fn zeroize(&mut self) {
+    self.0.zeroize();
+  }
+}
+
+/// # `U256` 字符串解析错误
+///
+/// ## 变体
+///
+/// * `InvalidChar` - 含有不属于对应进制的字符
+/// * `Overflow` - 数值超出 256 位，或十六进制字符串超过 64 位
+/// * `Empty` - 字符串（去除前缀后）为空
+///
+/// ## 实现特征
+///
+/// * `Clone`
+/// * `Copy`
+/// * `PartialEq`
+/// * `Eq`
+/// * `Debug`
+/// * `Display`
+/// * `Error`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ParseU256Error {
+  InvalidChar,
+  Overflow,
+  Empty
+}
+
+impl fmt::Display for ParseU256Error {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ParseU256Error::InvalidChar => write!(f, "Invalid character"),
+      ParseU256Error::Overflow => write!(f, "Number too large"),
+      ParseU256Error::Empty => write!(f, "Empty string")
+    }
+  }
+}
+
+impl error::Error for ParseU256Error {
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn to_minimal_bits_of_zero_is_empty() {
+    let bits = U256::C_0.to_minimal_bits();
+
+    assert_eq!(bits.len(), 0);
+    assert_eq!(U256::from_minimal_bits(&bits), Ok(U256::C_0));
+  }
+
+  #[test]
+  fn to_minimal_bits_of_one_is_single_bit() {
+    let bits = U256::C_1.to_minimal_bits();
+
+    assert_eq!(bits.len(), 1);
+    assert_eq!(bits.get_bytes(), &[0x80]);
+    assert_eq!(U256::from_minimal_bits(&bits), Ok(U256::C_1));
+  }
+
+  #[test]
+  fn to_minimal_bits_round_trips_across_limbs() {
+    let value = U256::from_le_u64_array(&[0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210, 0x5, 0]);
+    let bits = value.to_minimal_bits();
+
+    assert_eq!(bits.len(), 131);
+    assert_eq!(U256::from_minimal_bits(&bits), Ok(value));
+    assert_eq!(U256::from_minimal_bits(&U256::MAX.to_minimal_bits()), Ok(U256::MAX));
+  }
+
+  /// # 由种子生成确定的测试用 256 位整数（SplitMix64）
+  fn sample(seed: u64) -> U256 {
+    let mut state = seed;
+    let mut limbs = [0u64; 4];
+
+    for limb in limbs.iter_mut() {
+      state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+      let mut z = state;
+      z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+      z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+      *limb = z ^ (z >> 31);
+    }
+
+    U256::from_le_u64_array(&limbs)
+  }
+
+  #[test]
+  fn div_rem_u64_matches_u256_divisor() {
+    for seed in 0 .. 64 {
+      let value = sample(seed);
+
+      for divisor in [1u64, 2, 3, 7, 10, 255, 1 << 32, u64::MAX] {
+        let (quotient, remainder) = (value / divisor, value % divisor);
+
+        assert_eq!(quotient, value / U256::from(divisor));
+        assert_eq!(U256::from(remainder), value % U256::from(divisor));
+        assert_eq!(quotient * U256::from(divisor) + U256::from(remainder), value);
+      }
+    }
+  }
+
+  #[test]
+  fn leading_zeros_scans_every_limb() {
+    assert_eq!(U256::C_0.leading_zeros(), 256);
+    assert_eq!(U256::C_1.leading_zeros(), 255);
+    assert_eq!((U256::C_1 << 64u32).leading_zeros(), 191);
+    assert_eq!((U256::C_1 << 130u32).leading_zeros(), 125);
+    assert_eq!((U256::C_1 << 255u32).leading_zeros(), 0);
+    assert_eq!(U256::MAX.leading_zeros(), 0);
+    assert_eq!((U256::C_1 << 200u32).highest_bit(), 201);
+  }
+
+  #[test]
+  fn ord_compares_high_limbs_first() {
+    let low = U256::from_le_u64_array(&[u64::MAX, u64::MAX, u64::MAX, 0]);
+    let high = U256::from_le_u64_array(&[0, 0, 0, 1]);
+
+    assert!(low < high);
+    assert!(high > low);
+    assert_eq!(low.cmp(&low), cmp::Ordering::Equal);
+    assert!(U256::from_le_u64_array(&[0, 2, 0, 0]) > U256::from_le_u64_array(&[5, 1, 0, 0]));
+    assert!(U256::C_0 < U256::MAX);
+  }
+
+  #[test]
+  fn div_and_rem_run_long_division() {
+    let n = U256::from_be_u64_array(&[
+      0xfffffffeffffffff,
+      0xffffffffffffffff,
+      0x7203df6b21c6052b,
+      0x53bbf40939d54123
+    ]);
+
+    assert_eq!(U256::from(100u64) / U256::from(7u64), U256::from(14u64));
+    assert_eq!(U256::from(100u64) % U256::from(7u64), U256::from(2u64));
+    assert_eq!(U256::MAX / U256::MAX, U256::C_1);
+    assert_eq!(U256::MAX % n, U256::MAX - n);
+    assert_eq!(U256::MAX / n, U256::C_1);
+
+    for seed in 0 .. 32 {
+      let (a, b) = (sample(seed), sample(seed + 100) >> (seed as u32 * 7));
+      assert_eq!(a / b * b + a % b, a);
+      assert!(a % b < b);
+    }
+  }
+
+  #[test]
+  fn shifts_by_whole_limbs() {
+    let x = U256::from_le_u64_array(&[1, 2, 3, 4]);
+
+    assert_eq!(x << 64u32, U256::from_le_u64_array(&[0, 1, 2, 3]));
+    assert_eq!(x << 128u32, U256::from_le_u64_array(&[0, 0, 1, 2]));
+    assert_eq!(x >> 192u32, U256::from_le_u64_array(&[4, 0, 0, 0]));
+    assert_eq!(x << 256u32, U256::C_0);
+    assert_eq!(x >> 256u32, U256::C_0);
+    assert_eq!(x << 0u32, x);
+  }
+
+  #[test]
+  fn shifts_carry_across_limbs() {
+    let x = U256::from_le_u64_array(&[0x8000_0000_0000_0001, 0, 0, 0]);
+
+    assert_eq!(x << 1u32, U256::from_le_u64_array(&[2, 1, 0, 0]));
+    assert_eq!(x << 65u32, U256::from_le_u64_array(&[0, 2, 1, 0]));
+    assert_eq!((x << 65u32) >> 65u32, x);
+    assert_eq!(U256::MAX >> 255u32, U256::C_1);
+  }
+
+  #[test]
+  fn bit_accessors_cover_every_limb() {
+    let high = U256::C_1 << 130u32;
+
+    assert_eq!(U256::C_0.trailing_zeros(), 256);
+    assert_eq!(U256::C_0.count_ones(), 0);
+    assert_eq!(high.trailing_zeros(), 130);
+    assert_eq!(high.count_ones(), 1);
+    assert_eq!(U256::MAX.trailing_zeros(), 0);
+    assert_eq!(U256::MAX.count_ones(), 256);
+
+    assert!(high.bit(130));
+    assert!(!high.bit(129) && !high.bit(131));
+    assert!(!U256::C_0.bit(255));
+    assert!(U256::MAX.bit(0) && U256::MAX.bit(255));
+
+    let mut value = U256::C_0;
+    for i in [0, 63, 64, 130, 255] {
+      value.set_bit(i, true);
+      assert!(value.bit(i));
+    }
+    assert_eq!(value.count_ones(), 5);
+
+    value.set_bit(130, false);
+    value.set_bit(131, false);
+    assert_eq!(value, U256::from_le_u64_array(&[1 << 63 | 1, 1, 0, 1 << 63]));
+
+    let mut max = U256::MAX;
+    max.set_bit(255, false);
+    assert_eq!(max, U256::MAX >> 1u32);
+  }
+
+  #[test]
+  #[should_panic(expected = "bit index out of range")]
+  fn bit_panics_at_index_256() {
+    U256::MAX.bit(256);
+  }
+
+  #[test]
+  #[should_panic(expected = "bit index out of range")]
+  fn set_bit_panics_at_index_256() {
+    let mut value = U256::C_0;
+    value.set_bit(256, true);
+  }
+
+  #[test]
+  fn ct_eq_checks_every_limb() {
+    for seed in 0 .. 4 {
+      let value = sample(seed);
+
+      assert!(value.ct_eq(value));
+
+      for limb in 0 .. 4 {
+        let mut limbs = value.into_le_u64_array();
+        limbs[limb] ^= 1 << (17 * limb);
+
+        assert!(!value.ct_eq(U256::from_le_u64_array(&limbs)));
+        assert!(!U256::from_le_u64_array(&limbs).ct_eq(value));
+      }
+    }
+
+    assert!(U256::C_0.ct_eq(U256::C_0));
+    assert!(!U256::C_0.ct_eq(U256::MAX));
+  }
+
+  #[test]
+  fn ct_is_zero_returns_masks() {
+    assert_eq!(U256::C_0.ct_is_zero(), u64::MAX);
+    assert_eq!(U256::C_1.ct_is_zero(), 0);
+    assert_eq!(U256::MAX.ct_is_zero(), 0);
+
+    for limb in 0 .. 4 {
+      for bit in [0, 1, 63] {
+        let mut limbs = [0u64; 4];
+        limbs[limb] = 1 << bit;
+
+        assert_eq!(U256::from_le_u64_array(&limbs).ct_is_zero(), 0);
+      }
+    }
+  }
+
+  #[test]
+  #[cfg(feature = "zeroize")]
+  fn zeroize_clears_every_limb() {
+    use zeroize::Zeroize;
+
+    let mut value = U256::MAX;
+    value.zeroize();
+
+    assert_eq!(value.into_le_u64_array(), [0; 4]);
+  }
+
+  #[test]
+  fn formats_as_hex() {
+    let value = U256::from_le_u64_array(&[0xdead_beef, 0, 0x1, 0]);
+
+    assert_eq!(format!("{:x}", value), "1000000000000000000000000deadbeef");
+    assert_eq!(format!("{:X}", value), "1000000000000000000000000DEADBEEF");
+    assert_eq!(format!("{:#x}", value), "0x1000000000000000000000000deadbeef");
+    assert_eq!(format!("{:#X}", U256::from(255u64)), "0xFF");
+
+    assert_eq!(format!("{:x}", U256::C_0), "0");
+    assert_eq!(format!("{:#x}", U256::C_0), "0x0");
+    assert_eq!(format!("{:08x}", U256::from(255u64)), "000000ff");
+    assert_eq!(format!("{:#010x}", U256::from(255u64)), "0x000000ff");
+    assert_eq!(format!("{:x}", U256::MAX), "f".repeat(64));
+  }
+
+  #[test]
+  fn debug_prints_padded_hex() {
+    assert_eq!(format!("{:?}", U256::C_0), format!("0x{}", "0".repeat(64)));
+    assert_eq!(format!("{:?}", U256::from(0xabu64)), format!("0x{}ab", "0".repeat(62)));
+    assert_eq!(format!("{:?}", U256::MAX), format!("0x{}", "f".repeat(64)));
+  }
+
+  #[test]
+  fn hex_round_trips() {
+    for value in [U256::C_0, U256::C_1, U256::MAX, sample(10), sample(11)] {
+      let hex = value.to_hex();
+
+      assert_eq!(hex.len(), 64);
+      assert_eq!(U256::from_hex(&hex), Ok(value));
+      assert_eq!(U256::from_hex(&hex.to_uppercase()), Ok(value));
+    }
+
+    // 前导零与 0x 前缀
+    assert_eq!(U256::C_1.to_hex(), format!("{}1", "0".repeat(63)));
+    assert_eq!(U256::from_hex("000ff"), Ok(U256::from(255u64)));
+    assert_eq!(U256::from_hex("0xff"), Ok(U256::from(255u64)));
+    assert_eq!(U256::from_hex("0XFF"), Ok(U256::from(255u64)));
+    assert_eq!(U256::from_hex(&format!("0x{}", "f".repeat(64))), Ok(U256::MAX));
+  }
+
+  #[test]
+  fn from_hex_rejects_invalid_input() {
+    assert_eq!(U256::from_hex(""), Err(ParseU256Error::Empty));
+    assert_eq!(U256::from_hex("0x"), Err(ParseU256Error::Empty));
+    assert_eq!(U256::from_hex("12g4"), Err(ParseU256Error::InvalidChar));
+    assert_eq!(U256::from_hex("-1"), Err(ParseU256Error::InvalidChar));
+    assert_eq!(U256::from_hex(" 1"), Err(ParseU256Error::InvalidChar));
+    assert_eq!(U256::from_hex("0x0x1"), Err(ParseU256Error::InvalidChar));
+
+    // 超过 64 位，即便高位为 0
+    assert_eq!(U256::from_hex(&"f".repeat(65)), Err(ParseU256Error::Overflow));
+    assert_eq!(U256::from_hex(&format!("0{}", "f".repeat(64))), Err(ParseU256Error::Overflow));
+  }
+
+  #[test]
+  fn checked_arithmetic_detects_overflow() {
+    let two_128 = U256::from_le_u64_array(&[0, 0, 1, 0]);
+    let two_127 = U256::from_le_u64_array(&[0, 1 << 63, 0, 0]);
+
+    assert_eq!(U256::MAX.checked_add(U256::C_1), None);
+    assert_eq!(U256::MAX.checked_add(U256::C_0), Some(U256::MAX));
+    assert_eq!((U256::MAX - U256::C_1).checked_add(U256::C_1), Some(U256::MAX));
+
+    assert_eq!(U256::C_0.checked_sub(U256::C_1), None);
+    assert_eq!(U256::C_1.checked_sub(U256::C_1), Some(U256::C_0));
+    assert_eq!(
+      two_128.checked_sub(U256::C_1),
+      Some(U256::from_le_u64_array(&[u64::MAX, u64::MAX, 0, 0]))
+    );
+
+    assert_eq!(two_128.checked_mul(two_128), None);
+    assert_eq!(two_127.checked_mul(two_128), Some(U256::from_le_u64_array(&[0, 0, 0, 1 << 63])));
+    assert_eq!(U256::MAX.checked_mul(U256::C_1), Some(U256::MAX));
+    assert_eq!(U256::MAX.checked_mul(U256::C_2), None);
+    assert_eq!(U256::MAX.checked_mul(U256::C_0), Some(U256::C_0));
+  }
+
+  #[test]
+  fn widening_mul_returns_low_and_high_halves() {
+    let (low, high) = U256::MAX.widening_mul(U256::MAX);
+    assert_eq!((low, high), (U256::C_1, U256::MAX - U256::C_1));
+
+    let (low, high) = U256::from(u64::MAX).widening_mul(U256::from(u64::MAX));
+    let expected = (u64::MAX as u128) * (u64::MAX as u128);
+    assert_eq!(low, U256::from_le_u64_array(&[expected as u64, (expected >> 64) as u64, 0, 0]));
+    assert_eq!(high, U256::C_0);
+  }
+
+  #[test]
+  fn widening_mul_matches_mod_mul() {
+    use crate::math::ecc::ModOps;
+
+    // 最高位为 1 的模数 m 满足 2^256 mod m = 2^256 - m
+    let modulus = sample(20) | U256::from_le_u64_array(&[0, 0, 0, 1 << 63]);
+    let two_256 = U256::C_0 - modulus;
+
+    for seed in 0 .. 8 {
+      let (a, b) = (sample(100 + 2 * seed), sample(101 + 2 * seed));
+      let (low, high) = a.widening_mul(b);
+
+      assert_eq!(low, a * b);
+      assert_eq!(high.mod_mul(two_256, modulus).mod_add(low, modulus), a.mod_mul(b, modulus));
+    }
+  }
+
+  #[test]
+  fn cmp_u64_treats_high_limbs_as_greater() {
+    use cmp::Ordering::{Equal, Greater, Less};
+
+    assert_eq!(U256::from(5u64).cmp_u64(5), Equal);
+    assert_eq!(U256::from(5u64).cmp_u64(6), Less);
+    assert_eq!(U256::from(5u64).cmp_u64(4), Greater);
+    assert_eq!(U256::C_0.cmp_u64(0), Equal);
+
+    for limb in 1 .. 4 {
+      let mut limbs = [0u64; 4];
+      limbs[limb] = 1;
+      let value = U256::from_le_u64_array(&limbs);
+
+      assert_eq!(value.cmp_u64(u64::MAX), Greater);
+      assert_eq!(value.cmp_u64(0), Greater);
+      assert!(!value.eq_u64(0));
+    }
+
+    assert!(U256::from(7u64).eq_u64(7));
+    assert!(!U256::from_le_u64_array(&[7, 0, 0, 1]).eq_u64(7));
+  }
+}
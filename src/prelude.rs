@@ -0,0 +1,63 @@
+//! # 常用类型与函数
+//!
+//! 通过 `use sm_algorithm::prelude::*` 一次性引入常用的类型、特征和函数
+//!
+//! ```
+//! use sm_algorithm::prelude::*;
+//!
+//! let hex = |s: &str| U256::from_hex(s).unwrap();
+//! let params = EccParams::new(
+//!   hex("fffffffeffffffffffffffffffffffffffffffff00000000fffffffffffffffc"),
+//!   hex("28e9fa9e9d9f5e344d5a9e4bcf6509a7f39789f515ab8f92ddbcbd414d940e93"),
+//!   hex("fffffffeffffffffffffffffffffffffffffffff00000000ffffffffffffffff"),
+//!   hex("fffffffeffffffffffffffffffffffff7203df6b21c6052b53bbf40939d54123"),
+//!   hex("32c4ae2c1f1981195f9904466a39c9948fe30bbff2660be1715a4589334c74c7"),
+//!   hex("bc3736a2f4f6779c59bdcee36b692153d0a9877cc62a474002df32e52139f0a0")
+//! );
+//!
+//! let private_key = hex("3945208f7b2144b13f36e38ac6d39f95889393692860b51a42fb81ef4df7c5b8");
+//! let public_key = public_key_from_private(private_key, &params).unwrap();
+//! assert!(public_key == EccPoint::new_simple(params.g_x, params.g_y, &params) * private_key);
+//! assert!(pubkey_validate(&public_key));
+//!
+//! let message = BitSequence::with_bytes(b"message digest");
+//! let signing_input = SigningInput::with_id_bytes(&params, b"ALICE", public_key, private_key);
+//! let signature = generate_signature_deterministic(&signing_input, &message).unwrap();
+//!
+//! let verification_input = SigningVerificationInput::with_id_bytes(&params, b"ALICE", public_key);
+//! assert!(verify_signature(&verification_input, &message, signature));
+//!
+//! let tampered = BitSequence::with_bytes(b"message digesT");
+//! assert!(!verify_signature(&verification_input, &tampered, signature));
+//! ```
+
+#[cfg(feature = "std")]
+pub use crate::sm_2::{encrypt, encrypt_with_format, generate_signature, key_gen};
+pub use crate::{
+  math::{
+    bytes::{BitSequence, ByteOrder},
+    ecc::{EccOps, EccParams, EccPoint, ModInv, ModOps, MulStrategy},
+    u256::{ParseU256Error, U256}
+  },
+  sm_2::{
+    decrypt,
+    decrypt_with_format,
+    encrypt_with_rng,
+    generate_signature_deterministic,
+    generate_signature_with_rng,
+    key_gen_with_rng,
+    pubkey_validate,
+    public_key_from_private,
+    verify_batch,
+    verify_signature,
+    CipherFormat,
+    GeneratorTable,
+    KeyPair,
+    PrecomputedSigner,
+    SigningInput,
+    SigningVerificationInput
+  },
+  sm_3::{hash, hash_bytes, hmac, HmacSm3, Sm3},
+  sm_4::{Sm4, Sm4Key},
+  Error
+};
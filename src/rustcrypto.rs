@@ -0,0 +1,244 @@
+//! # RustCrypto 特征集成
+//!
+//! 为 `Sm3` 实现 `digest::Digest`（通过 `Update`、`FixedOutput`
+//! 等特征的通用实现）， 为 `Sm4` 实现
+//! `cipher::BlockEncrypt`/`BlockDecrypt`，以便接入 RustCrypto
+//! 生态中的通用构造（HMAC、HKDF、分组密码工作模式等）
+//!
+//! 仅在启用 `rustcrypto` 特性时编译
+//!
+//! `Sm3` 和 `Sm4` 的同名固有方法（如
+//! `update`、`encrypt_block`）优先于特征方法，
+//! 需要调用特征方法时请使用完全限定语法，例如
+//! `BlockEncrypt::encrypt_block(&key, &mut block)`
+
+use {
+  crate::{sm_3::Sm3, sm_4::Sm4},
+  cipher::{
+    consts::{U1, U16},
+    inout::InOut,
+    Block,
+    BlockBackend,
+    BlockCipher,
+    BlockClosure,
+    BlockDecrypt,
+    BlockEncrypt,
+    Key,
+    KeyInit,
+    KeySizeUser,
+    ParBlocksSizeUser
+  },
+  digest::{
+    consts::{U32, U64},
+    core_api::BlockSizeUser,
+    FixedOutput,
+    FixedOutputReset,
+    HashMarker,
+    Output,
+    OutputSizeUser,
+    Reset,
+    Update
+  }
+};
+
+impl HashMarker for Sm3 {
+}
+
+impl OutputSizeUser for Sm3 {
+  type OutputSize = U32;
+}
+
+impl BlockSizeUser for Sm3 {
+  type BlockSize = U64;
+}
+
+impl Update for Sm3 {
+  fn update(&mut self, data: &[u8]) {
+    Sm3::update(self, data);
+  }
+}
+
+impl FixedOutput for Sm3 {
+  fn finalize_into(self, out: &mut Output<Self>) {
+    out.copy_from_slice(&Sm3::finalize(self));
+  }
+}
+
+impl Reset for Sm3 {
+  fn reset(&mut self) {
+    Sm3::reset(self);
+  }
+}
+
+impl FixedOutputReset for Sm3 {
+  fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+    out.copy_from_slice(&self.clone().finalize());
+    Sm3::reset(self);
+  }
+}
+
+impl KeySizeUser for Sm4 {
+  type KeySize = U16;
+}
+
+impl KeyInit for Sm4 {
+  fn new(key: &Key<Self>) -> Self {
+    Sm4::new(&(*key).into())
+  }
+}
+
+impl cipher::BlockSizeUser for Sm4 {
+  type BlockSize = U16;
+}
+
+impl BlockCipher for Sm4 {
+}
+
+impl BlockEncrypt for Sm4 {
+  fn encrypt_with_backend(&self, f: impl BlockClosure<BlockSize = U16>) {
+    f.call(&mut Sm4Backend { key: self, decrypt: false });
+  }
+}
+
+impl BlockDecrypt for Sm4 {
+  fn decrypt_with_backend(&self, f: impl BlockClosure<BlockSize = U16>) {
+    f.call(&mut Sm4Backend { key: self, decrypt: true });
+  }
+}
+
+/// # SM4 分组处理后端
+///
+/// 逐个分组调用常数时间的分组变换
+///
+/// ## 成员
+///
+/// * `key` - 轮密钥
+/// * `decrypt` - 是否解密
+struct Sm4Backend<'a> {
+  key: &'a Sm4,
+  decrypt: bool
+}
+
+impl cipher::BlockSizeUser for Sm4Backend<'_> {
+  type BlockSize = U16;
+}
+
+impl ParBlocksSizeUser for Sm4Backend<'_> {
+  type ParBlocksSize = U1;
+}
+
+impl BlockBackend for Sm4Backend<'_> {
+  fn proc_block(&mut self, mut block: InOut<'_, '_, Block<Self>>) {
+    let input: [u8; 16] = (*block.get_in()).into();
+
+    let output = if self.decrypt {
+      self.key.decrypt_block_ct(&input)
+    } else {
+      self.key.encrypt_block_ct(&input)
+    };
+
+    block.get_out().copy_from_slice(&output);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, cipher::generic_array::GenericArray, digest::Digest};
+
+  const ABC_DIGEST: [u8; 32] = [
+    0x66,
+    0xc7,
+    0xf0,
+    0xf4,
+    0x62,
+    0xee,
+    0xed,
+    0xd9,
+    0xd1,
+    0xf2,
+    0xd4,
+    0x6b,
+    0xdc,
+    0x10,
+    0xe4,
+    0xe2,
+    0x41,
+    0x67,
+    0xc4,
+    0x87,
+    0x5c,
+    0xf2,
+    0xf7,
+    0xa2,
+    0x29,
+    0x7d,
+    0xa0,
+    0x2b,
+    0x8f,
+    0x4b,
+    0xa8,
+    0xe0
+  ];
+
+  #[test]
+  fn digest_matches_standard_example() {
+    assert_eq!(<Sm3 as Digest>::digest(b"abc").as_slice(), &ABC_DIGEST);
+
+    let mut hasher = <Sm3 as Digest>::new();
+    Digest::update(&mut hasher, b"a");
+    Digest::update(&mut hasher, b"bc");
+    assert_eq!(hasher.finalize_reset().as_slice(), &ABC_DIGEST);
+
+    Digest::update(&mut hasher, b"abc");
+    assert_eq!(hasher.finalize().as_slice(), &ABC_DIGEST);
+  }
+
+  #[test]
+  fn block_cipher_matches_standard_example() {
+    let key = [
+      0x01,
+      0x23,
+      0x45,
+      0x67,
+      0x89,
+      0xab,
+      0xcd,
+      0xef,
+      0xfe,
+      0xdc,
+      0xba,
+      0x98,
+      0x76,
+      0x54,
+      0x32,
+      0x10
+    ];
+    let expected = [
+      0x68,
+      0x1e,
+      0xdf,
+      0x34,
+      0xd2,
+      0x06,
+      0x96,
+      0x5e,
+      0x86,
+      0xb3,
+      0xe9,
+      0x4f,
+      0x53,
+      0x6e,
+      0x42,
+      0x46
+    ];
+
+    let cipher = <Sm4 as KeyInit>::new(&key.into());
+    let mut block = GenericArray::from(key);
+
+    BlockEncrypt::encrypt_block(&cipher, &mut block);
+    assert_eq!(block.as_slice(), &expected);
+
+    BlockDecrypt::decrypt_block(&cipher, &mut block);
+    assert_eq!(block.as_slice(), &key);
+  }
+}
@@ -0,0 +1,288 @@
+//! # serde 序列化支持
+//!
+//! 为 `U256`、`EccPoint` 和 `BitSequence` 实现 `serde::Serialize`，为 `U256`
+//! 和 `BitSequence` 实现 `serde::Deserialize`：
+//!
+//! * `U256` 在 JSON 等可读格式中为 64 位十六进制字符串
+//! * `U256` 在二进制格式中为 32 字节大端序字节串
+//! * `EccPoint` 为 65 字节的未压缩编码，无穷远点为单字节 0x00
+//! * `BitSequence` 为 `{ bytes, last_byte_len }` 结构
+//!
+//! `EccPoint` 借用曲线参数，无法直接反序列化，需先反序列化为 `EccPointBytes`，
+//! 再调用 `with_params` 绑定曲线参数并校验
+//!
+//! 仅在启用 `serde` 特性时编译
+
+use {
+  crate::{
+    math::{
+      bytes::{BitSequence, ByteOrder},
+      ecc::{EccParams, EccPoint},
+      u256::U256
+    },
+    Error
+  },
+  alloc::{format, string::String, vec::Vec},
+  core::fmt,
+  serde::{
+    de::{self, Deserializer, MapAccess, SeqAccess, Visitor},
+    ser::SerializeStruct,
+    Deserialize,
+    Serialize,
+    Serializer
+  }
+};
+
+/// # 以字节串形式序列化的字节切片
+struct Bytes<'a>(&'a [u8]);
+
+impl Serialize for Bytes<'_> {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_bytes(self.0)
+  }
+}
+
+/// # 以字节串形式反序列化的字节序列
+///
+/// 同时接受字节串与 u8 数组，后者为 JSON 等格式中字节串的表示
+struct ByteBuf(Vec<u8>);
+
+/// # `ByteBuf` 的访问器
+struct ByteBufVisitor;
+
+impl<'de> Visitor<'de> for ByteBufVisitor {
+  type Value = ByteBuf;
+
+  fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "a byte string")
+  }
+
+  fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<ByteBuf, E> {
+    Ok(ByteBuf(v.to_vec()))
+  }
+
+  fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<ByteBuf, E> {
+    Ok(ByteBuf(v))
+  }
+
+  fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<ByteBuf, A::Error> {
+    let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+
+    while let Some(byte) = seq.next_element()? {
+      bytes.push(byte);
+    }
+
+    Ok(ByteBuf(bytes))
+  }
+}
+
+impl<'de> Deserialize<'de> for ByteBuf {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    deserializer.deserialize_bytes(ByteBufVisitor)
+  }
+}
+
+impl Serialize for U256 {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+      serializer.serialize_str(&self.to_hex())
+    } else {
+      serializer.serialize_bytes(&self.into_be_bytes())
+    }
+  }
+}
+
+impl<'de> Deserialize<'de> for U256 {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    if deserializer.is_human_readable() {
+      let s = String::deserialize(deserializer)?;
+
+      return U256::from_hex(&s).map_err(de::Error::custom);
+    }
+
+    let ByteBuf(bytes) = ByteBuf::deserialize(deserializer)?;
+    let bytes: [u8; 32] =
+      bytes.try_into().map_err(|v: Vec<u8>| de::Error::invalid_length(v.len(), &"32 bytes"))?;
+
+    Ok(U256::from_be_bytes(&bytes))
+  }
+}
+
+impl Serialize for EccPoint<'_> {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_bytes(&self.to_bytes_with_order(ByteOrder::BigEndian))
+  }
+}
+
+/// # 反序列化得到的椭圆曲线点编码
+///
+/// 尚未绑定曲线参数的 `EccPoint` 序列化结果
+///
+/// ## 方法
+///
+/// * `with_params<'a>(self, params: &'a EccParams) -> Result<EccPoint<'a>,
+///   Error>` - 绑定曲线参数，解析编码并校验点在曲线上
+///
+/// ## 实现特征
+///
+/// * `Clone`
+/// * `Deserialize`
+/// * `Serialize`
+#[derive(Clone)]
+pub struct EccPointBytes(Vec<u8>);
+
+impl EccPointBytes {
+  pub fn with_params<'a>(self, params: &'a EccParams) -> Result<EccPoint<'a>, Error> {
+    let point = EccPoint::try_from_bytes(&self.0, params)?;
+
+    if !point.validate_on_curve() {
+      return Err(Error::NotOnCurve);
+    }
+
+    Ok(point)
+  }
+}
+
+impl Serialize for EccPointBytes {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_bytes(&self.0)
+  }
+}
+
+impl<'de> Deserialize<'de> for EccPointBytes {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    Ok(EccPointBytes(ByteBuf::deserialize(deserializer)?.0))
+  }
+}
+
+impl Serialize for BitSequence {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    let mut state = serializer.serialize_struct("BitSequence", 2)?;
+    state.serialize_field("bytes", &Bytes(self.get_bytes()))?;
+    state.serialize_field("last_byte_len", &self.get_last_byte_len())?;
+    state.end()
+  }
+}
+
+/// # `BitSequence` 的字段名
+const BIT_SEQUENCE_FIELDS: &[&str] = &["bytes", "last_byte_len"];
+
+/// # `BitSequence` 的访问器
+struct BitSequenceVisitor;
+
+impl BitSequenceVisitor {
+  /// # 校验字段并构造比特序列
+  ///
+  /// `last_byte_len` 须小于 8，且不为 0 时字节序列不能为空
+  fn build<E: de::Error>(bytes: Vec<u8>, last_byte_len: u8) -> Result<BitSequence, E> {
+    if last_byte_len >= 8 || (last_byte_len != 0 && bytes.is_empty()) {
+      return Err(E::invalid_value(
+        de::Unexpected::Unsigned(last_byte_len as u64),
+        &"a valid last_byte_len"
+      ));
+    }
+
+    Ok(BitSequence::new(bytes, last_byte_len))
+  }
+}
+
+impl<'de> Visitor<'de> for BitSequenceVisitor {
+  type Value = BitSequence;
+
+  fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "struct BitSequence")
+  }
+
+  fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<BitSequence, A::Error> {
+    let ByteBuf(bytes) = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+    let last_byte_len = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+    Self::build(bytes, last_byte_len)
+  }
+
+  fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<BitSequence, A::Error> {
+    let mut bytes = None;
+    let mut last_byte_len = None;
+
+    while let Some(key) = map.next_key::<String>()? {
+      match key.as_str() {
+        "bytes" if bytes.is_none() => bytes = Some(map.next_value::<ByteBuf>()?.0),
+        "last_byte_len" if last_byte_len.is_none() => last_byte_len = Some(map.next_value()?),
+        "bytes" | "last_byte_len" => {
+          return Err(de::Error::custom(format!("duplicate field `{}`", key)))
+        },
+        _ => return Err(de::Error::unknown_field(&key, BIT_SEQUENCE_FIELDS))
+      }
+    }
+
+    Self::build(
+      bytes.ok_or_else(|| de::Error::missing_field("bytes"))?,
+      last_byte_len.ok_or_else(|| de::Error::missing_field("last_byte_len"))?
+    )
+  }
+}
+
+impl<'de> Deserialize<'de> for BitSequence {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    deserializer.deserialize_struct("BitSequence", BIT_SEQUENCE_FIELDS, BitSequenceVisitor)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::sm_2::GeneratorTable, alloc::vec};
+
+  #[test]
+  fn u256_round_trips_as_hex_string() {
+    for value in [U256::C_0, U256::C_1, U256::MAX, U256::from(0x1234_5678u64) << 100u32] {
+      let json = serde_json::to_string(&value).unwrap();
+
+      assert_eq!(json, format!("\"{}\"", value.to_hex()));
+      assert_eq!(serde_json::from_str::<U256>(&json).unwrap(), value);
+    }
+
+    assert!(serde_json::from_str::<U256>("\"xyz\"").is_err());
+  }
+
+  #[test]
+  fn ecc_point_round_trips_through_with_params() {
+    let point = GeneratorTable::new().mul_base(U256::from(7u64));
+    let json = serde_json::to_string(&point).unwrap();
+    let bytes: EccPointBytes = serde_json::from_str(&json).unwrap();
+
+    assert!(bytes.clone().with_params(point.params).unwrap() == point);
+
+    // 篡改 y 坐标的最后一个字节后不再位于曲线上
+    let mut tampered: Vec<u8> = serde_json::from_str(&json).unwrap();
+    tampered[64] ^= 1;
+    let tampered: EccPointBytes =
+      serde_json::from_str(&serde_json::to_string(&tampered).unwrap()).unwrap();
+
+    assert!(tampered.with_params(point.params).is_err());
+  }
+
+  #[test]
+  fn bit_sequence_round_trips_as_struct() {
+    let bits = BitSequence::new(vec![0xab, 0xc0], 3);
+    let json = serde_json::to_string(&bits).unwrap();
+
+    assert_eq!(json, r#"{"bytes":[171,192],"last_byte_len":3}"#);
+    assert!(serde_json::from_str::<BitSequence>(&json).unwrap() == bits);
+
+    let empty = BitSequence::new(Vec::new(), 0);
+    let json = serde_json::to_string(&empty).unwrap();
+
+    assert!(serde_json::from_str::<BitSequence>(&json).unwrap() == empty);
+  }
+
+  #[test]
+  fn bit_sequence_rejects_invalid_last_byte_len() {
+    for json in [
+      r#"{"bytes":[171],"last_byte_len":8}"#,
+      r#"{"bytes":[171],"last_byte_len":255}"#,
+      r#"{"bytes":[],"last_byte_len":3}"#
+    ] {
+      assert!(serde_json::from_str::<BitSequence>(json).is_err());
+    }
+  }
+}
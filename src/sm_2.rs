@@ -1,10 +1,30 @@
-use crate::{
-  math::{
-    bytes::BitSequence,
-    ecc::{EccOps, EccParams, EccPoint, ModInv, ModOps},
-    u256::U256
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+use {
+  crate::{
+    der::{
+      write_tlv,
+      DerReader,
+      OID_EC_PUBLIC_KEY,
+      OID_SM2,
+      TAG_BIT_STRING,
+      TAG_CONTEXT_0,
+      TAG_CONTEXT_1,
+      TAG_INTEGER,
+      TAG_OCTET_STRING,
+      TAG_OID,
+      TAG_SEQUENCE
+    },
+    math::{
+      bytes::{BitSequence, ByteOrder},
+      ecc::{EccOps, EccParams, EccPoint, ModInv, ModOps, MulStrategy},
+      u256::U256
+    },
+    sm_3::{hash, hash_to_bits, hmac},
+    Error
   },
-  sm_3::hash
+  alloc::vec::Vec,
+  rand::CryptoRng
 };
 
 /// # SM2 p 参数
@@ -56,19 +76,157 @@ static SM2_GY: U256 = U256::from_be_u64_array(&[
 ]);
 
 /// # SM2 椭圆曲线参数结构体
-static SM2_PARAMS: EccParams =
-  EccParams { a: SM2_A, b: SM2_B, p: SM2_P, n: SM2_N, g_x: SM2_GX, g_y: SM2_GY };
+static SM2_PARAMS: EccParams = EccParams::new(SM2_A, SM2_B, SM2_P, SM2_N, SM2_GX, SM2_GY);
+
+impl EccParams {
+  /// # 判断是否为 SM2 推荐曲线参数
+  ///
+  /// ## 返回值
+  ///
+  /// * `bool` - 参数与 SM2 推荐曲线参数完全一致时返回 true
+  pub fn is_sm2(&self) -> bool {
+    *self == SM2_PARAMS
+  }
+}
 
 /// # SM2 ECC 点 G
 static SM2_G: EccPoint<'static> =
   EccPoint { x: SM2_GX, y: SM2_GY, params: &SM2_PARAMS, infinity: false };
 
+/// # SM2 基点 G 的预计算表，首次使用时计算
+#[cfg(feature = "std")]
+static SM2_G_TABLE: OnceLock<GeneratorTable> = OnceLock::new();
+
+/// # SM2 基点 G 的预计算表
+///
+/// 第 i 行第 j 项为 [j * 2^(8i)]G，数乘因子按字节拆分后每个字节查表一次，
+/// [k]G 只需 32 次点加，不需要逐位倍点；表在 Jacobian 坐标下构造，
+/// 再以批量求逆统一转换为仿射坐标
+///
+/// ## 构造方法
+///
+/// * `new() -> Self` - 计算一张新的预计算表
+/// * `get() -> &'static Self` - 获取全局唯一的预计算表，首次调用时计算，需启用
+///   `std` 特性
+///
+/// ## 方法
+///
+/// * `mul_base(&self, k: U256) -> EccPoint<'static>` - 计算 [k]G
+///
+/// ## 实现特征
+///
+/// * `Default` - 同 `new`
+///
+/// ## 注意事项
+///
+/// * `mul_base` 每次查表均遍历整行并以掩码选择，点加不含分支，运算为常数时间，
+///   可用于私钥、随机数等秘密数乘因子
+/// * 本模块的函数仅在启用 `std` 特性时使用全局预计算表，否则基点数乘使用
+///   `ecc_mul_ct`
+pub struct GeneratorTable {
+  rows: Vec<[EccPoint<'static>; 256]>
+}
+
+impl GeneratorTable {
+  pub fn new() -> Self {
+    Self { rows: SM2_G.fixed_base_table(&SM2_PARAMS) }
+  }
+
+  #[cfg(feature = "std")]
+  pub fn get() -> &'static Self {
+    SM2_G_TABLE.get_or_init(Self::new)
+  }
+
+  pub fn mul_base(&self, k: U256) -> EccPoint<'static> {
+    EccPoint::fixed_base_mul(&self.rows, k, &SM2_PARAMS)
+  }
+}
+
+impl Default for GeneratorTable {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// # 常数时间的基点数乘
+///
+/// SM2 推荐曲线在启用 `std` 特性时使用全局预计算表 `GeneratorTable`，
+/// 其余情况使用 `ecc_mul_ct`
+///
+/// ## 参数
+///
+/// * `k` - 数乘因子
+/// * `params` - 椭圆曲线参数结构体的引用
+///
+/// ## 返回值
+///
+/// * `EccPoint` - [k]G
+fn mul_generator(k: U256, params: &EccParams) -> EccPoint<'_> {
+  #[cfg(feature = "std")]
+  if params.is_sm2() {
+    return GeneratorTable::get().mul_base(k);
+  }
+
+  EccPoint::new(params.g_x, params.g_y, params, false).ecc_mul_ct(k, params)
+}
+
 /// # SM2 密钥对结构体
+///
+/// ## 构造方法
+///
+/// * `from_parts(private: U256, public: EccPoint<'a>) -> Self` -
+///   由已有的私钥和公钥构造，不校验两者是否对应
+///
+/// ## 方法
+///
+/// * `private_key(&self) -> U256` - 获取私钥
+/// * `private_key_bytes(&self) -> [u8; 32]` - 获取大端序编码的私钥
+/// * `public_key(&self) -> &EccPoint<'a>` - 获取公钥
+///
+/// ## 实现特征
+///
+/// * `Zeroize`、`ZeroizeOnDrop` - 启用 `zeroize` 特性时，丢弃时清零私钥
 pub struct KeyPair<'a> {
   private_key: U256,
   public_key: EccPoint<'a>
 }
 
+impl<'a> KeyPair<'a> {
+  pub fn from_parts(private: U256, public: EccPoint<'a>) -> Self {
+    Self { private_key: private, public_key: public }
+  }
+
+  pub fn private_key(&self) -> U256 {
+    self.private_key
+  }
+
+  pub fn private_key_bytes(&self) -> [u8; 32] {
+    self.private_key.into_be_bytes()
+  }
+
+  pub fn public_key(&self) -> &EccPoint<'a> {
+    &self.public_key
+  }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for KeyPair<'_> {
+  fn zeroize(&mut self) {
+    self.private_key.zeroize();
+  }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for KeyPair<'_> {
+  fn drop(&mut self) {
+    zeroize::Zeroize::zeroize(self);
+  }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for KeyPair<'_> {
+}
+
 /// # SM2 密钥对生成函数
 ///
 /// 使用给定参数，随机生成私钥及对应公钥
@@ -80,15 +238,63 @@ pub struct KeyPair<'a> {
 /// ## 返回
 ///
 /// 返回一个包含私钥和公钥的密钥对
-pub fn key_gen(params: &EccParams) -> KeyPair {
-  // 随机生成私钥
-  let d = U256::random_in_range(&mut rand::rng(), U256::C_1, params.n - U256::C_1);
+///
+/// 如果 n 过小，私钥的取值范围 [1, n - 2] 为空，返回 `Error::UnsupportedCurve`
+#[cfg(feature = "std")]
+pub fn key_gen(params: &EccParams) -> Result<KeyPair<'_>, Error> {
+  key_gen_with_rng(params, &mut rand::rng())
+}
+
+/// # SM2 指定随机数生成器的密钥对生成函数
+///
+/// 与 `key_gen` 相同，但从调用者提供的随机数生成器取得私钥
+///
+/// ## 参数
+///
+/// * `params` - 椭圆曲线参数
+/// * `rng` - 密码学安全的随机数生成器
+///
+/// ## 返回
+///
+/// 返回一个包含私钥和公钥的密钥对
+///
+/// 如果 n 过小，私钥的取值范围 [1, n - 2] 为空，返回 `Error::UnsupportedCurve`
+pub fn key_gen_with_rng<'a, R: CryptoRng>(
+  params: &'a EccParams,
+  rng: &mut R
+) -> Result<KeyPair<'a>, Error> {
+  // 随机生成 [1, n - 2] 范围内的私钥
+  let bound =
+    if params.n_minus_1() > U256::C_1 { params.n_minus_1() - U256::C_1 } else { U256::C_0 };
+  let d = U256::random_below(rng, bound).ok_or(Error::UnsupportedCurve)? + U256::C_1;
 
   // 计算对应的公钥
-  let g = EccPoint::new(params.g_x, params.g_y, params, false);
-  let p = g.ecc_mul(d, params);
+  Ok(KeyPair { private_key: d, public_key: mul_generator(d, params) })
+}
+
+/// # SM2 公钥计算函数
+///
+/// 由私钥计算对应的公钥 [d]G，用于导入已有私钥
+///
+/// ## 参数
+///
+/// * `private` - 私钥 d
+/// * `params` - 椭圆曲线参数
+///
+/// ## 返回
+///
+/// 如果私钥在 [1, n - 1] 范围内，返回对应公钥
+///
+/// 否则返回 `Error::InvalidPrivateKey`
+pub fn public_key_from_private<'a>(
+  private: U256,
+  params: &'a EccParams
+) -> Result<EccPoint<'a>, Error> {
+  if private.eq_u64(0) || private >= params.n {
+    return Err(Error::InvalidPrivateKey);
+  }
 
-  KeyPair { private_key: d, public_key: p }
+  Ok(mul_generator(private, params))
 }
 
 /// # SM2 公钥验证函数
@@ -114,7 +320,7 @@ pub fn pubkey_validate<'a>(p: &'a EccPoint) -> bool {
   }
 
   // 如果 [n]p 是无穷远点，则公钥有效
-  p.ecc_mul(params.n, params).infinity
+  p.ecc_mul_with(params.n, params, MulStrategy::Windowed).infinity
 }
 
 /// # SM2 获取Z值函数
@@ -134,19 +340,59 @@ fn get_z(params: &EccParams, id: &BitSequence, public_key: &EccPoint) -> U256 {
   let mut bits = BitSequence::new_empty();
 
   // ID.bit_len() as u16 || ID || a || b || Gx || Gy || Px || Py
-  bits.append_bytes(
-    &((id.get_bytes().len() - 8 + id.get_last_byte_len() as usize) as u16).to_le_bytes()
-  );
+  bits.append_bytes(&(id.len() as u16).to_be_bytes());
   bits.append_bits(id);
-  bits.append_bytes(&params.a.into_le_bytes());
-  bits.append_bytes(&params.b.into_le_bytes());
-  bits.append_bytes(&params.g_x.into_le_bytes());
-  bits.append_bytes(&params.g_y.into_le_bytes());
-  bits.append_bytes(&public_key.x.into_le_bytes());
-  bits.append_bytes(&public_key.y.into_le_bytes());
+  bits.append_bytes(&params.a.into_be_bytes());
+  bits.append_bytes(&params.b.into_be_bytes());
+  bits.append_bytes(&params.g_x.into_be_bytes());
+  bits.append_bytes(&params.g_y.into_be_bytes());
+  bits.append_bytes(&public_key.x.into_be_bytes());
+  bits.append_bytes(&public_key.y.into_be_bytes());
 
   // Z
-  hash(&bits).into()
+  U256::from_be_bytes(&hash(&bits))
+}
+
+/// # SM2 计算消息摘要函数
+///
+/// 计算 e = H(Za || M)，签名与签名验证共用此函数，确保两侧计算方式一致
+///
+/// ## 参数
+///
+/// * `params` - 椭圆曲线参数
+/// * `id` - 用户ID
+/// * `public_key` - 公钥
+/// * `message` - 比特序列消息
+///
+/// ## 返回
+///
+/// 返回一个U256值，表示消息摘要e
+pub fn compute_e(
+  params: &EccParams,
+  id: &BitSequence,
+  public_key: &EccPoint,
+  message: &BitSequence
+) -> U256 {
+  compute_e_with_z(get_z(params, id, public_key), message)
+}
+
+/// # SM2 由 Z 值计算消息摘要函数
+///
+/// ## 参数
+///
+/// * `z` - 预先计算的 Z 值
+/// * `message` - 比特序列消息
+///
+/// ## 返回
+///
+/// 返回一个U256值，表示消息摘要e
+fn compute_e_with_z(z: U256, message: &BitSequence) -> U256 {
+  // m_bar = Za || M
+  let mut m_bar = BitSequence::new(z.into_be_bytes().to_vec(), 0);
+  m_bar.append_bits(message);
+
+  // e = H(m_bar)
+  U256::from_be_bytes(&hash(&m_bar))
 }
 
 /// # SM2 签名输入结构体
@@ -164,10 +410,13 @@ fn get_z(params: &EccParams, id: &BitSequence, public_key: &EccPoint) -> U256 {
 ///
 /// * `SigningInput::new(params, id, public_key, private_key)` -
 ///   创建签名输入结构体
+/// * `SigningInput::with_id_bytes(params, id, public_key, private_key)` -
+///   使用字节序列形式的用户ID创建签名输入结构体
 ///
 /// ## 实现特征
 ///
 /// * `Clone`
+/// * `Zeroize`、`ZeroizeOnDrop` - 启用 `zeroize` 特性时，丢弃时清零私钥
 #[derive(Clone)]
 pub struct SigningInput<'a> {
   params: &'a EccParams,
@@ -185,6 +434,33 @@ impl<'a> SigningInput<'a> {
   ) -> Self {
     Self { params, id, public_key, private_key }
   }
+
+  pub fn with_id_bytes(
+    params: &'a EccParams,
+    id: &[u8],
+    public_key: EccPoint<'a>,
+    private_key: U256
+  ) -> Self {
+    Self::new(params, BitSequence::with_bytes(id), public_key, private_key)
+  }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for SigningInput<'_> {
+  fn zeroize(&mut self) {
+    self.private_key.zeroize();
+  }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for SigningInput<'_> {
+  fn drop(&mut self) {
+    zeroize::Zeroize::zeroize(self);
+  }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for SigningInput<'_> {
 }
 
 /// # SM2 签名函数
@@ -198,660 +474,2881 @@ impl<'a> SigningInput<'a> {
 ///
 /// ## 返回
 ///
-/// 返回一个元组，包含签名结果的r值和s值
-pub fn generate_signature(input: &SigningInput, message: &BitSequence) -> ([u8; 32], [u8; 32]) {
-  // m_bar = Za || M
-
-  let mut m_bar =
-    BitSequence::new(get_z(input.params, &input.id, &input.public_key).into_le_bytes().to_vec(), 0);
-  m_bar.append_bits(message);
-
-  // e = H(m_bar)
-  let e = hash(&m_bar).into();
+/// 如果签名成功，返回一个元组，包含大端序编码的签名结果r值和s值
+///
+/// 如果私钥为 n - 1，返回 `Error::InvalidPrivateKey`
+#[cfg(feature = "std")]
+pub fn generate_signature(
+  input: &SigningInput,
+  message: &BitSequence
+) -> Result<([u8; 32], [u8; 32]), Error> {
+  generate_signature_with_order(input, message, ByteOrder::BigEndian)
+}
 
-  let g = EccPoint::new(input.params.g_x, input.params.g_y, input.params, false);
-  loop {
-    let k = U256::random_in_range(&mut rand::rng(), U256::C_1, input.params.n);
+/// # SM2 指定随机数生成器的签名函数
+///
+/// 与 `generate_signature` 相同，但从调用者提供的随机数生成器取得随机数 k
+///
+/// ## 参数
+///
+/// * `input` - 签名输入结构体
+/// * `message` - 比特序列消息
+/// * `rng` - 密码学安全的随机数生成器
+///
+/// ## 返回
+///
+/// 如果签名成功，返回一个元组，包含大端序编码的签名结果r值和s值
+///
+/// 如果私钥为 n - 1，返回 `Error::InvalidPrivateKey`
+pub fn generate_signature_with_rng<R: CryptoRng>(
+  input: &SigningInput,
+  message: &BitSequence,
+  rng: &mut R
+) -> Result<([u8; 32], [u8; 32]), Error> {
+  // e = H(Za || M)
+  let e = compute_e(input.params, &input.id, &input.public_key, message);
 
-    let x1 = g.ecc_mul(k, input.params).x;
+  sign_with_e(input, e, ByteOrder::BigEndian, rng)
+}
 
-    let r = x1.mod_add(e, input.params.p);
+/// # SM2 指定字节序的签名函数
+///
+/// 与 `generate_signature` 相同，但签名结果使用指定字节序编码
+///
+/// ## 参数
+///
+/// * `input` - 签名输入结构体
+/// * `message` - 比特序列消息
+/// * `order` - 签名结果的字节序
+///
+/// ## 返回
+///
+/// 如果签名成功，返回一个元组，包含签名结果的r值和s值
+///
+/// 如果私钥为 n - 1，返回 `Error::InvalidPrivateKey`
+#[cfg(feature = "std")]
+pub fn generate_signature_with_order(
+  input: &SigningInput,
+  message: &BitSequence,
+  order: ByteOrder
+) -> Result<([u8; 32], [u8; 32]), Error> {
+  // e = H(Za || M)
+  let e = compute_e(input.params, &input.id, &input.public_key, message);
 
-    // r == 0 或 r + k == n，重新生成
-    if r == U256::C_0 || r.mod_add(k, input.params.p) == input.params.n {
-      continue;
-    }
+  sign_with_e(input, e, order, &mut rand::rng())
+}
 
-    // s = (k + 1)^-1 * (k - d_a * r) mod n
-    let s = input
-      .private_key
-      .mod_add(U256::C_1, input.params.p)
-      .mod_inv(input.params.p)
-      .unwrap()
-      .mod_mul(
-        k.mod_add(-input.private_key.mod_mul(r, input.params.p), input.params.p),
-        input.params.p
-      )
-      .modded(input.params.n);
+/// # SM2 随机数签名计算函数
+///
+/// 随机生成 k 并签名，r == 0、r + k == n 或 s == 0 时重新生成 k
+///
+/// ## 参数
+///
+/// * `input` - 签名输入结构体
+/// * `e` - 消息摘要
+/// * `order` - 签名结果的字节序
+/// * `rng` - 密码学安全的随机数生成器
+///
+/// ## 返回
+///
+/// 如果签名成功，返回一个元组，包含签名结果的r值和s值
+///
+/// 如果私钥为 n - 1，不再重试，返回 `Error::InvalidPrivateKey`
+fn sign_with_e<R: CryptoRng>(
+  input: &SigningInput,
+  e: U256,
+  order: ByteOrder,
+  rng: &mut R
+) -> Result<([u8; 32], [u8; 32]), Error> {
+  loop {
+    let k = U256::random_in_range(rng, U256::C_1, input.params.n);
 
-    // s == 0，重新生成
-    if s != U256::C_0 {
-      break (r.into_le_bytes(), s.into_le_bytes());
+    // 只有与 k 相关的错误才值得重试
+    match sign_with_e_and_k(input, e, k, order) {
+      Err(Error::InvalidNonce) => {},
+      result => break result
     }
   }
 }
 
-/// # SM2 签名验证输入结构体
+/// # SM2 确定性签名函数
 ///
-/// 签名验证输入结构体，包含椭圆曲线参数、用户ID、公钥
+/// 仿照 RFC 6979，以 HMAC-SM3 为基础的 HMAC_DRBG 从私钥和消息摘要 e 派生随机数
+/// k，相同的输入总是得到相同的签名，不依赖随机数生成器
 ///
 /// ## 参数
 ///
-/// * `params` - 椭圆曲线参数
-/// * `id` - 用户ID
-/// * `public_key` - 公钥
-///
-/// ## 构造方法
+/// * `input` - 签名输入结构体
+/// * `message` - 比特序列消息
 ///
-/// * `SigningVerificationInput::new(params, id, public_key)` -
-///   创建签名验证输入结构体
+/// ## 返回
 ///
-/// ## 实现特征
+/// 如果签名成功，返回一个元组，包含大端序编码的签名结果r值和s值
 ///
-/// * `Clone`
-#[derive(Clone)]
-pub struct SigningVerificationInput<'a> {
-  params: &'a EccParams,
-  id: BitSequence,
-  public_key: EccPoint<'a>
-}
+/// 如果私钥为 n - 1，返回 `Error::InvalidPrivateKey`
+pub fn generate_signature_deterministic(
+  input: &SigningInput,
+  message: &BitSequence
+) -> Result<([u8; 32], [u8; 32]), Error> {
+  // e = H(Za || M)
+  let e = compute_e(input.params, &input.id, &input.public_key, message);
+  let n = input.params.n;
 
-impl<'a> SigningVerificationInput<'a> {
-  pub fn new(params: &'a EccParams, id: BitSequence, public_key: EccPoint<'a>) -> Self {
-    Self { params, id, public_key }
+  // int2octets(d) 与 bits2octets(e)
+  let x = input.private_key.into_be_bytes();
+  let h = e.modded(n).into_be_bytes();
+
+  let mut v = [0x01u8; 32];
+  let mut key = [0x00u8; 32];
+
+  key = hmac(&key, &[&v[..], &[0x00], &x, &h].concat());
+  v = hmac(&key, &v);
+  key = hmac(&key, &[&v[..], &[0x01], &x, &h].concat());
+  v = hmac(&key, &v);
+
+  loop {
+    // HMAC-SM3 输出 256 位，不短于 n 的位数，一次即可得到候选值
+    v = hmac(&key, &v);
+    let k = U256::from_be_bytes(&v) >> (256 - n.highest_bit()) as u32;
+
+    // k 不在 [1, n - 1] 内，或 r == 0、r + k == n、s == 0 时，更新状态后重试
+    if !k.eq_u64(0) && k < n {
+      match sign_with_e_and_k(input, e, k, ByteOrder::BigEndian) {
+        Err(Error::InvalidNonce) => {},
+        result => break result
+      }
+    }
+
+    key = hmac(&key, &[&v[..], &[0x00]].concat());
+    v = hmac(&key, &v);
   }
 }
 
-/// # SM2 签名验证函数
+/// # SM2 指定随机数签名函数
 ///
-/// 使用给定的椭圆曲线参数、用户ID、公钥和签名结果，验证签名结果是否有效
+/// 使用调用者提供的随机数 k 签名，用于复现标准中的已知答案测试
 ///
 /// ## 参数
 ///
-/// * `input` - 签名验证输入结构体
+/// * `input` - 签名输入结构体
 /// * `message` - 比特序列消息
-/// * `signature` - 签名结果
+/// * `k` - 随机数，取值范围为 [1, n - 1]
 ///
 /// ## 返回
 ///
-/// 返回一个布尔值，表示签名结果是否有效
-pub fn verify_signature(
-  input: &SigningVerificationInput,
+/// 如果签名成功，返回一个元组，包含大端序编码的签名结果r值和s值
+///
+/// 如果 k 不在范围内或导致 r、s 无效，返回 `Error::InvalidNonce`；私钥为 n - 1
+/// 时返回 `Error::InvalidPrivateKey`
+pub fn generate_signature_with_k(
+  input: &SigningInput,
   message: &BitSequence,
-  signature: ([u8; 32], [u8; 32])
-) -> bool {
-  let r = U256::from_le_bytes(&signature.0);
-  let s = U256::from_le_bytes(&signature.1);
-
-  // 检验是否在域内
-  if r >= input.params.n || s >= input.params.n {
-    return false;
-  }
-
-  // t = (t + s) mod n，t == 0 则验证失败
-  let t = r.mod_add(s, input.params.p).modded(input.params.n);
-  if t == U256::C_0 {
-    return false;
+  k: U256
+) -> Result<([u8; 32], [u8; 32]), Error> {
+  if k.eq_u64(0) || k >= input.params.n {
+    return Err(Error::InvalidNonce);
   }
 
-  // x1 = [s]G + [t]Pa
-  let g = EccPoint::new(input.params.g_x, input.params.g_y, input.params, false);
-  let x1 =
-    g.ecc_mul(s, input.params).ecc_add(input.public_key.ecc_mul(t, input.params), input.params).x;
-
-  // m_bar = Za || M
-  let mut m_bar =
-    BitSequence::new(get_z(input.params, &input.id, &input.public_key).into_le_bytes().to_vec(), 0);
-  m_bar.append_bits(message);
-
-  // e = H(m_bar)
-  let e: U256 = hash(&m_bar).into();
+  let e = compute_e(input.params, &input.id, &input.public_key, message);
 
-  // R = (e + x1) mod n == r
-  e.mod_add(x1, input.params.p).modded(input.params.n) == r
+  sign_with_e_and_k(input, e, k, ByteOrder::BigEndian)
 }
 
-/// # SM2 密钥派生函数
-///
-/// 使用给定的比特序列和目标长度，生成一个比特序列
+/// # SM2 签名计算函数
 ///
 /// ## 参数
 ///
-/// * `sequence` - 比特序列
-/// * `target_length` - 目标长度
+/// * `input` - 签名输入结构体
+/// * `e` - 消息摘要
+/// * `k` - 随机数
+/// * `order` - 签名结果的字节序
 ///
 /// ## 返回
 ///
-/// 返回一个比特序列
-fn key_derivation_function(sequence: &BitSequence, target_length: u64) -> BitSequence {
-  let mut result = BitSequence::new_empty();
+/// 如果签名成功，返回一个元组，包含签名结果的r值和s值
+///
+/// 如果 r == 0、r + k == n 或 s == 0，返回 `Error::InvalidNonce`
+///
+/// 如果私钥为 n - 1，d_a + 1 不可逆，返回 `Error::InvalidPrivateKey`
+fn sign_with_e_and_k(
+  input: &SigningInput,
+  e: U256,
+  k: U256,
+  order: ByteOrder
+) -> Result<([u8; 32], [u8; 32]), Error> {
+  let n = input.params.n;
 
-  // 遍历次数 klen.div_ceil(v)，其中 v 为 256
-  let blocks: u32 = target_length.div_ceil(256) as u32;
+  let x1 = mul_generator(k, input.params).x;
 
-  // i 即为计数器 ct 计数，从 1 开始，到 blocks 结束
-  for i in 1 ..= if target_length % 256 != 0 { blocks - 1 } else { blocks } {
-    // Ha_i = H(Z || ct)
+  // r = (e + x1) mod n，e 与 x1 可能不小于 n，先分别约减
+  let r = e.modded(n).mod_add(x1.modded(n), n);
 
-    let mut temp_sequence = sequence.clone();
+  if r.eq_u64(0) || r.mod_add(k, n).eq_u64(0) {
+    return Err(Error::InvalidNonce);
+  }
 
-    temp_sequence.append_bytes(&i.to_le_bytes());
+  // d_a = n - 1 时 d_a + 1 不可逆，与 k 无关，调用者不应重试
+  let d_plus_1 = input.private_key.mod_add(U256::C_1, n);
 
-    result.append_bytes(&hash(&temp_sequence));
+  if d_plus_1.eq_u64(0) {
+    return Err(Error::InvalidPrivateKey);
   }
 
-  // 如果 target_length % 256 != 0，则需要处理最后一个块，长度为 klen - (v * (klen
-  // / v))
-  if target_length % 256 != 0 {
-    let mut temp_sequence = sequence.clone();
+  // s = (d_a + 1)^-1 * (k - r * d_a) mod n，d_a 为秘密值，使用费马求逆
+  let s =
+    d_plus_1.mod_inv_prime(n).mod_mul(k.mod_add(r.mod_mul(input.private_key, n).mod_neg(n), n), n);
 
-    temp_sequence.append_bytes(&blocks.to_le_bytes());
+  if s.eq_u64(0) {
+    return Err(Error::InvalidNonce);
+  }
 
-    // assertEq!(klen - (v * (klen / v)), klen % v)
-    let target_len = target_length % 256;
+  Ok((r.into_bytes_with_order(order), s.into_bytes_with_order(order)))
+}
 
-    // 取 hash 结果前 target_len / 8 字节，其余舍弃
-    let mut hash_result = hash(&temp_sequence)[.. target_len.div_ceil(8) as usize].to_vec();
+/// # SM2 预计算签名器
+///
+/// 在构造时计算并缓存 Z 值，用同一身份签名多条消息时无需重复计算
+///
+/// ## 成员
+///
+/// * `input` - 签名输入结构体
+/// * `z` - 缓存的 Z 值
+///
+/// ## 构造方法
+///
+/// * `PrecomputedSigner::new(input: &SigningInput<'a>) -> Self` -
+///   从签名输入结构体创建，并计算 Z 值
+///
+/// ## 实现特征
+///
+/// * `Clone`
+///
+/// ## 方法
+///
+/// * `z(&self) -> U256` - 获取缓存的 Z 值
+/// * `sign(&self, message: &BitSequence) -> Result<([u8; 32], [u8; 32]),
+///   Error>`
+///   - 签名，结果与 `generate_signature` 相同
+/// * `sign_with_rng<R: CryptoRng>(&self, message: &BitSequence, rng: &mut R) ->
+///   Result<([u8; 32], [u8; 32]), Error>` - 使用指定的随机数生成器签名
+#[derive(Clone)]
+pub struct PrecomputedSigner<'a> {
+  input: SigningInput<'a>,
+  z: U256
+}
 
-    // 计算最后一个字节需要舍弃的位数
-    let discarded_bits = 8 - target_len % 8;
+impl<'a> PrecomputedSigner<'a> {
+  pub fn new(input: &SigningInput<'a>) -> Self {
+    let z = get_z(input.params, &input.id, &input.public_key);
 
-    let last_byte = hash_result.last_mut().unwrap();
+    Self { input: input.clone(), z }
+  }
 
-    // 仅舍弃右侧 discarded_bits 位，剩余位位置不变
-    *last_byte >>= discarded_bits;
-    *last_byte <<= discarded_bits;
+  pub fn z(&self) -> U256 {
+    self.z
+  }
 
-    result
-      .append_bits(&BitSequence::try_with_bits(hash_result.as_ref(), target_len as u64).unwrap());
+  #[cfg(feature = "std")]
+  pub fn sign(&self, message: &BitSequence) -> Result<([u8; 32], [u8; 32]), Error> {
+    self.sign_with_rng(message, &mut rand::rng())
   }
 
-  result
+  pub fn sign_with_rng<R: CryptoRng>(
+    &self,
+    message: &BitSequence,
+    rng: &mut R
+  ) -> Result<([u8; 32], [u8; 32]), Error> {
+    let e = compute_e_with_z(self.z, message);
+
+    sign_with_e(&self.input, e, ByteOrder::BigEndian, rng)
+  }
 }
 
-/// # SM2 密钥交换输入结构体
+/// # SM2 签名验证输入结构体
 ///
-/// 密钥交换输入结构体，包含椭圆曲线参数、用户ID、公钥和对方用户ID、对方公钥
+/// 签名验证输入结构体，包含椭圆曲线参数、用户ID、公钥
 ///
 /// ## 参数
 ///
 /// * `params` - 椭圆曲线参数
 /// * `id` - 用户ID
 /// * `public_key` - 公钥
-/// * `other_id` - 对方用户ID
-/// * `other_public_key` - 对方公钥
-pub struct ExchangeKeyInput<'a> {
+///
+/// ## 构造方法
+///
+/// * `SigningVerificationInput::new(params, id, public_key)` -
+///   创建签名验证输入结构体
+/// * `SigningVerificationInput::with_id_bytes(params, id, public_key)` -
+///   使用字节序列形式的用户ID创建签名验证输入结构体
+///
+/// ## 实现特征
+///
+/// * `Clone`
+#[derive(Clone)]
+pub struct SigningVerificationInput<'a> {
   params: &'a EccParams,
   id: BitSequence,
-  public_key: EccPoint<'a>,
-  other_id: BitSequence,
-  other_public_key: EccPoint<'a>
+  public_key: EccPoint<'a>
 }
 
-/// # SM2 密钥交换发起者状态结构体
+impl<'a> SigningVerificationInput<'a> {
+  pub fn new(params: &'a EccParams, id: BitSequence, public_key: EccPoint<'a>) -> Self {
+    Self { params, id, public_key }
+  }
+
+  pub fn with_id_bytes(params: &'a EccParams, id: &[u8], public_key: EccPoint<'a>) -> Self {
+    Self::new(params, BitSequence::with_bytes(id), public_key)
+  }
+}
+
+/// # SM2 签名结果
 ///
-/// 记录密钥交换发起者的状态，包含随机点、随机数
+/// 大端序编码的签名结果 (r, s)
+pub type Signature = ([u8; 32], [u8; 32]);
+
+/// # SM2 签名验证函数
+///
+/// 使用给定的椭圆曲线参数、用户ID、公钥和签名结果，验证签名结果是否有效
 ///
 /// ## 参数
 ///
-/// * `r_point` - 随机点
-/// * `r` - 随机数
-pub struct ExchangeKeyStateInitiator<'a> {
-  r_point: EccPoint<'a>,
-  r: U256
+/// * `input` - 签名验证输入结构体
+/// * `message` - 比特序列消息
+/// * `signature` - 大端序编码的签名结果
+///
+/// ## 返回
+///
+/// 返回一个布尔值，表示签名结果是否有效
+pub fn verify_signature(
+  input: &SigningVerificationInput,
+  message: &BitSequence,
+  signature: ([u8; 32], [u8; 32])
+) -> bool {
+  verify_signature_with_order(input, message, signature, ByteOrder::BigEndian)
 }
 
-/// # SM2 密钥交换接收者状态结构体
+/// # SM2 指定字节序的签名验证函数
 ///
-/// 记录密钥交换接收者的状态，包含随机点、随机数、计算点、对方随机点
+/// 与 `verify_signature` 相同，但签名结果使用指定字节序解析
 ///
 /// ## 参数
 ///
-/// * `r_point` - 随机点
-/// * `r` - 随机数
-/// * `v` - 计算点
-/// * `r_point_other` - 对方随机点
-pub struct ExchangeKeyStateReceiver<'a> {
-  r_point: EccPoint<'a>,
-  v: EccPoint<'a>,
-  r_point_other: EccPoint<'a>
+/// * `input` - 签名验证输入结构体
+/// * `message` - 比特序列消息
+/// * `signature` - 签名结果
+/// * `order` - 签名结果的字节序
+///
+/// ## 返回
+///
+/// 返回一个布尔值，表示签名结果是否有效
+pub fn verify_signature_with_order(
+  input: &SigningVerificationInput,
+  message: &BitSequence,
+  signature: ([u8; 32], [u8; 32]),
+  order: ByteOrder
+) -> bool {
+  let r = U256::from_bytes_with_order(&signature.0, order);
+  let s = U256::from_bytes_with_order(&signature.1, order);
+
+  verify_signature_raw(input, message, r, s)
 }
 
-/// # SM2 密钥交换生成输出结构体
+/// # SM2 签名验证函数，签名为 `U256`
 ///
-/// 密钥交换生成输出结构体，包含密钥和发送到对方的负载
+/// 与 `verify_signature` 相同，但直接接受整数形式的签名结果，无需字节编码转换
 ///
 /// ## 参数
 ///
-/// * `key` - 密钥
-/// * `payload` - 发送到对方的负载
-pub struct ExchangeKeyGenerateOutput<'a> {
-  pub key: BitSequence,
-  pub payload: ExchangeKeyGeneratePayload<'a>
+/// * `input` - 签名验证输入结构体
+/// * `message` - 比特序列消息
+/// * `r` - 签名结果的r值
+/// * `s` - 签名结果的s值
+///
+/// ## 返回
+///
+/// 返回一个布尔值，表示签名结果是否有效
+pub fn verify_signature_raw(
+  input: &SigningVerificationInput,
+  message: &BitSequence,
+  r: U256,
+  s: U256
+) -> bool {
+  // e = H(Za || M)
+  let e = compute_e(input.params, &input.id, &input.public_key, message);
+
+  verify_with_e(input, e, r, s)
 }
 
-/// # SM2 密钥交换生成负载结构体
+/// # SM2 批量签名验证函数
 ///
-/// 密钥交换生成负载结构体，包含自己的随机点、验证参数
+/// 使用同一签名验证输入验证多条消息的签名，Z
+/// 值只计算一次；每个签名单独给出结果， 其中一个无效不影响其他签名
 ///
 /// ## 参数
 ///
-/// * `received` - 随机点
-/// * `validator` - 验证参数
-pub struct ExchangeKeyGeneratePayload<'a> {
-  pub received: EccPoint<'a>,
-  pub validator: [u8; 32]
+/// * `input` - 签名验证输入结构体
+/// * `messages_and_sigs` - 比特序列消息及其大端序编码的签名结果
+///
+/// ## 返回
+///
+/// 返回与输入一一对应的布尔值，表示各签名结果是否有效
+pub fn verify_batch(
+  input: &SigningVerificationInput,
+  messages_and_sigs: &[(&BitSequence, Signature)]
+) -> Vec<bool> {
+  let z = get_z(input.params, &input.id, &input.public_key);
+
+  messages_and_sigs
+    .iter()
+    .map(|(message, signature)| {
+      let e = compute_e_with_z(z, message);
+      let r = U256::from_be_bytes(&signature.0);
+      let s = U256::from_be_bytes(&signature.1);
+
+      verify_with_e(input, e, r, s)
+    })
+    .collect()
 }
 
-/// # SM2 密钥交换确认输出结构体
-///
-/// 密钥交换确认输出结构体，包含密钥和验证参数
+/// # SM2 签名验证计算函数
 ///
 /// ## 参数
 ///
-/// * `key` - 密钥
-/// * `payload` - 验证参数
-pub struct ExchangeKeyConfirmOutput {
-  pub key: BitSequence,
-  pub payload: [u8; 32]
+/// * `input` - 签名验证输入结构体
+/// * `e` - 消息摘要
+/// * `r` - 签名结果的r值
+/// * `s` - 签名结果的s值
+///
+/// ## 返回
+///
+/// 返回一个布尔值，表示签名结果是否有效
+fn verify_with_e(input: &SigningVerificationInput, e: U256, r: U256, s: U256) -> bool {
+  let n = input.public_key.order();
+
+  // 检验 r, s 是否在 [1, n - 1] 内
+  if r.eq_u64(0) || s.eq_u64(0) || r >= n || s >= n {
+    return false;
+  }
+
+  // t = (r + s) mod n，t == 0 则验证失败
+  let t = r.mod_add(s, n);
+  if t.eq_u64(0) {
+    return false;
+  }
+
+  // x1 = [s]G + [t]Pa
+  let g = EccPoint::new(input.params.g_x, input.params.g_y, input.params, false);
+  let x1 = g.ecc_mul_double(s, input.public_key, t, input.params).x;
+
+  // R = (e + x1) mod n == r，e 与 x1 可能不小于 n，先分别约减
+  e.modded(n).mod_add(x1.modded(n), n).ct_eq(r)
 }
 
-/// # SM2 密钥交换初始化函数
+/// # SM2 签名 DER 编码函数
 ///
-/// 初始化密钥交换，生成随机点
+/// 将签名编码为 GB/T 35276 规定的 `SEQUENCE { r INTEGER, s INTEGER }`，
+/// 与 OpenSSL、GmSSL 等实现兼容
 ///
 /// ## 参数
 ///
-/// * `input` - 密钥交换输入结构体
+/// * `sig` - 大端序的签名结果 (r, s)
 ///
 /// ## 返回
 ///
-/// 返回一个元组，包含随机点和一个状态结构体
-pub fn exchange_key_initiate<'a>(
-  input: &'a ExchangeKeyInput
-) -> (EccPoint<'a>, ExchangeKeyStateInitiator<'a>) {
-  let g = EccPoint::new(input.params.g_x, input.params.g_y, input.params, false);
-  let r = U256::random_in_range(&mut rand::rng(), U256::C_1, input.params.n);
-  let r_point = g.ecc_mul(r, input.params);
+/// 返回 DER 编码的签名
+pub fn signature_to_der(sig: ([u8; 32], [u8; 32])) -> Vec<u8> {
+  let mut integers = Vec::with_capacity(70);
+  write_tlv(&mut integers, TAG_INTEGER, &der_unsigned_integer(&sig.0));
+  write_tlv(&mut integers, TAG_INTEGER, &der_unsigned_integer(&sig.1));
 
-  (r_point, ExchangeKeyStateInitiator { r_point, r })
+  let mut result = Vec::with_capacity(72);
+  write_tlv(&mut result, TAG_SEQUENCE, &integers);
+
+  result
 }
 
-/// # SM2 密钥交换生成函数
-///
-/// 生成密钥交换的响应
+/// # SM2 签名 DER 解码函数
 ///
 /// ## 参数
 ///
-/// * `input` - 密钥交换输入结构体
-/// * `received` - 对方生成的随机点
-/// * `private_key` - 私钥
-/// * `klen` - 密钥长度
+/// * `der` - DER 编码的签名
 ///
 /// ## 返回
 ///
-/// 如果协商成功，返回一个元组，包含密钥和发送到对方的负载，以及自己的状态结构体
+/// 如果解析成功，返回大端序的签名结果 (r, s)
 ///
-/// 如果协商失败，返回一个错误
-pub fn exchange_key_generate<'a>(
-  input: &'a ExchangeKeyInput,
-  received: &'a EccPoint,
-  private_key: U256,
-  klen: u64
-) -> Result<(ExchangeKeyGenerateOutput<'a>, ExchangeKeyStateReceiver<'a>), &'static str> {
-  // 提前验证 B5 前半部分，接收到的点是否在椭圆曲线上
-  if !received.validate_on_given_curve(input.params) {
-    return Err("Invalid received point");
-  }
-
-  // 生成随机数
-  let r = U256::random_in_range(&mut rand::rng(), U256::C_1, input.params.n);
-
-  // 计算随机点
-  let g = EccPoint::new(input.params.g_x, input.params.g_y, input.params, false);
-  let r_point = g.ecc_mul(r, input.params);
+/// 如果编码不合法、整数为负数或超过 256 位、或存在多余的数据，返回错误
+pub fn signature_from_der(der: &[u8]) -> Result<([u8; 32], [u8; 32]), Error> {
+  let mut outer = DerReader::new(der);
+  let mut fields = DerReader::new(outer.expect(TAG_SEQUENCE)?);
 
-  // omega = ceil(log2(n)).div_ceil(2) - 1
-  let omega = input.params.n.highest_bit().div_ceil(2) as u64 - 1;
+  if !outer.is_empty() {
+    return Err(Error::InvalidDer);
+  }
 
-  // x1_bar = 2^omega + (received.x & (2^omega - 1))
-  let x1_bar = (U256::C_1 << omega as u32) + (received.x & (U256::C_1 << omega as u32 - 1));
+  let r = parse_der_unsigned_integer(fields.expect(TAG_INTEGER)?)?;
+  let s = parse_der_unsigned_integer(fields.expect(TAG_INTEGER)?)?;
 
-  // x2_bar = 2^omega + (r_point.x & (2^omega - 1))
-  let x2_bar = (U256::C_1 << omega as u32) + (r_point.x & (U256::C_1 << omega as u32 - 1));
+  if !fields.is_empty() {
+    return Err(Error::InvalidDer);
+  }
 
-  // t = (private_key + x2_bar * r) mod n
-  let t =
-    private_key.mod_add(x2_bar.mod_mul(r, input.params.p), input.params.p).modded(input.params.n);
+  Ok((r, s))
+}
 
-  // V = [h \cdot t](input_other_public_key + [x1_bar]received)
-  let v = input
-    .other_public_key
-    .ecc_add(received.ecc_mul(x1_bar, input.params), input.params)
-    .ecc_mul(t, input.params);
+/// # 编码 DER 非负整数的内容
+///
+/// 去除前导 0；最高位为 1 时补一个 0x00，避免被解释为负数
+///
+/// ## 参数
+///
+/// * `bytes` - 大端序的整数
+///
+/// ## 返回
+///
+/// 返回 INTEGER 的内容，至少为一个字节
+fn der_unsigned_integer(bytes: &[u8; 32]) -> Vec<u8> {
+  let skip = bytes.iter().take_while(|b| **b == 0).count().min(31);
+  let significant = &bytes[skip ..];
 
-  // 如果 V 是无穷远点，则验证失败
-  if v.infinity {
-    return Err("Invalid received point");
+  let mut result = Vec::with_capacity(33);
+  if significant[0] & 0x80 != 0 {
+    result.push(0x00);
   }
+  result.extend_from_slice(significant);
 
-  // Z = v.x || v.y || Za || Zb
-  let mut sequence = BitSequence::new_empty();
-  sequence.append_bytes(&v.x.into_le_bytes());
-  sequence.append_bytes(&v.y.into_le_bytes());
-  sequence
-    .append_bytes(&get_z(input.params, &input.other_id, &input.other_public_key).into_le_bytes());
-  sequence.append_bytes(&get_z(input.params, &input.id, &input.public_key).into_le_bytes());
+  result
+}
 
-  // K = KDF(Z, klen)
-  let key = key_derivation_function(&sequence, klen);
+/// # 解析 DER 非负整数的内容
+///
+/// ## 参数
+///
+/// * `value` - INTEGER 的内容
+///
+/// ## 返回
+///
+/// 如果为最短编码的非负整数且不超过 256 位，返回大端序的 32 字节整数
+fn parse_der_unsigned_integer(value: &[u8]) -> Result<[u8; 32], Error> {
+  let magnitude = match value {
+    [] => return Err(Error::InvalidDer),
+    [first, ..] if first & 0x80 != 0 => return Err(Error::InvalidDer),
+    // 前导 0 仅在下一个字节最高位为 1 时允许
+    [0x00, second, ..] if second & 0x80 == 0 => return Err(Error::InvalidDer),
+    [0x00, rest @ ..] if !rest.is_empty() => rest,
+    _ => value
+  };
 
-  // 中间结果 Internal = v.x || Za || Zb || received.x || received.y || r_point.x
-  // || r_point.y
-  let mut to_hash_sequence_internal = BitSequence::new_empty();
-  to_hash_sequence_internal.append_bytes(&v.x.into_le_bytes());
-  to_hash_sequence_internal
-    .append_bytes(&get_z(input.params, &input.id, &input.public_key).into_le_bytes());
-  to_hash_sequence_internal
-    .append_bytes(&get_z(input.params, &input.other_id, &input.other_public_key).into_le_bytes());
-  to_hash_sequence_internal.append_bytes(&received.x.into_le_bytes());
-  to_hash_sequence_internal.append_bytes(&received.y.into_le_bytes());
-  to_hash_sequence_internal.append_bytes(&r_point.x.into_le_bytes());
-  to_hash_sequence_internal.append_bytes(&r_point.y.into_le_bytes());
+  if magnitude.len() > 32 {
+    return Err(Error::InvalidDer);
+  }
 
-  // 验证参数 S = H(0x02 || v.y || Internal)
-  let mut to_hash_sequence = BitSequence::new_empty();
-  to_hash_sequence.append_bytes(&[0x02]);
-  to_hash_sequence.append_bytes(&v.y.into_le_bytes());
-  to_hash_sequence.append_bytes(&hash(&to_hash_sequence_internal));
-  let validator = hash(&to_hash_sequence);
+  let mut result = [0u8; 32];
+  result[32 - magnitude.len() ..].copy_from_slice(magnitude);
 
-  Ok((
-    ExchangeKeyGenerateOutput {
-      key,
-      payload: ExchangeKeyGeneratePayload { received: r_point, validator }
-    },
-    ExchangeKeyStateReceiver { r_point, v, r_point_other: received.clone() }
-  ))
+  Ok(result)
 }
 
-/// # SM2 密钥交换确认函数
+/// # SM2 密文 DER 编码函数
 ///
-/// 确认密钥交换
+/// 将 `encrypt` 生成的 C1 || C2 || C3 密文编码为 GM/T 0009 规定的
+/// `SEQUENCE { XCoordinate INTEGER, YCoordinate INTEGER, HASH OCTET STRING,
+/// CipherText OCTET STRING }`，与 OpenSSL、GmSSL 等实现兼容
 ///
 /// ## 参数
 ///
-/// * `input` - 密钥交换输入结构体
-/// * `state` - 状态结构体
-/// * `respond` - 对方发送的载荷
-/// * `private_key` - 私钥
-/// * `klen` - 密钥长度
+/// * `cipher_text` - C1 || C2 || C3 格式的密文，C1 为大端序的未压缩编码
 ///
 /// ## 返回
 ///
-/// 如果协商成功，返回一个元组，包含密钥和验证参数
+/// 如果密文格式正确，返回 DER 编码的密文
 ///
-/// 如果协商失败，返回一个错误
-pub fn exchange_key_confirm<'a>(
-  input: &'a ExchangeKeyInput,
-  state: &'a ExchangeKeyStateInitiator<'a>,
-  respond: &'a ExchangeKeyGeneratePayload,
-  private_key: U256,
-  klen: u64
-) -> Result<ExchangeKeyConfirmOutput, &'static str> {
+/// 如果密文不是整字节、长度不足或 C1 不是未压缩编码，返回
+/// `Error::InvalidCipherText`
+pub fn ciphertext_to_der(cipher_text: &BitSequence) -> Result<Vec<u8>, Error> {
+  let bytes = cipher_text.get_bytes();
+
+  if cipher_text.get_last_byte_len() != 0 || bytes.len() < 65 + 32 || bytes[0] != 0x04 {
+    return Err(Error::InvalidCipherText);
+  }
+
+  let x: &[u8; 32] = bytes[1 .. 33].try_into().unwrap();
+  let y: &[u8; 32] = bytes[33 .. 65].try_into().unwrap();
+  let (c2, c3) = bytes[65 ..].split_at(bytes.len() - 65 - 32);
+
+  let mut fields = Vec::with_capacity(bytes.len() + 16);
+  write_tlv(&mut fields, TAG_INTEGER, &der_unsigned_integer(x));
+  write_tlv(&mut fields, TAG_INTEGER, &der_unsigned_integer(y));
+  write_tlv(&mut fields, TAG_OCTET_STRING, c3);
+  write_tlv(&mut fields, TAG_OCTET_STRING, c2);
+
+  let mut result = Vec::with_capacity(fields.len() + 6);
+  write_tlv(&mut result, TAG_SEQUENCE, &fields);
+
+  Ok(result)
+}
+
+/// # SM2 密文 DER 解码函数
+///
+/// ## 参数
+///
+/// * `der` - DER 编码的密文
+///
+/// ## 返回
+///
+/// 如果解析成功，返回 C1 || C2 || C3 格式的密文，可直接传入 `decrypt`
+///
+/// 如果编码不合法、坐标为负数或超过 256 位、杂凑值不是 32
+/// 字节、或存在多余的数据， 返回错误
+pub fn ciphertext_from_der(der: &[u8]) -> Result<BitSequence, Error> {
+  let mut outer = DerReader::new(der);
+  let mut fields = DerReader::new(outer.expect(TAG_SEQUENCE)?);
+
+  if !outer.is_empty() {
+    return Err(Error::InvalidDer);
+  }
+
+  let x = parse_der_unsigned_integer(fields.expect(TAG_INTEGER)?)?;
+  let y = parse_der_unsigned_integer(fields.expect(TAG_INTEGER)?)?;
+  let c3 = fields.expect(TAG_OCTET_STRING)?;
+  let c2 = fields.expect(TAG_OCTET_STRING)?;
+
+  if !fields.is_empty() || c3.len() != 32 {
+    return Err(Error::InvalidDer);
+  }
+
+  let mut result = BitSequence::new_empty();
+  result.append_bytes(&[0x04]);
+  result.append_bytes(&x);
+  result.append_bytes(&y);
+  result.append_bytes(c2);
+  result.append_bytes(c3);
+
+  Ok(result)
+}
+
+/// # SM2 密钥派生函数
+///
+/// 使用给定的比特序列和目标长度，生成一个比特序列
+///
+/// ## 参数
+///
+/// * `sequence` - 比特序列
+/// * `target_length` - 目标长度
+///
+/// ## 返回
+///
+/// 返回一个比特序列
+fn key_derivation_function(sequence: &BitSequence, target_length: u64) -> BitSequence {
+  let mut result = BitSequence::new_empty();
+
+  // 遍历次数 klen.div_ceil(v)，其中 v 为 256
+  let blocks: u32 = target_length.div_ceil(256) as u32;
+
+  // i 即为计数器 ct 计数，从 1 开始，到 blocks 结束
+  for i in 1 ..= if target_length % 256 != 0 { blocks - 1 } else { blocks } {
+    // Ha_i = H(Z || ct)
+
+    let mut temp_sequence = sequence.clone();
+
+    temp_sequence.append_bytes(&i.to_be_bytes());
+
+    result.append_bits(&hash_to_bits(&temp_sequence));
+  }
+
+  // 如果 target_length % 256 != 0，则需要处理最后一个块，长度为 klen - (v * (klen
+  // / v))
+  if target_length % 256 != 0 {
+    let mut temp_sequence = sequence.clone();
+
+    temp_sequence.append_bytes(&blocks.to_be_bytes());
+
+    // assertEq!(klen - (v * (klen / v)), klen % v)
+    let target_len = target_length % 256;
+
+    // 取 hash 结果前 target_len / 8 字节，其余舍弃
+    let mut hash_result = hash(&temp_sequence)[.. target_len.div_ceil(8) as usize].to_vec();
+
+    // 计算最后一个字节需要舍弃的位数，target_len 为 8 的倍数时不舍弃
+    let discarded_bits = (8 - target_len % 8) % 8;
+
+    let last_byte = hash_result.last_mut().unwrap();
+
+    // 仅舍弃右侧 discarded_bits 位，剩余位位置不变
+    *last_byte >>= discarded_bits;
+    *last_byte <<= discarded_bits;
+
+    result
+      .append_bits(&BitSequence::try_with_bits(hash_result.as_ref(), target_len as u64).unwrap());
+  }
+
+  result
+}
+
+/// # SM2 密钥交换输入结构体
+///
+/// 密钥交换输入结构体，包含椭圆曲线参数、用户ID、公钥和对方用户ID、对方公钥
+///
+/// ## 参数
+///
+/// * `params` - 椭圆曲线参数
+/// * `id` - 用户ID
+/// * `public_key` - 公钥
+/// * `other_id` - 对方用户ID
+/// * `other_public_key` - 对方公钥
+pub struct ExchangeKeyInput<'a> {
+  params: &'a EccParams,
+  id: BitSequence,
+  public_key: EccPoint<'a>,
+  other_id: BitSequence,
+  other_public_key: EccPoint<'a>
+}
+
+/// # SM2 密钥交换发起者状态结构体
+///
+/// 记录密钥交换发起者的状态，包含随机点、随机数
+///
+/// ## 参数
+///
+/// * `r_point` - 随机点
+/// * `r` - 随机数
+pub struct ExchangeKeyStateInitiator<'a> {
+  r_point: EccPoint<'a>,
+  r: U256
+}
+
+/// # SM2 密钥交换接收者状态结构体
+///
+/// 记录密钥交换接收者的状态，包含随机点、随机数、计算点、对方随机点
+///
+/// ## 参数
+///
+/// * `r_point` - 随机点
+/// * `r` - 随机数
+/// * `v` - 计算点
+/// * `r_point_other` - 对方随机点
+pub struct ExchangeKeyStateReceiver<'a> {
+  r_point: EccPoint<'a>,
+  v: EccPoint<'a>,
+  r_point_other: EccPoint<'a>
+}
+
+/// # SM2 密钥交换生成输出结构体
+///
+/// 密钥交换生成输出结构体，包含密钥和发送到对方的负载
+///
+/// ## 参数
+///
+/// * `key` - 密钥
+/// * `payload` - 发送到对方的负载
+pub struct ExchangeKeyGenerateOutput<'a> {
+  pub key: BitSequence,
+  pub payload: ExchangeKeyGeneratePayload<'a>
+}
+
+/// # SM2 密钥交换生成负载结构体
+///
+/// 密钥交换生成负载结构体，包含自己的随机点、验证参数
+///
+/// ## 参数
+///
+/// * `received` - 随机点
+/// * `validator` - 验证参数
+pub struct ExchangeKeyGeneratePayload<'a> {
+  pub received: EccPoint<'a>,
+  pub validator: [u8; 32]
+}
+
+/// # SM2 密钥交换确认输出结构体
+///
+/// 密钥交换确认输出结构体，包含密钥和验证参数
+///
+/// ## 参数
+///
+/// * `key` - 密钥
+/// * `payload` - 验证参数
+pub struct ExchangeKeyConfirmOutput {
+  pub key: BitSequence,
+  pub payload: [u8; 32]
+}
+
+/// # SM2 密钥交换初始化函数
+///
+/// 初始化密钥交换，生成随机点
+///
+/// ## 参数
+///
+/// * `input` - 密钥交换输入结构体
+///
+/// ## 返回
+///
+/// 返回一个元组，包含随机点和一个状态结构体
+#[cfg(feature = "std")]
+pub fn exchange_key_initiate<'a>(
+  input: &'a ExchangeKeyInput
+) -> (EccPoint<'a>, ExchangeKeyStateInitiator<'a>) {
+  exchange_key_initiate_with_rng(input, &mut rand::rng())
+}
+
+/// # SM2 指定随机数生成器的密钥交换初始化函数
+///
+/// 与 `exchange_key_initiate` 相同，但从调用者提供的随机数生成器取得随机数
+///
+/// ## 参数
+///
+/// * `input` - 密钥交换输入结构体
+/// * `rng` - 密码学安全的随机数生成器
+///
+/// ## 返回
+///
+/// 返回一个元组，包含随机点和一个状态结构体
+pub fn exchange_key_initiate_with_rng<'a, R: CryptoRng>(
+  input: &'a ExchangeKeyInput,
+  rng: &mut R
+) -> (EccPoint<'a>, ExchangeKeyStateInitiator<'a>) {
+  let r = U256::random_in_range(rng, U256::C_1, input.params.n);
+  let r_point = mul_generator(r, input.params);
+
+  (r_point, ExchangeKeyStateInitiator { r_point, r })
+}
+
+/// # SM2 密钥交换生成函数
+///
+/// 生成密钥交换的响应
+///
+/// ## 参数
+///
+/// * `input` - 密钥交换输入结构体
+/// * `received` - 对方生成的随机点
+/// * `private_key` - 私钥
+/// * `klen` - 密钥长度
+///
+/// ## 返回
+///
+/// 如果协商成功，返回一个元组，包含密钥和发送到对方的负载，以及自己的状态结构体
+///
+/// 如果协商失败，返回一个错误
+#[cfg(feature = "std")]
+pub fn exchange_key_generate<'a>(
+  input: &'a ExchangeKeyInput,
+  received: &'a EccPoint,
+  private_key: U256,
+  klen: u64
+) -> Result<(ExchangeKeyGenerateOutput<'a>, ExchangeKeyStateReceiver<'a>), Error> {
+  exchange_key_generate_with_rng(input, received, private_key, klen, &mut rand::rng())
+}
+
+/// # SM2 指定随机数生成器的密钥交换生成函数
+///
+/// 与 `exchange_key_generate` 相同，但从调用者提供的随机数生成器取得随机数
+///
+/// ## 参数
+///
+/// * `input` - 密钥交换输入结构体
+/// * `received` - 对方生成的随机点
+/// * `private_key` - 私钥
+/// * `klen` - 密钥长度
+/// * `rng` - 密码学安全的随机数生成器
+///
+/// ## 返回
+///
+/// 如果协商成功，返回一个元组，包含密钥和发送到对方的负载，以及自己的状态结构体
+///
+/// 如果协商失败，返回一个错误
+pub fn exchange_key_generate_with_rng<'a, R: CryptoRng>(
+  input: &'a ExchangeKeyInput,
+  received: &'a EccPoint,
+  private_key: U256,
+  klen: u64,
+  rng: &mut R
+) -> Result<(ExchangeKeyGenerateOutput<'a>, ExchangeKeyStateReceiver<'a>), Error> {
+  // 提前验证 B5 前半部分，接收到的点是否在椭圆曲线上
+  if !received.validate_on_given_curve(input.params) {
+    return Err(Error::NotOnCurve);
+  }
+
+  // 生成随机数
+  let r = U256::random_in_range(rng, U256::C_1, input.params.n);
+
+  // 计算随机点
+  let r_point = mul_generator(r, input.params);
+
+  // omega = ceil(log2(n)).div_ceil(2) - 1
+  let omega = input.params.n.highest_bit().div_ceil(2) as u64 - 1;
+
+  // x1_bar = 2^omega + (received.x & (2^omega - 1))
+  let x1_bar = (U256::C_1 << omega as u32) + (received.x & (U256::C_1 << omega as u32 - 1));
+
+  // x2_bar = 2^omega + (r_point.x & (2^omega - 1))
+  let x2_bar = (U256::C_1 << omega as u32) + (r_point.x & (U256::C_1 << omega as u32 - 1));
+
+  // t = (private_key + x2_bar * r) mod n
+  let t =
+    private_key.mod_add(x2_bar.mod_mul(r, input.params.p), input.params.p).modded(input.params.n);
+
+  // V = [h \cdot t](input_other_public_key + [x1_bar]received)
+  let v = input
+    .other_public_key
+    .ecc_add(received.ecc_mul_with(x1_bar, input.params, MulStrategy::Windowed), input.params)
+    .ecc_mul_ct(t, input.params);
+
+  // 如果 V 是无穷远点，则验证失败
+  if v.infinity {
+    return Err(Error::InvalidPoint);
+  }
+
+  // Z = v.x || v.y || Za || Zb
+  let mut sequence = BitSequence::new_empty();
+  sequence.append_bytes(&v.x.into_be_bytes());
+  sequence.append_bytes(&v.y.into_be_bytes());
+  sequence
+    .append_bytes(&get_z(input.params, &input.other_id, &input.other_public_key).into_be_bytes());
+  sequence.append_bytes(&get_z(input.params, &input.id, &input.public_key).into_be_bytes());
+
+  // K = KDF(Z, klen)
+  let key = key_derivation_function(&sequence, klen);
+
+  // 中间结果 Internal = v.x || Za || Zb || received.x || received.y || r_point.x
+  // || r_point.y
+  let mut to_hash_sequence_internal = BitSequence::new_empty();
+  to_hash_sequence_internal.append_bytes(&v.x.into_be_bytes());
+  to_hash_sequence_internal
+    .append_bytes(&get_z(input.params, &input.id, &input.public_key).into_be_bytes());
+  to_hash_sequence_internal
+    .append_bytes(&get_z(input.params, &input.other_id, &input.other_public_key).into_be_bytes());
+  to_hash_sequence_internal.append_bytes(&received.x.into_be_bytes());
+  to_hash_sequence_internal.append_bytes(&received.y.into_be_bytes());
+  to_hash_sequence_internal.append_bytes(&r_point.x.into_be_bytes());
+  to_hash_sequence_internal.append_bytes(&r_point.y.into_be_bytes());
+
+  // 验证参数 S = H(0x02 || v.y || Internal)
+  let mut to_hash_sequence = BitSequence::new_empty();
+  to_hash_sequence.append_bytes(&[0x02]);
+  to_hash_sequence.append_bytes(&v.y.into_be_bytes());
+  to_hash_sequence.append_bits(&hash_to_bits(&to_hash_sequence_internal));
+  let validator = hash(&to_hash_sequence);
+
+  Ok((
+    ExchangeKeyGenerateOutput {
+      key,
+      payload: ExchangeKeyGeneratePayload { received: r_point, validator }
+    },
+    ExchangeKeyStateReceiver { r_point, v, r_point_other: received.clone() }
+  ))
+}
+
+/// # SM2 密钥交换确认函数
+///
+/// 确认密钥交换
+///
+/// ## 参数
+///
+/// * `input` - 密钥交换输入结构体
+/// * `state` - 状态结构体
+/// * `respond` - 对方发送的载荷
+/// * `private_key` - 私钥
+/// * `klen` - 密钥长度
+///
+/// ## 返回
+///
+/// 如果协商成功，返回一个元组，包含密钥和验证参数
+///
+/// 如果协商失败，返回一个错误
+pub fn exchange_key_confirm<'a>(
+  input: &'a ExchangeKeyInput,
+  state: &'a ExchangeKeyStateInitiator<'a>,
+  respond: &'a ExchangeKeyGeneratePayload,
+  private_key: U256,
+  klen: u64
+) -> Result<ExchangeKeyConfirmOutput, Error> {
   // 提前验证 A6 前半部分，接收到的点是否在椭圆曲线上
   if !respond.received.validate_on_given_curve(input.params) {
-    return Err("Invalid received point");
+    return Err(Error::NotOnCurve);
+  }
+
+  // omega = ceil(log2(n)).div_ceil(2) - 1
+  let omega: u64 = (input.params.n.highest_bit() - 1).div_ceil(2) as u64 - 1;
+
+  // x1_bar = 2^omega + (state.r_point.x & (2^omega - 1))
+  let x1_bar = (U256::C_1 << omega as u32) + (state.r_point.x & (U256::C_1 << omega as u32 - 1));
+
+  // x2_bar = 2^omega + (respond.received.x & (2^omega - 1))
+  let x2_bar = (U256::C_1 << omega as u32) + (respond.received.x & (U256::C_1 << omega as u32 - 1));
+
+  // t = (private_key + x1_bar * r) mod n
+  let t = private_key
+    .mod_add(x1_bar.mod_mul(state.r, input.params.p), input.params.p)
+    .modded(input.params.n);
+
+  // U = [h \cdot t](input_other_public_key + [x2_bar]respond.received)
+  let u = input
+    .other_public_key
+    .ecc_add(
+      respond.received.ecc_mul_with(x2_bar, input.params, MulStrategy::Windowed),
+      input.params
+    )
+    .ecc_mul_ct(t, input.params);
+
+  // 如果 U 是无穷远点，则验证失败
+  if u.infinity {
+    return Err(Error::InvalidPoint);
+  }
+
+  // Z = u.x || u.y || Za || Zb
+  let mut sequence = BitSequence::new_empty();
+  sequence.append_bytes(&u.x.into_be_bytes());
+  sequence.append_bytes(&u.y.into_be_bytes());
+  sequence
+    .append_bytes(&get_z(input.params, &input.other_id, &input.other_public_key).into_be_bytes());
+  sequence.append_bytes(&get_z(input.params, &input.id, &input.public_key).into_be_bytes());
+
+  // K = KDF(Z, klen)
+  let key = key_derivation_function(&sequence, klen);
+
+  // 中间结果 Internal = u.x || Za || Zb || received.x || received.y || r_point.x
+  // || r_point.y
+  let mut to_hash_sequence_internal = BitSequence::new_empty();
+  to_hash_sequence_internal.append_bytes(&u.x.into_be_bytes());
+  to_hash_sequence_internal
+    .append_bytes(&get_z(input.params, &input.id, &input.public_key).into_be_bytes());
+  to_hash_sequence_internal
+    .append_bytes(&get_z(input.params, &input.other_id, &input.other_public_key).into_be_bytes());
+  to_hash_sequence_internal.append_bytes(&state.r_point.x.into_be_bytes());
+  to_hash_sequence_internal.append_bytes(&state.r_point.y.into_be_bytes());
+  to_hash_sequence_internal.append_bytes(&respond.received.x.into_be_bytes());
+  to_hash_sequence_internal.append_bytes(&respond.received.y.into_be_bytes());
+
+  // 验证参数 Sb = H(0x02 || u.y || Internal)
+  let mut to_hash_sequence_1 = BitSequence::new_empty();
+  to_hash_sequence_1.append_bytes(&[0x02]);
+  to_hash_sequence_1.append_bytes(&u.y.into_be_bytes());
+  to_hash_sequence_1.append_bits(&hash_to_bits(&to_hash_sequence_internal));
+  let validator_1 = hash(&to_hash_sequence_1);
+
+  // 如果验证参数不匹配，则验证失败
+  if !ct_eq_bytes(&validator_1, &respond.validator) {
+    return Err(Error::ValidatorMismatch);
+  }
+
+  // 验证参数 Sa = H(0x03 || u.y || Internal)
+  let mut to_hash_sequence_2 = BitSequence::new_empty();
+  to_hash_sequence_2.append_bytes(&[0x03]);
+  to_hash_sequence_2.append_bytes(&u.y.into_be_bytes());
+  to_hash_sequence_2.append_bits(&hash_to_bits(&to_hash_sequence_internal));
+  let validator_2 = hash(&to_hash_sequence_2);
+
+  Ok(ExchangeKeyConfirmOutput { key, payload: validator_2 })
+}
+
+/// # SM2 密钥交换验证函数
+///
+/// 验证密钥交换
+///
+/// ## 参数
+///
+/// * `input` - 密钥交换输入结构体
+/// * `state` - 状态结构体
+/// * `respond` - 对方发送的验证参数
+///
+/// ## 返回
+///
+/// 如果验证成功，返回 true
+pub fn exchange_key_validate<'a>(
+  input: &'a ExchangeKeyInput,
+  state: &'a ExchangeKeyStateReceiver<'a>,
+  respond: &[u8; 32]
+) -> bool {
+  // 中间结果 Internal = v.x || Za || Zb || state.r_point_other.x ||
+  // state.r_point_other.y || state.r_point.x || state.r_point.y
+  let mut to_hash_sequence_internal = BitSequence::new_empty();
+  to_hash_sequence_internal.append_bytes(&state.v.x.into_be_bytes());
+  to_hash_sequence_internal
+    .append_bytes(&get_z(input.params, &input.id, &input.public_key).into_be_bytes());
+  to_hash_sequence_internal
+    .append_bytes(&get_z(input.params, &input.other_id, &input.other_public_key).into_be_bytes());
+  to_hash_sequence_internal.append_bytes(&state.r_point_other.x.into_be_bytes());
+  to_hash_sequence_internal.append_bytes(&state.r_point_other.y.into_be_bytes());
+  to_hash_sequence_internal.append_bytes(&state.r_point.x.into_be_bytes());
+  to_hash_sequence_internal.append_bytes(&state.r_point.y.into_be_bytes());
+
+  // 验证参数 Sa = H(0x03 || v.y || Internal)
+  let mut to_hash_sequence = BitSequence::new_empty();
+  to_hash_sequence.append_bytes(&[0x03]);
+  to_hash_sequence.append_bytes(&state.v.y.into_be_bytes());
+  to_hash_sequence.append_bits(&hash_to_bits(&to_hash_sequence_internal));
+
+  // 如果验证参数不匹配，则验证失败
+  ct_eq_bytes(&hash(&to_hash_sequence), respond)
+}
+
+/// # SM2 密文格式
+///
+/// 指定密文中 C1、C2、C3 的拼接顺序
+///
+/// ## 变体
+///
+/// * `C1C2C3` - C1 || C2 || C3，GB/T 32918 规定的顺序，为默认值
+/// * `C1C3C2` - C1 || C3 || C2，GM/T 0009 等规范及 GmSSL、Bouncy Castle
+///   等实现使用的顺序
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum CipherFormat {
+  #[default]
+  C1C2C3,
+  C1C3C2
+}
+
+/// # SM2 加密函数
+///
+/// 加密消息
+///
+/// ## 参数
+///
+/// * `params` - 椭圆曲线参数
+/// * `message` - 消息
+/// * `public_key` - 公钥
+///
+/// ## 返回
+///
+/// 如果加密成功，返回密文
+///
+/// 如果加密失败，返回错误
+#[cfg(feature = "std")]
+pub fn encrypt(
+  params: &EccParams,
+  message: &BitSequence,
+  public_key: &EccPoint
+) -> Result<BitSequence, Error> {
+  encrypt_with_aad(params, message, &BitSequence::new_empty(), public_key)
+}
+
+/// # SM2 指定随机数生成器的加密函数
+///
+/// 与 `encrypt` 相同，但从调用者提供的随机数生成器取得随机数 k
+///
+/// ## 参数
+///
+/// * `params` - 椭圆曲线参数
+/// * `message` - 消息
+/// * `public_key` - 公钥
+/// * `rng` - 密码学安全的随机数生成器
+///
+/// ## 返回
+///
+/// 如果加密成功，返回密文
+///
+/// 如果加密失败，返回错误
+pub fn encrypt_with_rng<R: CryptoRng>(
+  params: &EccParams,
+  message: &BitSequence,
+  public_key: &EccPoint,
+  rng: &mut R
+) -> Result<BitSequence, Error> {
+  encrypt_random_k(
+    params,
+    message,
+    &BitSequence::new_empty(),
+    public_key,
+    ByteOrder::BigEndian,
+    CipherFormat::C1C2C3,
+    rng
+  )
+}
+
+/// # SM2 带关联数据的加密函数
+///
+/// 将关联数据 AAD 混入杂凑值的计算，即 C3 = H(x2 || AAD || M || y2)，
+/// 使密文与关联数据绑定；AAD 本身不包含在密文中
+///
+/// ## 参数
+///
+/// * `params` - 椭圆曲线参数
+/// * `message` - 消息
+/// * `aad` - 关联数据
+/// * `public_key` - 公钥
+///
+/// ## 返回
+///
+/// 如果加密成功，返回密文
+///
+/// 如果加密失败，返回错误
+///
+/// ## 注意事项
+///
+/// * 这是本 crate 的扩展，并非 GB/T 32918 规定的算法；AAD 非空时， 密文只能由
+///   `decrypt_with_aad` 使用相同的 AAD 解密
+/// * AAD 为空时，与 `encrypt` 等价
+#[cfg(feature = "std")]
+pub fn encrypt_with_aad(
+  params: &EccParams,
+  message: &BitSequence,
+  aad: &BitSequence,
+  public_key: &EccPoint
+) -> Result<BitSequence, Error> {
+  encrypt_random_k(
+    params,
+    message,
+    aad,
+    public_key,
+    ByteOrder::BigEndian,
+    CipherFormat::C1C2C3,
+    &mut rand::rng()
+  )
+}
+
+/// # SM2 指定字节序的加密函数
+///
+/// 与 `encrypt` 相同，但密文中 C1 的坐标使用指定字节序编码
+///
+/// ## 参数
+///
+/// * `params` - 椭圆曲线参数
+/// * `message` - 消息
+/// * `public_key` - 公钥
+/// * `order` - C1 坐标的字节序
+///
+/// ## 返回
+///
+/// 如果加密成功，返回密文
+///
+/// 如果加密失败，返回错误
+#[cfg(feature = "std")]
+pub fn encrypt_with_order(
+  params: &EccParams,
+  message: &BitSequence,
+  public_key: &EccPoint,
+  order: ByteOrder
+) -> Result<BitSequence, Error> {
+  encrypt_random_k(
+    params,
+    message,
+    &BitSequence::new_empty(),
+    public_key,
+    order,
+    CipherFormat::C1C2C3,
+    &mut rand::rng()
+  )
+}
+
+/// # SM2 指定密文格式的加密函数
+///
+/// 与 `encrypt` 相同，但按指定格式拼接 C1、C2、C3
+///
+/// ## 参数
+///
+/// * `params` - 椭圆曲线参数
+/// * `message` - 消息
+/// * `public_key` - 公钥
+/// * `format` - 密文格式
+///
+/// ## 返回
+///
+/// 如果加密成功，返回密文
+///
+/// 如果加密失败，返回错误
+#[cfg(feature = "std")]
+pub fn encrypt_with_format(
+  params: &EccParams,
+  message: &BitSequence,
+  public_key: &EccPoint,
+  format: CipherFormat
+) -> Result<BitSequence, Error> {
+  encrypt_random_k(
+    params,
+    message,
+    &BitSequence::new_empty(),
+    public_key,
+    ByteOrder::BigEndian,
+    format,
+    &mut rand::rng()
+  )
+}
+
+/// # SM2 随机数加密函数
+///
+/// 随机生成 k 并加密，t 全为 0 时重新生成 k
+///
+/// ## 参数
+///
+/// * `params` - 椭圆曲线参数
+/// * `message` - 消息
+/// * `aad` - 关联数据，为空时即标准的 SM2 加密
+/// * `public_key` - 公钥
+/// * `order` - C1 坐标的字节序
+/// * `format` - 密文格式
+/// * `rng` - 密码学安全的随机数生成器
+///
+/// ## 返回
+///
+/// 如果加密成功，返回密文
+///
+/// 如果加密失败，返回错误
+fn encrypt_random_k<R: CryptoRng>(
+  params: &EccParams,
+  message: &BitSequence,
+  aad: &BitSequence,
+  public_key: &EccPoint,
+  order: ByteOrder,
+  format: CipherFormat,
+  rng: &mut R
+) -> Result<BitSequence, Error> {
+  loop {
+    let k = U256::random_in_range(rng, U256::C_1, params.n);
+
+    // t 全为 0 时，重新生成 k
+    match encrypt_with_k_and_aad(params, message, aad, public_key, k, order, format) {
+      Err(Error::KdfAllZero) => continue,
+      result => break result
+    }
+  }
+}
+
+/// # SM2 指定随机数加密函数
+///
+/// 使用调用者提供的随机数 k 加密消息，用于复现标准中的已知答案测试
+///
+/// ## 参数
+///
+/// * `params` - 椭圆曲线参数
+/// * `message` - 消息
+/// * `public_key` - 公钥
+/// * `k` - 随机数，取值范围为 [1, n - 1]
+///
+/// ## 返回
+///
+/// 如果加密成功，返回密文
+///
+/// 如果加密失败，返回错误；当 k 导致 t 全为 0 时，返回 `Error::KdfAllZero`
+pub fn encrypt_with_k(
+  params: &EccParams,
+  message: &BitSequence,
+  public_key: &EccPoint,
+  k: U256
+) -> Result<BitSequence, Error> {
+  encrypt_with_k_and_aad(
+    params,
+    message,
+    &BitSequence::new_empty(),
+    public_key,
+    k,
+    ByteOrder::BigEndian,
+    CipherFormat::C1C2C3
+  )
+}
+
+/// # SM2 指定随机数和关联数据的加密函数
+///
+/// ## 参数
+///
+/// * `params` - 椭圆曲线参数
+/// * `message` - 消息
+/// * `aad` - 关联数据，为空时即标准的 SM2 加密
+/// * `public_key` - 公钥
+/// * `k` - 随机数，取值范围为 [1, n - 1]
+/// * `order` - C1 坐标的字节序
+/// * `format` - 密文格式
+///
+/// ## 返回
+///
+/// 如果加密成功，返回密文
+///
+/// 如果加密失败，返回错误；当 k 导致 t 全为 0 时，返回 `Error::KdfAllZero`
+fn encrypt_with_k_and_aad(
+  params: &EccParams,
+  message: &BitSequence,
+  aad: &BitSequence,
+  public_key: &EccPoint,
+  k: U256,
+  order: ByteOrder,
+  format: CipherFormat
+) -> Result<BitSequence, Error> {
+  if k.eq_u64(0) || k >= params.n {
+    return Err(Error::InvalidNonce);
   }
 
-  // omega = ceil(log2(n)).div_ceil(2) - 1
-  let omega: u64 = (input.params.n.highest_bit() - 1).div_ceil(2) as u64 - 1;
+  // C1 = [k]G
+  let c1 = mul_generator(k, params);
 
-  // x1_bar = 2^omega + (state.r_point.x & (2^omega - 1))
-  let x1_bar = (U256::C_1 << omega as u32) + (state.r_point.x & (U256::C_1 << omega as u32 - 1));
+  if c1.infinity {
+    return Err(Error::InvalidPoint);
+  }
 
-  // x2_bar = 2^omega + (respond.received.x & (2^omega - 1))
-  let x2_bar = (U256::C_1 << omega as u32) + (respond.received.x & (U256::C_1 << omega as u32 - 1));
+  // S = [h]Pb，SM2 推荐曲线的余因子 h 为 1，即 S = Pb
+  if public_key.infinity {
+    return Err(Error::InvalidPublicKey);
+  }
 
-  // t = (private_key + x1_bar * r) mod n
-  let t = private_key
-    .mod_add(x1_bar.mod_mul(state.r, input.params.p), input.params.p)
-    .modded(input.params.n);
+  // (x2, y2) = [k]Pb
+  let intermediate = public_key.ecc_mul_ct(k, params);
 
-  // U = [h \cdot t](input_other_public_key + [x2_bar]respond.received)
-  let u = input
-    .other_public_key
-    .ecc_add(respond.received.ecc_mul(x2_bar, input.params), input.params)
-    .ecc_mul(t, input.params);
+  // t = KDF(x2 || y2, klen)
+  let mut sequence = BitSequence::new_empty();
+  sequence.append_bits(&BitSequence::try_with_bits(&intermediate.x.into_be_bytes(), 256).unwrap());
+  sequence.append_bits(&BitSequence::try_with_bits(&intermediate.y.into_be_bytes(), 256).unwrap());
 
-  // 如果 U 是无穷远点，则验证失败
-  if u.infinity {
-    return Err("Invalid received point");
+  let t = key_derivation_function(&sequence, message.len());
+
+  if t.get_bytes().iter().all(|b| *b == 0) {
+    return Err(Error::KdfAllZero);
   }
 
-  // Z = u.x || u.y || Za || Zb
+  // c2 = M ^ t
+  let c2 = message.xor(&t).unwrap();
+
+  // c3 = H(x2 || AAD || M || y2)
   let mut sequence = BitSequence::new_empty();
-  sequence.append_bytes(&u.x.into_le_bytes());
-  sequence.append_bytes(&u.y.into_le_bytes());
-  sequence
-    .append_bytes(&get_z(input.params, &input.other_id, &input.other_public_key).into_le_bytes());
-  sequence.append_bytes(&get_z(input.params, &input.id, &input.public_key).into_le_bytes());
+  sequence.append_bits(&BitSequence::try_with_bits(&intermediate.x.into_be_bytes(), 256).unwrap());
+  sequence.append_bits(aad);
+  sequence.append_bits(message);
+  sequence.append_bits(&BitSequence::try_with_bits(&intermediate.y.into_be_bytes(), 256).unwrap());
 
-  // K = KDF(Z, klen)
-  let key = key_derivation_function(&sequence, klen);
+  let c3 = hash_to_bits(&sequence);
 
-  // 中间结果 Internal = u.x || Za || Zb || received.x || received.y || r_point.x
-  // || r_point.y
-  let mut to_hash_sequence_internal = BitSequence::new_empty();
-  to_hash_sequence_internal.append_bytes(&u.x.into_le_bytes());
-  to_hash_sequence_internal
-    .append_bytes(&get_z(input.params, &input.id, &input.public_key).into_le_bytes());
-  to_hash_sequence_internal
-    .append_bytes(&get_z(input.params, &input.other_id, &input.other_public_key).into_le_bytes());
-  to_hash_sequence_internal.append_bytes(&state.r_point.x.into_le_bytes());
-  to_hash_sequence_internal.append_bytes(&state.r_point.y.into_le_bytes());
-  to_hash_sequence_internal.append_bytes(&respond.received.x.into_le_bytes());
-  to_hash_sequence_internal.append_bytes(&respond.received.y.into_le_bytes());
+  let mut result = BitSequence::new_empty();
+  result.append_bytes(&c1.to_bytes_with_order(order));
+
+  match format {
+    CipherFormat::C1C2C3 => {
+      result.append_bits(&c2);
+      result.append_bits(&c3);
+    },
+    CipherFormat::C1C3C2 => {
+      result.append_bits(&c3);
+      result.append_bits(&c2);
+    }
+  }
+
+  Ok(result)
+}
+
+/// # 常数时间字节序列比较函数
+///
+/// 长度不一致时直接返回 false，长度一致时比较时间与内容无关
+///
+/// ## 参数
+///
+/// * `a` - 字节序列
+/// * `b` - 字节序列
+///
+/// ## 返回
+///
+/// 返回一个布尔值，表示两者是否一致
+fn ct_eq_bytes(a: &[u8], b: &[u8]) -> bool {
+  if a.len() != b.len() {
+    return false;
+  }
+
+  // 累积所有字节的差异，不提前返回
+  a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// # SM2 杂凑值校验函数
+///
+/// 以常数时间比较期望的杂凑值与比特序列，长度不一致时直接返回 false
+///
+/// ## 参数
+///
+/// * `expected` - 期望的杂凑值
+/// * `sequence_slice` - 待校验的比特序列
+///
+/// ## 返回
+///
+/// 返回一个布尔值，表示两者是否一致
+fn check_mac(expected: &[u8], sequence_slice: &BitSequence) -> bool {
+  sequence_slice.get_last_byte_len() == 0 && ct_eq_bytes(expected, sequence_slice.get_bytes())
+}
+
+/// # SM2 解密函数
+///
+/// 解密消息
+///
+/// ## 参数
+///
+/// * `params` - 椭圆曲线参数
+/// * `cipher_text` - 密文
+/// * `private_key` - 私钥
+///
+/// ## 返回
+///
+/// 如果解密成功，返回明文
+///
+/// 如果解密失败，返回错误；密文长度不足时返回 `Error::InvalidCipherText`
+pub fn decrypt(
+  params: &EccParams,
+  cipher_text: &BitSequence,
+  private_key: U256
+) -> Result<BitSequence, Error> {
+  decrypt_with_aad(params, cipher_text, &BitSequence::new_empty(), private_key)
+}
+
+/// # SM2 指定字节序的解密函数
+///
+/// 解密 `encrypt_with_order` 生成的密文
+///
+/// ## 参数
+///
+/// * `params` - 椭圆曲线参数
+/// * `cipher_text` - 密文
+/// * `private_key` - 私钥
+/// * `order` - C1 坐标的字节序，须与加密时一致
+///
+/// ## 返回
+///
+/// 如果解密成功，返回明文
+///
+/// 如果解密失败，返回错误
+pub fn decrypt_with_order(
+  params: &EccParams,
+  cipher_text: &BitSequence,
+  private_key: U256,
+  order: ByteOrder
+) -> Result<BitSequence, Error> {
+  decrypt_inner(
+    params,
+    cipher_text,
+    &BitSequence::new_empty(),
+    private_key,
+    order,
+    CipherFormat::C1C2C3
+  )
+}
+
+/// # SM2 指定密文格式的解密函数
+///
+/// 解密 `encrypt_with_format` 生成的密文
+///
+/// ## 参数
+///
+/// * `params` - 椭圆曲线参数
+/// * `cipher_text` - 密文
+/// * `private_key` - 私钥
+/// * `format` - 密文格式，须与加密时一致
+///
+/// ## 返回
+///
+/// 如果解密成功，返回明文
+///
+/// 如果解密失败，返回错误
+pub fn decrypt_with_format(
+  params: &EccParams,
+  cipher_text: &BitSequence,
+  private_key: U256,
+  format: CipherFormat
+) -> Result<BitSequence, Error> {
+  decrypt_inner(
+    params,
+    cipher_text,
+    &BitSequence::new_empty(),
+    private_key,
+    ByteOrder::BigEndian,
+    format
+  )
+}
+
+/// # SM2 带关联数据的解密函数
+///
+/// 解密 `encrypt_with_aad` 生成的密文，校验 C3 = H(x2 || AAD || M || y2)
+///
+/// ## 参数
+///
+/// * `params` - 椭圆曲线参数
+/// * `cipher_text` - 密文
+/// * `aad` - 关联数据，须与加密时一致
+/// * `private_key` - 私钥
+///
+/// ## 返回
+///
+/// 如果解密成功，返回明文
+///
+/// 如果解密失败，返回错误；AAD 不一致时返回 `Error::DigestMismatch`
+///
+/// ## 注意事项
+///
+/// * 这是本 crate 的扩展，并非 GB/T 32918 规定的算法
+/// * AAD 为空时，与 `decrypt` 等价
+pub fn decrypt_with_aad(
+  params: &EccParams,
+  cipher_text: &BitSequence,
+  aad: &BitSequence,
+  private_key: U256
+) -> Result<BitSequence, Error> {
+  decrypt_inner(params, cipher_text, aad, private_key, ByteOrder::BigEndian, CipherFormat::C1C2C3)
+}
+
+/// # SM2 解密函数的实现
+///
+/// ## 参数
+///
+/// * `params` - 椭圆曲线参数
+/// * `cipher_text` - 密文
+/// * `aad` - 关联数据，为空时即标准的 SM2 解密
+/// * `private_key` - 私钥
+/// * `order` - C1 坐标的字节序
+/// * `format` - 密文格式
+///
+/// ## 返回
+///
+/// 如果解密成功，返回明文
+///
+/// 如果解密失败，返回错误
+fn decrypt_inner(
+  params: &EccParams,
+  cipher_text: &BitSequence,
+  aad: &BitSequence,
+  private_key: U256,
+  order: ByteOrder,
+  format: CipherFormat
+) -> Result<BitSequence, Error> {
+  // 密文由 C1（65 字节）、C2 与 C3（32 字节）按指定格式拼接而成；
+  // C2 与明文等长，不一定是整字节，因此 C2 和 C3 按比特偏移截取
+  let len = cipher_text.len();
+
+  if len < (65 + 32) * 8 {
+    return Err(Error::InvalidCipherText);
+  }
+
+  let c1_bytes = cipher_text.first_bytes(65).ok_or(Error::InvalidCipherText)?;
+  let (c2, c3) = match format {
+    CipherFormat::C1C2C3 => {
+      let c2 = cipher_text.slice(65 * 8, len - 256)?;
+      // 密文为整字节时 C3 即最后 32 字节，否则 C3 跨越字节边界
+      let c3 = match cipher_text.get_last_byte_len() {
+        0 => BitSequence::with_bytes(cipher_text.last_bytes(32).ok_or(Error::InvalidCipherText)?),
+        _ => cipher_text.slice(len - 256, len)?
+      };
+
+      (c2, c3)
+    },
+    CipherFormat::C1C3C2 => {
+      let c1_c3 = cipher_text.first_bytes(65 + 32).ok_or(Error::InvalidCipherText)?;
+
+      (cipher_text.slice((65 + 32) * 8, len)?, BitSequence::with_bytes(&c1_c3[65 ..]))
+    }
+  };
+
+  let key_length = c2.len();
+  let c1 = EccPoint::try_from_bytes_with_order(c1_bytes, params, order)?;
+
+  if !c1.validate_on_curve() {
+    return Err(Error::NotOnCurve);
+  }
+
+  // S = [h]C1，SM2 推荐曲线的余因子 h 为 1，即 S = C1
+  if c1.infinity {
+    return Err(Error::InvalidPoint);
+  }
+
+  let p2 = c1.ecc_mul_ct(private_key, params);
+
+  let mut sequence = BitSequence::new_empty();
+  sequence.append_bits(&BitSequence::try_with_bits(&p2.x.into_be_bytes(), 256).unwrap());
+  sequence.append_bits(&BitSequence::try_with_bits(&p2.y.into_be_bytes(), 256).unwrap());
+
+  let t = key_derivation_function(&sequence, key_length);
+
+  if t.get_bytes().iter().all(|b| *b == 0) {
+    return Err(Error::KdfAllZero);
+  }
+
+  let result = c2.xor(&t).unwrap();
+
+  let mut to_hash_sequence = BitSequence::new_empty();
+  to_hash_sequence.append_bits(&BitSequence::try_with_bits(&p2.x.into_be_bytes(), 256).unwrap());
+  to_hash_sequence.append_bits(aad);
+  to_hash_sequence.append_bits(&result);
+  to_hash_sequence.append_bits(&BitSequence::try_with_bits(&p2.y.into_be_bytes(), 256).unwrap());
+
+  let u = hash(&to_hash_sequence);
+
+  if !check_mac(&u, &c3) {
+    return Err(Error::DigestMismatch);
+  }
+
+  Ok(result)
+}
+
+/// # SM2 字节接口默认用户ID
+///
+/// 字节接口签名与验证时使用的用户ID，即 GM/T 0009 规定的默认值
+pub const DEFAULT_ID: &[u8] = b"1234567812345678";
+
+/// # SM2 字节接口私钥解析函数
+///
+/// ## 参数
+///
+/// * `private_key` - 32 字节私钥，大端序
+///
+/// ## 返回
+///
+/// 如果私钥在 [1, n - 2] 范围内，返回私钥及对应公钥
+///
+/// 否则返回错误
+fn key_pair_from_bytes(private_key: &[u8; 32]) -> Result<KeyPair<'static>, Error> {
+  let d = U256::from_be_bytes(private_key);
+
+  if d.eq_u64(0) || d >= SM2_PARAMS.n_minus_1() {
+    return Err(Error::InvalidPrivateKey);
+  }
+
+  Ok(KeyPair { private_key: d, public_key: mul_generator(d, &SM2_PARAMS) })
+}
+
+/// # SM2 字节接口公钥解析函数
+///
+/// ## 参数
+///
+/// * `public_key` - 编码后的公钥，格式为 0x04 || x || y，坐标为大端序
+///
+/// ## 返回
+///
+/// 如果公钥有效，返回公钥
+///
+/// 否则返回错误
+fn public_key_from_bytes(public_key: &[u8]) -> Result<EccPoint<'static>, Error> {
+  let point = EccPoint::try_from_bytes(public_key, &SM2_PARAMS)?;
+
+  if !pubkey_validate(&point) {
+    return Err(Error::InvalidPublicKey);
+  }
+
+  Ok(point)
+}
+
+/// # SM2 字节接口公钥计算函数
+///
+/// 由私钥计算 SM2 推荐曲线上的公钥
+///
+/// ## 参数
+///
+/// * `private_key` - 32 字节大端序私钥
+///
+/// ## 返回
+///
+/// 如果私钥有效，返回编码后的公钥 0x04 || x || y，坐标为大端序
+///
+/// 否则返回错误
+pub fn public_key_bytes(private_key: &[u8; 32]) -> Result<Vec<u8>, Error> {
+  key_pair_from_bytes(private_key).map(|key_pair| key_pair.public_key.into())
+}
+
+/// # SM2 字节接口签名函数
+///
+/// 使用 SM2 推荐曲线和默认用户ID `DEFAULT_ID`，对字节消息进行签名
+///
+/// ## 参数
+///
+/// * `private_key` - 32 字节大端序私钥
+/// * `message` - 消息
+///
+/// ## 返回
+///
+/// 如果签名成功，返回 64 字节的签名 r || s
+///
+/// 如果私钥无效，返回错误
+#[cfg(feature = "std")]
+pub fn sign_bytes(private_key: &[u8; 32], message: &[u8]) -> Result<Vec<u8>, Error> {
+  let key_pair = key_pair_from_bytes(private_key)?;
+  let input =
+    SigningInput::with_id_bytes(&SM2_PARAMS, DEFAULT_ID, key_pair.public_key, key_pair.private_key);
+
+  let (r, s) = generate_signature(&input, &BitSequence::with_bytes(message))?;
+
+  Ok([r, s].concat())
+}
+
+/// # SM2 字节接口签名验证函数
+///
+/// 使用 SM2 推荐曲线和默认用户ID `DEFAULT_ID`，验证字节消息的签名
+///
+/// ## 参数
+///
+/// * `public_key` - 编码后的公钥，格式为 0x04 || x || y，坐标为大端序
+/// * `message` - 消息
+/// * `signature` - 64 字节的签名 r || s
+///
+/// ## 返回
+///
+/// 返回一个布尔值，表示签名是否有效；公钥或签名格式无效时返回 false
+pub fn verify_bytes(public_key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+  let Ok(public_key) = public_key_from_bytes(public_key) else {
+    return false;
+  };
+
+  let Ok(signature) = <&[u8; 64]>::try_from(signature) else {
+    return false;
+  };
+
+  let input = SigningVerificationInput::with_id_bytes(&SM2_PARAMS, DEFAULT_ID, public_key);
+  let r: [u8; 32] = signature[.. 32].try_into().unwrap();
+  let s: [u8; 32] = signature[32 ..].try_into().unwrap();
 
-  // 验证参数 Sb = H(0x02 || u.y || Internal)
-  let mut to_hash_sequence_1 = BitSequence::new_empty();
-  to_hash_sequence_1.append_bytes(&[0x02]);
-  to_hash_sequence_1.append_bytes(&u.y.into_le_bytes());
-  to_hash_sequence_1.append_bytes(&hash(&to_hash_sequence_internal));
-  let validator_1 = hash(&to_hash_sequence_1);
+  verify_signature(&input, &BitSequence::with_bytes(message), (r, s))
+}
 
-  // 如果验证参数不匹配，则验证失败
-  if validator_1 != respond.validator {
-    return Err("Invalid validator");
-  }
+/// # SM2 字节接口加密函数
+///
+/// 使用 SM2 推荐曲线加密字节消息
+///
+/// ## 参数
+///
+/// * `public_key` - 编码后的公钥，格式为 0x04 || x || y，坐标为大端序
+/// * `message` - 消息
+///
+/// ## 返回
+///
+/// 如果加密成功，返回密文 C1 || C2 || C3
+///
+/// 如果加密失败，返回错误
+#[cfg(feature = "std")]
+pub fn encrypt_bytes(public_key: &[u8], message: &[u8]) -> Result<Vec<u8>, Error> {
+  let public_key = public_key_from_bytes(public_key)?;
 
-  // 验证参数 Sa = H(0x03 || u.y || Internal)
-  let mut to_hash_sequence_2 = BitSequence::new_empty();
-  to_hash_sequence_2.append_bytes(&[0x03]);
-  to_hash_sequence_2.append_bytes(&u.y.into_le_bytes());
-  to_hash_sequence_2.append_bytes(&hash(&to_hash_sequence_internal));
-  let validator_2 = hash(&to_hash_sequence_2);
+  encrypt(&SM2_PARAMS, &BitSequence::with_bytes(message), &public_key).map(Vec::from)
+}
 
-  Ok(ExchangeKeyConfirmOutput { key, payload: validator_2 })
+/// # SM2 字节接口解密函数
+///
+/// 使用 SM2 推荐曲线解密 `encrypt_bytes` 生成的密文
+///
+/// ## 参数
+///
+/// * `private_key` - 32 字节大端序私钥
+/// * `cipher_text` - 密文 C1 || C2 || C3
+///
+/// ## 返回
+///
+/// 如果解密成功，返回明文
+///
+/// 如果解密失败，返回错误
+pub fn decrypt_bytes(private_key: &[u8; 32], cipher_text: &[u8]) -> Result<Vec<u8>, Error> {
+  let key_pair = key_pair_from_bytes(private_key)?;
+
+  decrypt(&SM2_PARAMS, &BitSequence::with_bytes(cipher_text), key_pair.private_key).map(Vec::from)
 }
 
-/// # SM2 密钥交换验证函数
+/// # SM2 DER 私钥解析函数
 ///
-/// 验证密钥交换
+/// 解析 PKCS#8（`PrivateKeyInfo`）或 SEC1（`ECPrivateKey`）格式的 DER
+/// 编码私钥， 要求曲线为 SM2 推荐曲线（OID 1.2.156.10197.1.301）
 ///
 /// ## 参数
 ///
-/// * `input` - 密钥交换输入结构体
-/// * `state` - 状态结构体
-/// * `respond` - 对方发送的验证参数
+/// * `der` - DER 编码的私钥
 ///
 /// ## 返回
 ///
-/// 如果验证成功，返回 true
-pub fn exchange_key_validate<'a>(
-  input: &'a ExchangeKeyInput,
-  state: &'a ExchangeKeyStateReceiver<'a>,
-  respond: &[u8; 32]
-) -> bool {
-  // 中间结果 Internal = v.x || Za || Zb || state.r_point_other.x ||
-  // state.r_point_other.y || state.r_point.x || state.r_point.y
-  let mut to_hash_sequence_internal = BitSequence::new_empty();
-  to_hash_sequence_internal.append_bytes(&state.v.x.into_le_bytes());
-  to_hash_sequence_internal
-    .append_bytes(&get_z(input.params, &input.id, &input.public_key).into_le_bytes());
-  to_hash_sequence_internal
-    .append_bytes(&get_z(input.params, &input.other_id, &input.other_public_key).into_le_bytes());
-  to_hash_sequence_internal.append_bytes(&state.r_point_other.x.into_le_bytes());
-  to_hash_sequence_internal.append_bytes(&state.r_point_other.y.into_le_bytes());
-  to_hash_sequence_internal.append_bytes(&state.r_point.x.into_le_bytes());
-  to_hash_sequence_internal.append_bytes(&state.r_point.y.into_le_bytes());
+/// 如果解析成功，返回密钥对；私钥中附带公钥时，校验其与私钥一致
+///
+/// 如果格式错误、曲线不是 SM2 推荐曲线或私钥无效，返回错误
+pub fn parse_sm2_private_key_der(der: &[u8]) -> Result<KeyPair<'static>, Error> {
+  let mut outer = DerReader::new(der);
+  let mut fields = DerReader::new(outer.expect(TAG_SEQUENCE)?);
 
-  // 验证参数 Sa = H(0x03 || v.y || Internal)
-  let mut to_hash_sequence = BitSequence::new_empty();
-  to_hash_sequence.append_bytes(&[0x03]);
-  to_hash_sequence.append_bytes(&state.v.y.into_le_bytes());
-  to_hash_sequence.append_bytes(&hash(&to_hash_sequence_internal));
+  let version = fields.expect(TAG_INTEGER)?;
 
-  // 如果验证参数不匹配，则验证失败
-  hash(&to_hash_sequence) == *respond
+  match version {
+    // PKCS#8：version 0, AlgorithmIdentifier, OCTET STRING { ECPrivateKey }
+    [0x00] => {
+      expect_sm2_algorithm(fields.expect(TAG_SEQUENCE)?)?;
+      parse_ec_private_key(fields.expect(TAG_OCTET_STRING)?)
+    },
+    // SEC1：直接为 ECPrivateKey
+    [0x01] => parse_ec_private_key(der),
+    _ => Err(Error::UnsupportedEncoding)
+  }
 }
 
-/// # SM2 加密函数
+/// # SM2 DER 公钥解析函数
 ///
-/// 加密消息
+/// 解析 `SubjectPublicKeyInfo` 格式的 DER 编码公钥，要求曲线为 SM2 推荐曲线
 ///
 /// ## 参数
 ///
-/// * `params` - 椭圆曲线参数
-/// * `message` - 消息
-/// * `public_key` - 公钥
+/// * `der` - DER 编码的公钥
 ///
 /// ## 返回
 ///
-/// 如果加密成功，返回密文
+/// 如果解析成功且公钥有效，返回公钥
 ///
-/// 如果加密失败，返回错误
-pub fn encrypt(
-  params: &EccParams,
-  message: &BitSequence,
-  public_key: &EccPoint
-) -> Result<BitSequence, &'static str> {
-  let g = EccPoint::new(params.g_x, params.g_y, params, false);
+/// 否则返回错误
+pub fn parse_sm2_public_key_der(der: &[u8]) -> Result<EccPoint<'static>, Error> {
+  let mut outer = DerReader::new(der);
+  let mut fields = DerReader::new(outer.expect(TAG_SEQUENCE)?);
 
-  let (c1, intermediate, t) = loop {
-    let k = U256::random_in_range(&mut rand::rng(), U256::C_1, params.n);
+  expect_sm2_algorithm(fields.expect(TAG_SEQUENCE)?)?;
+  parse_sec1_point(fields.expect(TAG_BIT_STRING)?)
+}
 
-    let c1 = g.ecc_mul(k, params);
+/// # 校验算法标识为 SM2 推荐曲线上的椭圆曲线公钥
+///
+/// ## 参数
+///
+/// * `algorithm` - `AlgorithmIdentifier` 的内容
+///
+/// ## 返回
+///
+/// 如果算法为 id-ecPublicKey 且参数为 SM2 曲线 OID，返回 `Ok(())`
+fn expect_sm2_algorithm(algorithm: &[u8]) -> Result<(), Error> {
+  let mut fields = DerReader::new(algorithm);
 
-    if c1.infinity {
-      return Err("Invalid c1");
-    }
+  if fields.expect(TAG_OID)? != OID_EC_PUBLIC_KEY {
+    return Err(Error::UnsupportedAlgorithm);
+  }
 
-    if public_key.ecc_mul(params.n, params).infinity {
-      return Err("Invalid s");
-    }
+  if fields.expect(TAG_OID)? != OID_SM2 {
+    return Err(Error::UnsupportedCurve);
+  }
 
-    let intermediate = public_key.ecc_mul(k, params);
+  Ok(())
+}
 
-    let mut sequence = BitSequence::new_empty();
-    sequence.append_bits(&BitSequence::from(c1));
-    sequence.append_bits(&BitSequence::from(intermediate));
+/// # 解析 SEC1 `ECPrivateKey`
+///
+/// ## 参数
+///
+/// * `der` - DER 编码的 `ECPrivateKey`
+///
+/// ## 返回
+///
+/// 如果解析成功，返回密钥对
+fn parse_ec_private_key(der: &[u8]) -> Result<KeyPair<'static>, Error> {
+  let mut outer = DerReader::new(der);
+  let mut fields = DerReader::new(outer.expect(TAG_SEQUENCE)?);
 
-    let t = key_derivation_function(&sequence, message.len());
+  if fields.expect(TAG_INTEGER)? != [0x01] {
+    return Err(Error::UnsupportedEncoding);
+  }
 
-    if t.get_bytes().iter().find(|b| **b != 0).is_none() {
-      continue;
-    }
+  // 私钥为大端序整数，编码器可能省略前导 0
+  let scalar = fields.expect(TAG_OCTET_STRING)?;
 
-    break (c1, intermediate, t);
-  };
+  if scalar.len() > 32 {
+    return Err(Error::InvalidPrivateKey);
+  }
 
-  // c2 = M ^ t
-  let c2 = message.xor(&t).unwrap();
+  let d =
+    U256::from_be_iter(core::iter::repeat_n(0, 32 - scalar.len()).chain(scalar.iter().copied()))?;
 
-  // c3 = H(x2 || M || y2)
-  let mut sequence = BitSequence::new_empty();
-  sequence.append_bits(&BitSequence::try_with_bits(&intermediate.x.into_le_bytes(), 256).unwrap());
-  sequence.append_bits(&message);
-  sequence.append_bits(&BitSequence::try_with_bits(&intermediate.y.into_le_bytes(), 256).unwrap());
+  if d.eq_u64(0) || d >= SM2_PARAMS.n_minus_1() {
+    return Err(Error::InvalidPrivateKey);
+  }
 
-  let c3 = BitSequence::try_with_bits(&hash(&sequence), 256).unwrap();
+  let public_key = mul_generator(d, &SM2_PARAMS);
 
-  let mut result = BitSequence::new_empty();
-  result.append_bits(&BitSequence::from(c1));
-  result.append_bits(&c2);
-  result.append_bits(&c3);
+  if fields.peek_tag() == Some(TAG_CONTEXT_0) {
+    let mut parameters = DerReader::new(fields.expect(TAG_CONTEXT_0)?);
 
-  Ok(result)
+    if parameters.expect(TAG_OID)? != OID_SM2 {
+      return Err(Error::UnsupportedCurve);
+    }
+  }
+
+  if fields.peek_tag() == Some(TAG_CONTEXT_1) {
+    let mut embedded = DerReader::new(fields.expect(TAG_CONTEXT_1)?);
+    let embedded = parse_sec1_point(embedded.expect(TAG_BIT_STRING)?)?;
+
+    if embedded != public_key {
+      return Err(Error::PublicKeyMismatch);
+    }
+  }
+
+  if !fields.is_empty() {
+    return Err(Error::InvalidDer);
+  }
+
+  Ok(KeyPair { private_key: d, public_key })
 }
 
-/// # SM2 解密函数
-///
-/// 解密消息
+/// # 解析 BIT STRING 中的未压缩 SEC1 点
 ///
 /// ## 参数
 ///
-/// * `params` - 椭圆曲线参数
-/// * `cipher_text` - 密文
-/// * `private_key` - 私钥
+/// * `bit_string` - BIT STRING 的内容，首字节为未使用的比特数
 ///
 /// ## 返回
 ///
-/// 如果解密成功，返回明文
-///
-/// 如果解密失败，返回错误
-pub fn decrypt(
-  params: &EccParams,
-  cipher_text: &BitSequence,
-  private_key: U256
-) -> Result<BitSequence, &'static str> {
-  let key_length = cipher_text.len() - 65 - 256;
-  let c1 = EccPoint::from_bytes(&cipher_text.get_bytes()[0 .. 65].try_into().unwrap(), params);
+/// 如果格式正确且点为有效公钥，返回该点
+fn parse_sec1_point(bit_string: &[u8]) -> Result<EccPoint<'static>, Error> {
+  // 0x00（未使用的比特数）|| 0x04 || x || y，x 和 y 为大端序
+  let [0x00, 0x04, coordinates @ ..] = bit_string else {
+    return Err(Error::UnsupportedEncoding);
+  };
 
-  if !c1.validate_on_curve() {
-    return Err("Invalid c1");
+  if coordinates.len() != 64 {
+    return Err(Error::InvalidLength);
   }
 
-  if c1.ecc_mul(params.n, params).infinity {
-    return Err("Invalid s");
+  let x = U256::from_be_bytes(coordinates[.. 32].try_into().unwrap());
+  let y = U256::from_be_bytes(coordinates[32 ..].try_into().unwrap());
+  let point = EccPoint::new_simple(x, y, &SM2_PARAMS);
+
+  if !pubkey_validate(&point) {
+    return Err(Error::InvalidPublicKey);
   }
 
-  let p2 = c1.ecc_mul(private_key, params);
+  Ok(point)
+}
 
-  let mut sequence = BitSequence::new_empty();
-  sequence.append_bits(&BitSequence::try_with_bits(&p2.x.into_le_bytes(), 256).unwrap());
-  sequence.append_bits(&BitSequence::try_with_bits(&p2.y.into_le_bytes(), 256).unwrap());
+#[cfg(test)]
+mod tests {
+  use {super::*, rand::SeedableRng, rand_chacha::ChaCha20Rng};
+
+  /// # GB/T 32918 附录中使用的 Fp-256 测试曲线参数
+  static TEST_PARAMS: EccParams = EccParams::new(
+    U256::from_be_u64_array(&[
+      0x787968b4fa32c3fd,
+      0x2417842e73bbfeff,
+      0x2f3c848b6831d7e0,
+      0xec65228b3937e498
+    ]),
+    U256::from_be_u64_array(&[
+      0x63e4c6d3b23b0c84,
+      0x9cf84241484bfe48,
+      0xf61d59a5b16ba06e,
+      0x6e12d1da27c5249a
+    ]),
+    U256::from_be_u64_array(&[
+      0x8542d69e4c044f18,
+      0xe8b92435bf6ff7de,
+      0x457283915c45517d,
+      0x722edb8b08f1dfc3
+    ]),
+    U256::from_be_u64_array(&[
+      0x8542d69e4c044f18,
+      0xe8b92435bf6ff7dd,
+      0x297720630485628d,
+      0x5ae74ee7c32e79b7
+    ]),
+    U256::from_be_u64_array(&[
+      0x421debd61b62eab6,
+      0x746434ebc3cc315e,
+      0x32220b3badd50bdc,
+      0x4c4e6c147fedd43d
+    ]),
+    U256::from_be_u64_array(&[
+      0x0680512bcbb42c07,
+      0xd47349d2153b70c4,
+      0xe5d7fdfcbfa36ea1,
+      0xa85841b9e46e09a2
+    ])
+  );
 
-  let t = key_derivation_function(&sequence, key_length);
+  fn hex(s: &str) -> Vec<u8> {
+    (0 .. s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i .. i + 2], 16).unwrap()).collect()
+  }
 
-  if t.get_bytes().iter().all(|b| *b == 0) {
-    return Err("Invalid t");
+  fn u256(s: &str) -> U256 {
+    U256::from_hex(s).unwrap()
+  }
+
+  #[test]
+  fn encrypt_with_k_matches_standard_example() {
+    let private = u256("1649ab77a00637bd5e2efe283fbf353534aa7f7cb89463f208ddbc2920bb0da0");
+    let public_key = public_key_from_private(private, &TEST_PARAMS).unwrap();
+    let k = u256("4c62eefd6ecfc2b95b92fd6c3d9575148afa17425546d49018e5388d49dd7b4f");
+    let message = BitSequence::with_bytes(b"encryption standard");
+
+    let cipher_text = encrypt_with_k(&TEST_PARAMS, &message, &public_key, k).unwrap();
+
+    let expected = hex(concat!(
+      "04",
+      "245c26fb68b1ddddb12c4b6bf9f2b6d5fe60a383b0d18d1c4144abf17f6252e7",
+      "76cb9264c2a7e88e52b19903fdc47378f605e36811f5c07423a24b84400f01b8",
+      "650053a89b41c418b0c3aad00d886c00286467",
+      "9c3d7360c30156fab7c80a0276712da9d8094a634b766d3a285e07480653426d"
+    ));
+    assert_eq!(cipher_text.get_bytes(), expected.as_slice());
+
+    let decrypted = decrypt(&TEST_PARAMS, &cipher_text, private).unwrap();
+    assert_eq!(decrypted.get_bytes(), message.get_bytes());
   }
 
-  let c2 = cipher_text.slice(65, cipher_text.len() - 256).unwrap();
+  /// # OpenSSL 生成的 SM2 私钥，PKCS#8 格式
+  const PKCS8_KEY: &str = concat!(
+    "308187020100301306072a8648ce3d020106082a811ccf5501822d046d306b0201010420517f8e3aeccd21dae488",
+    "906bd39d2e2f70403c89ff11b4110a671c61cd7acc8fa1440342000422e0f5b33f5fdb76295b6603471727f963c0",
+    "a7341ec1f844c150047f036f8d4b8d7dbeec17c5554f63eb629f3faedc6ad2c26fcb55317760197c4cd6ca96d6d7"
+  );
 
-  let result = c2.xor(&t).unwrap();
+  /// # 同一私钥的 SEC1 格式
+  const SEC1_KEY: &str = concat!(
+    "30770201010420517f8e3aeccd21dae488906bd39d2e2f70403c89ff11b4110a671c61cd7acc8fa00a06082a811c",
+    "cf5501822da1440342000422e0f5b33f5fdb76295b6603471727f963c0a7341ec1f844c150047f036f8d4b8d7dbe",
+    "ec17c5554f63eb629f3faedc6ad2c26fcb55317760197c4cd6ca96d6d7"
+  );
 
-  let mut to_hash_sequence = BitSequence::new_empty();
-  to_hash_sequence.append_bits(&BitSequence::try_with_bits(&p2.x.into_le_bytes(), 256).unwrap());
-  to_hash_sequence.append_bits(&result);
-  to_hash_sequence.append_bits(&BitSequence::try_with_bits(&p2.y.into_le_bytes(), 256).unwrap());
+  /// # 对应的公钥，SubjectPublicKeyInfo 格式
+  const PUBLIC_KEY: &str = concat!(
+    "3059301306072a8648ce3d020106082a811ccf5501822d0342000422e0f5b33f5fdb76295b6603471727f963c0a7",
+    "341ec1f844c150047f036f8d4b8d7dbeec17c5554f63eb629f3faedc6ad2c26fcb55317760197c4cd6ca96d6d7"
+  );
+
+  #[test]
+  fn parse_openssl_keys() {
+    let pkcs8 = parse_sm2_private_key_der(&hex(PKCS8_KEY)).unwrap();
+    let sec1 = parse_sm2_private_key_der(&hex(SEC1_KEY)).unwrap();
+    let public_key = parse_sm2_public_key_der(&hex(PUBLIC_KEY)).unwrap();
+
+    assert_eq!(
+      pkcs8.private_key,
+      u256("517f8e3aeccd21dae488906bd39d2e2f70403c89ff11b4110a671c61cd7acc8f")
+    );
+    assert_eq!(sec1.private_key, pkcs8.private_key);
+    assert!(pkcs8.public_key == public_key);
+    assert!(sec1.public_key == public_key);
+
+    let input =
+      SigningInput::with_id_bytes(&SM2_PARAMS, DEFAULT_ID, pkcs8.public_key, pkcs8.private_key);
+    let message = BitSequence::with_bytes(b"message digest");
+    let signature =
+      generate_signature_with_k(&input, &message, U256::from(0x1234_5678u64)).unwrap();
+    let verification = SigningVerificationInput::with_id_bytes(&SM2_PARAMS, DEFAULT_ID, public_key);
+
+    assert!(verify_signature(&verification, &message, signature));
+  }
 
-  let u = BitSequence::try_with_bits(&hash(&to_hash_sequence), 256).unwrap();
+  #[test]
+  fn parse_keys_rejects_other_curves() {
+    let mut der = hex(PKCS8_KEY);
+    // 将 SM2 曲线 OID 的最后一个字节改为其他值
+    der[26] ^= 0x01;
+    assert!(matches!(parse_sm2_private_key_der(&der), Err(Error::UnsupportedCurve)));
+
+    let mut der = hex(PUBLIC_KEY);
+    der[22] ^= 0x01;
+    assert!(matches!(parse_sm2_public_key_der(&der), Err(Error::UnsupportedCurve)));
+
+    let mut der = hex(SEC1_KEY);
+    // 修改附带的公钥坐标，使其不在曲线上
+    let last = der.len() - 1;
+    der[last] ^= 0x01;
+    assert!(parse_sm2_private_key_der(&der).is_err());
+  }
 
-  if u != cipher_text.slice(cipher_text.len() - 256, cipher_text.len()).unwrap() {
-    return Err("Invalid u");
+  #[test]
+  fn sign_with_k_matches_standard_example() {
+    let private = u256("128b2fa8bd433c6c068c8d803dff79792a519a55171b1b650c23661d15897263");
+    let public_key = public_key_from_private(private, &TEST_PARAMS).unwrap();
+    let input =
+      SigningInput::with_id_bytes(&TEST_PARAMS, b"ALICE123@YAHOO.COM", public_key, private);
+    let message = BitSequence::with_bytes(b"message digest");
+    let k = u256("6cb28d99385c175c94f94e934817663fc176d925dd72b727260dbaae1fb2f96f");
+
+    let (r, s) = generate_signature_with_k(&input, &message, k).unwrap();
+
+    assert_eq!(
+      U256::from_be_bytes(&r),
+      u256("40f1ec59f793d9f49e09dcef49130d4194f79fb1eed2caa55bacdb49c4e755d1")
+    );
+    assert_eq!(
+      U256::from_be_bytes(&s),
+      u256("6fc6dac32c5d5cf10c77dfb20f7c2eb667a457872fb09ec56327a67ec7deebe7")
+    );
+
+    let verification =
+      SigningVerificationInput::with_id_bytes(&TEST_PARAMS, b"ALICE123@YAHOO.COM", public_key);
+    assert!(verify_signature(&verification, &message, (r, s)));
   }
 
-  Ok(result)
+  #[test]
+  fn sign_with_k_matches_sm2_curve_example() {
+    let private = u256("3945208f7b2144b13f36e38ac6d39f95889393692860b51a42fb81ef4df7c5b8");
+    let public_key = public_key_from_private(private, &SM2_PARAMS).unwrap();
+    let input = SigningInput::with_id_bytes(&SM2_PARAMS, DEFAULT_ID, public_key, private);
+    let message = BitSequence::with_bytes(b"message digest");
+    let k = u256("59276e27d506861a16680f3ad9c02dccef3cc1fa3cdbe4ce6d54b80deac1bc21");
+
+    let (r, s) = generate_signature_with_k(&input, &message, k).unwrap();
+
+    assert_eq!(
+      U256::from_be_bytes(&r),
+      u256("f5a03b0648d2c4630eeac513e1bb81a15944da3827d5b74143ac7eaceee720b3")
+    );
+    assert_eq!(
+      U256::from_be_bytes(&s),
+      u256("b1b6aa29df212fd8763182bc0d421ca1bb9038fd1f7f42d4840b69c485bbc1aa")
+    );
+  }
+
+  #[test]
+  fn sign_with_k_rejects_out_of_range_nonce() {
+    let private = u256("128b2fa8bd433c6c068c8d803dff79792a519a55171b1b650c23661d15897263");
+    let public_key = public_key_from_private(private, &TEST_PARAMS).unwrap();
+    let input = SigningInput::with_id_bytes(&TEST_PARAMS, DEFAULT_ID, public_key, private);
+    let message = BitSequence::with_bytes(b"message digest");
+
+    for k in [U256::C_0, TEST_PARAMS.n, U256::MAX] {
+      assert!(matches!(generate_signature_with_k(&input, &message, k), Err(Error::InvalidNonce)));
+    }
+  }
+
+  #[test]
+  fn decrypt_round_trips_messages_that_are_not_whole_bytes() {
+    let private = u256("1649ab77a00637bd5e2efe283fbf353534aa7f7cb89463f208ddbc2920bb0da0");
+    let public_key = public_key_from_private(private, &SM2_PARAMS).unwrap();
+    let k = u256("4c62eefd6ecfc2b95b92fd6c3d9575148afa17425546d49018e5388d49dd7b4f");
+    let message = BitSequence::try_with_bits(&[0xa5, 0xf8], 13).unwrap();
+    let aad = BitSequence::new_empty();
+
+    for format in [CipherFormat::C1C2C3, CipherFormat::C1C3C2] {
+      let cipher_text = encrypt_with_k_and_aad(
+        &SM2_PARAMS,
+        &message,
+        &aad,
+        &public_key,
+        k,
+        ByteOrder::BigEndian,
+        format
+      )
+      .unwrap();
+      assert_eq!(cipher_text.len(), 65 * 8 + 13 + 256);
+
+      let decrypted =
+        decrypt_inner(&SM2_PARAMS, &cipher_text, &aad, private, ByteOrder::BigEndian, format)
+          .unwrap();
+      assert_eq!(decrypted.len(), 13);
+      assert_eq!(decrypted.get_bytes(), message.get_bytes());
+    }
+  }
+
+  /// # OpenSSL 使用 `PKCS8_KEY` 和 `DEFAULT_ID` 对 "message digest" 生成的 DER
+  /// 签名
+  const OPENSSL_SIGNATURE: &str = concat!(
+    "304502207d166dae37e38e78a5cb7bc94aaf994e141c80531fbc2cb4a47b83441b0f91b8022100cbe3ed019238b2",
+    "a4e1c503de433058e0cce31539902fae46af1003a0d7a2cbe8"
+  );
+
+  #[test]
+  fn signature_der_round_trips() {
+    let mut high = [0u8; 32];
+    high[0] = 0x80;
+    let mut short = [0u8; 32];
+    short[31] = 0x01;
+
+    for sig in [(high, short), (short, high), ([0xff; 32], [0u8; 32])] {
+      assert_eq!(signature_from_der(&signature_to_der(sig)), Ok(sig));
+    }
+
+    // 最高位为 1 时补 0x00，前导 0 被去除
+    let der = signature_to_der((high, short));
+    assert_eq!(&der[.. 5], &[0x30, 0x26, 0x02, 0x21, 0x00]);
+    assert_eq!(&der[der.len() - 3 ..], &[0x02, 0x01, 0x01]);
+  }
+
+  #[test]
+  fn signature_der_rejects_malformed_input() {
+    let der = hex(OPENSSL_SIGNATURE);
+
+    // 多余的数据
+    let mut trailing = der.clone();
+    trailing.push(0x00);
+    assert_eq!(signature_from_der(&trailing), Err(Error::InvalidDer));
+
+    // 负数
+    let mut negative = der.clone();
+    negative[4] |= 0x80;
+    assert_eq!(signature_from_der(&negative), Err(Error::InvalidDer));
+
+    // 截断
+    assert!(signature_from_der(&der[.. der.len() - 1]).is_err());
+    // 非最短编码的前导 0
+    assert_eq!(
+      signature_from_der(&[0x30, 0x07, 0x02, 0x02, 0x00, 0x01, 0x02, 0x01, 0x01]),
+      Err(Error::InvalidDer)
+    );
+  }
+
+  #[test]
+  fn verify_openssl_signature() {
+    let public_key = parse_sm2_public_key_der(&hex(PUBLIC_KEY)).unwrap();
+    let input = SigningVerificationInput::with_id_bytes(&SM2_PARAMS, DEFAULT_ID, public_key);
+    let signature = signature_from_der(&hex(OPENSSL_SIGNATURE)).unwrap();
+
+    assert!(verify_signature(&input, &BitSequence::with_bytes(b"message digest"), signature));
+    assert!(!verify_signature(&input, &BitSequence::with_bytes(b"message digesT"), signature));
+  }
+
+  #[test]
+  #[cfg(feature = "std")]
+  fn key_gen_returns_key_in_range() {
+    let key_pair = key_gen(&SM2_PARAMS).unwrap();
+
+    assert!(!key_pair.private_key.eq_u64(0));
+    assert!(key_pair.private_key < SM2_PARAMS.n_minus_1());
+    assert!(key_pair.public_key == SM2_G.ecc_mul(key_pair.private_key, &SM2_PARAMS));
+    assert!(pubkey_validate(&key_pair.public_key));
+  }
+
+  #[test]
+  #[cfg(feature = "std")]
+  fn key_gen_rejects_tiny_order() {
+    let params = EccParams::new(SM2_A, SM2_B, SM2_P, U256::C_2, SM2_GX, SM2_GY);
+
+    assert!(matches!(key_gen(&params), Err(Error::UnsupportedCurve)));
+  }
+
+  #[test]
+  #[cfg(feature = "std")]
+  fn key_pair_getters() {
+    let key_pair = key_gen(&SM2_PARAMS).unwrap();
+    let public_key: &EccPoint = key_pair.public_key();
+
+    assert!(*public_key == key_pair.public_key);
+    assert_eq!(key_pair.private_key(), key_pair.private_key);
+    assert_eq!(U256::from_be_bytes(&key_pair.private_key_bytes()), key_pair.private_key);
+
+    let rebuilt = KeyPair::from_parts(key_pair.private_key(), *key_pair.public_key());
+    assert!(rebuilt.public_key() == key_pair.public_key());
+    assert_eq!(rebuilt.private_key(), key_pair.private_key());
+  }
+
+  /// # OpenSSL 使用 `PUBLIC_KEY` 加密 "encryption standard" 得到的 DER 密文
+  const OPENSSL_CIPHER_TEXT: &str = concat!(
+    "307d022100a3a54398095c08b0b7e99d4f65ebcfebc3889f8b14535775262e252de8218758022100e5e17c248973",
+    "356bbbe58311fbe1f1e71d7271bb315a9caf954a36817c6b77a80420713a63932e7cd80e423ddf30a10e207da493",
+    "faf5551aad7cd5c1a86fcf947c6e0413c6a78675c3746c26f30ada70d2f226db6850aa"
+  );
+
+  #[test]
+  fn decrypt_openssl_cipher_text() {
+    let key_pair = parse_sm2_private_key_der(&hex(PKCS8_KEY)).unwrap();
+    let der = hex(OPENSSL_CIPHER_TEXT);
+    let cipher_text = ciphertext_from_der(&der).unwrap();
+
+    let plain_text = decrypt(&SM2_PARAMS, &cipher_text, key_pair.private_key()).unwrap();
+    assert_eq!(plain_text.get_bytes(), b"encryption standard");
+    assert_eq!(ciphertext_to_der(&cipher_text).unwrap(), der);
+  }
+
+  #[test]
+  fn cipher_text_der_round_trips() {
+    let public_key = parse_sm2_public_key_der(&hex(PUBLIC_KEY)).unwrap();
+    let k = u256("4c62eefd6ecfc2b95b92fd6c3d9575148afa17425546d49018e5388d49dd7b4f");
+    let message = BitSequence::with_bytes(b"round trip");
+
+    let cipher_text = encrypt_with_k(&SM2_PARAMS, &message, &public_key, k).unwrap();
+    let der = ciphertext_to_der(&cipher_text).unwrap();
+    assert!(ciphertext_from_der(&der).unwrap() == cipher_text);
+
+    let unaligned = BitSequence::try_with_bits(&[0xa5, 0xf8], 13).unwrap();
+    let cipher_text = encrypt_with_k(&SM2_PARAMS, &unaligned, &public_key, k).unwrap();
+    assert_eq!(ciphertext_to_der(&cipher_text), Err(Error::InvalidCipherText));
+  }
+
+  #[test]
+  fn cipher_text_der_rejects_malformed_input() {
+    let der = hex(OPENSSL_CIPHER_TEXT);
+
+    let mut trailing = der.clone();
+    trailing.push(0x00);
+    assert_eq!(ciphertext_from_der(&trailing).err(), Some(Error::InvalidDer));
+
+    // C3 只有 31 字节
+    let mut short_hash = der[.. 72].to_vec();
+    short_hash.extend([0x04, 0x1f]);
+    short_hash.extend(&der[74 .. 105]);
+    short_hash.extend(&der[106 ..]);
+    short_hash[1] -= 1;
+    assert_eq!(ciphertext_from_der(&short_hash).err(), Some(Error::InvalidDer));
+  }
+
+  #[test]
+  #[cfg(feature = "std")]
+  fn precomputed_signer_signs_many_messages() {
+    let key_pair = parse_sm2_private_key_der(&hex(PKCS8_KEY)).unwrap();
+    let input = SigningInput::with_id_bytes(
+      &SM2_PARAMS,
+      DEFAULT_ID,
+      key_pair.public_key,
+      key_pair.private_key
+    );
+    let verification =
+      SigningVerificationInput::with_id_bytes(&SM2_PARAMS, DEFAULT_ID, key_pair.public_key);
+    let signer = PrecomputedSigner::new(&input);
+
+    assert_eq!(signer.z(), get_z(&SM2_PARAMS, &input.id, &input.public_key));
+
+    for i in 0 .. 10u8 {
+      let message = BitSequence::with_bytes(&[i; 16]);
+      let signature = signer.sign(&message).unwrap();
+
+      assert!(verify_signature(&verification, &message, signature));
+      assert!(!verify_signature(&verification, &BitSequence::with_bytes(&[i; 15]), signature));
+    }
+  }
+
+  #[test]
+  fn generator_table_matches_ecc_mul() {
+    let table = GeneratorTable::new();
+    let edge_cases = [U256::C_0, U256::C_1, U256::C_2, SM2_N - U256::C_1, SM2_N, U256::MAX];
+    let random =
+      (0 .. 16u64).map(|i| U256::from_be_bytes(&crate::sm_3::hash_bytes(&i.to_be_bytes())));
+
+    for k in edge_cases.into_iter().chain(random) {
+      assert!(table.mul_base(k) == SM2_G.ecc_mul(k, &SM2_PARAMS));
+    }
+
+    assert!(table.mul_base(SM2_N).infinity);
+  }
+
+  #[test]
+  fn key_gen_with_rng_is_reproducible() {
+    let first = key_gen_with_rng(&SM2_PARAMS, &mut ChaCha20Rng::seed_from_u64(1)).unwrap();
+    let second = key_gen_with_rng(&SM2_PARAMS, &mut ChaCha20Rng::seed_from_u64(1)).unwrap();
+    let other = key_gen_with_rng(&SM2_PARAMS, &mut ChaCha20Rng::seed_from_u64(2)).unwrap();
+
+    assert_eq!(first.private_key, second.private_key);
+    assert!(first.public_key == second.public_key);
+    assert_ne!(first.private_key, other.private_key);
+    assert!(first.public_key == SM2_G.ecc_mul(first.private_key, &SM2_PARAMS));
+  }
+
+  #[test]
+  fn signature_and_encryption_with_rng_are_reproducible() {
+    let key_pair = key_gen_with_rng(&SM2_PARAMS, &mut ChaCha20Rng::seed_from_u64(3)).unwrap();
+    let input = SigningInput::with_id_bytes(
+      &SM2_PARAMS,
+      DEFAULT_ID,
+      key_pair.public_key,
+      key_pair.private_key
+    );
+    let verification =
+      SigningVerificationInput::with_id_bytes(&SM2_PARAMS, DEFAULT_ID, key_pair.public_key);
+    let message = BitSequence::with_bytes(b"reproducible");
+
+    let sign = |seed| {
+      generate_signature_with_rng(&input, &message, &mut ChaCha20Rng::seed_from_u64(seed)).unwrap()
+    };
+    let signature = sign(4);
+
+    assert_eq!(signature, sign(4));
+    assert_ne!(signature, sign(5));
+    assert!(verify_signature(&verification, &message, signature));
+
+    let encrypt = |seed| {
+      encrypt_with_rng(
+        &SM2_PARAMS,
+        &message,
+        &key_pair.public_key,
+        &mut ChaCha20Rng::seed_from_u64(seed)
+      )
+      .unwrap()
+    };
+    let cipher_text = encrypt(6);
+
+    assert_eq!(cipher_text.get_bytes(), encrypt(6).get_bytes());
+    assert_ne!(cipher_text.get_bytes(), encrypt(7).get_bytes());
+    assert!(decrypt(&SM2_PARAMS, &cipher_text, key_pair.private_key).unwrap() == message);
+  }
+
+  #[test]
+  fn key_exchange_with_rng_is_reproducible() {
+    let mut rng = ChaCha20Rng::seed_from_u64(8);
+    let alice = key_gen_with_rng(&SM2_PARAMS, &mut rng).unwrap();
+    let bob = key_gen_with_rng(&SM2_PARAMS, &mut rng).unwrap();
+    let (alice_id, bob_id) = (BitSequence::with_bytes(b"ALICE"), BitSequence::with_bytes(b"BOB"));
+    let alice_input = ExchangeKeyInput {
+      params: &SM2_PARAMS,
+      id: alice_id.clone(),
+      public_key: alice.public_key,
+      other_id: bob_id.clone(),
+      other_public_key: bob.public_key
+    };
+    let bob_input = ExchangeKeyInput {
+      params: &SM2_PARAMS,
+      id: bob_id,
+      public_key: bob.public_key,
+      other_id: alice_id,
+      other_public_key: alice.public_key
+    };
+
+    let exchange = |seed| {
+      let mut rng = ChaCha20Rng::seed_from_u64(seed);
+      let (r_point, _) = exchange_key_initiate_with_rng(&alice_input, &mut rng);
+      let (generated, _) =
+        exchange_key_generate_with_rng(&bob_input, &r_point, bob.private_key, 128, &mut rng)
+          .unwrap();
+
+      (
+        r_point.to_compressed_bytes(),
+        generated.payload.received.to_compressed_bytes(),
+        generated.payload.validator,
+        generated.key.get_bytes().to_vec()
+      )
+    };
+
+    let first = exchange(9);
+    let other = exchange(10);
+
+    assert_eq!(first, exchange(9));
+    assert_ne!(first.0, other.0);
+    assert_ne!(first.1, other.1);
+  }
+
+  #[test]
+  fn signing_with_private_key_n_minus_1_fails() {
+    let d = SM2_N - U256::C_1;
+    let input =
+      SigningInput::with_id_bytes(&SM2_PARAMS, DEFAULT_ID, SM2_G.ecc_mul(d, &SM2_PARAMS), d);
+    let message = BitSequence::with_bytes(b"message digest");
+    let mut rng = ChaCha20Rng::seed_from_u64(11);
+
+    assert_eq!(
+      generate_signature_with_k(&input, &message, U256::C_1).err(),
+      Some(Error::InvalidPrivateKey)
+    );
+    assert_eq!(
+      generate_signature_with_rng(&input, &message, &mut rng).err(),
+      Some(Error::InvalidPrivateKey)
+    );
+    assert_eq!(
+      generate_signature_deterministic(&input, &message).err(),
+      Some(Error::InvalidPrivateKey)
+    );
+    assert_eq!(
+      PrecomputedSigner::new(&input).sign_with_rng(&message, &mut rng).err(),
+      Some(Error::InvalidPrivateKey)
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "std")]
+  fn little_endian_round_trips() {
+    let key_pair = parse_sm2_private_key_der(&hex(PKCS8_KEY)).unwrap();
+    let signing_input = SigningInput::with_id_bytes(
+      &SM2_PARAMS,
+      DEFAULT_ID,
+      key_pair.public_key,
+      key_pair.private_key
+    );
+    let verification_input =
+      SigningVerificationInput::with_id_bytes(&SM2_PARAMS, DEFAULT_ID, key_pair.public_key);
+    let message = BitSequence::with_bytes(b"little endian");
+    let order = ByteOrder::LittleEndian;
+
+    let (r, s) = generate_signature_with_order(&signing_input, &message, order).unwrap();
+    assert!(verify_signature_with_order(&verification_input, &message, (r, s), order));
+
+    // 小端序签名逐字节反转后即为大端序签名
+    let (mut r_be, mut s_be) = (r, s);
+    r_be.reverse();
+    s_be.reverse();
+    assert!(verify_signature(&verification_input, &message, (r_be, s_be)));
+    assert!(!verify_signature(&verification_input, &message, (r, s)));
+
+    let cipher_text =
+      encrypt_with_order(&SM2_PARAMS, &message, &key_pair.public_key, order).unwrap();
+    let plain_text = decrypt_with_order(&SM2_PARAMS, &cipher_text, key_pair.private_key, order);
+    assert!(plain_text.unwrap() == message);
+
+    // C1 的坐标以小端序编码
+    let c1 =
+      EccPoint::try_from_bytes_with_order(&cipher_text.get_bytes()[.. 65], &SM2_PARAMS, order);
+    assert!(c1.unwrap().validate_on_curve());
+  }
+
+  #[test]
+  fn verify_batch_reports_each_signature() {
+    let key_pair = parse_sm2_private_key_der(&hex(PKCS8_KEY)).unwrap();
+    let signing_input = SigningInput::with_id_bytes(
+      &SM2_PARAMS,
+      DEFAULT_ID,
+      key_pair.public_key,
+      key_pair.private_key
+    );
+    let verification_input =
+      SigningVerificationInput::with_id_bytes(&SM2_PARAMS, DEFAULT_ID, key_pair.public_key);
+
+    let messages: Vec<_> = (0 .. 4u8).map(|i| BitSequence::with_bytes(&[i; 8])).collect();
+    let mut signatures: Vec<_> = messages
+      .iter()
+      .map(|message| generate_signature_deterministic(&signing_input, message).unwrap())
+      .collect();
+
+    // 篡改第 1 个签名的 s，第 3 个签名改用其他消息的签名
+    signatures[1].1[31] ^= 1;
+    signatures[3] = signatures[0];
+
+    let batch: Vec<_> = messages.iter().zip(&signatures).map(|(m, s)| (m, *s)).collect();
+    assert_eq!(verify_batch(&verification_input, &batch), [true, false, true, false]);
+
+    for ((message, signature), valid) in batch.iter().zip([true, false, true, false]) {
+      assert_eq!(verify_signature(&verification_input, message, *signature), valid);
+    }
+
+    assert!(verify_batch(&verification_input, &[]).is_empty());
+  }
+
+  #[test]
+  fn deterministic_signature_is_repeatable() {
+    let key_pair = parse_sm2_private_key_der(&hex(PKCS8_KEY)).unwrap();
+    let signing_input = SigningInput::with_id_bytes(
+      &SM2_PARAMS,
+      DEFAULT_ID,
+      key_pair.public_key,
+      key_pair.private_key
+    );
+    let verification_input =
+      SigningVerificationInput::with_id_bytes(&SM2_PARAMS, DEFAULT_ID, key_pair.public_key);
+    let first = BitSequence::with_bytes(b"message digest");
+    let second = BitSequence::with_bytes(b"message digesT");
+
+    let signature = generate_signature_deterministic(&signing_input, &first).unwrap();
+    assert_eq!(generate_signature_deterministic(&signing_input, &first).unwrap(), signature);
+    assert!(verify_signature(&verification_input, &first, signature));
+
+    let other = generate_signature_deterministic(&signing_input, &second).unwrap();
+    assert!(verify_signature(&verification_input, &second, other));
+    assert_ne!(other.0, signature.0);
+
+    // r = e + x1 mod n，x1 不同说明派生出的 k 不同
+    let x1 = |message: &BitSequence, r: &[u8; 32]| {
+      let e = compute_e(&SM2_PARAMS, &signing_input.id, &key_pair.public_key, message);
+      U256::from_be_bytes(r).mod_sub(e, SM2_PARAMS.n)
+    };
+    assert_ne!(x1(&first, &signature.0), x1(&second, &other.0));
+  }
+
+  #[test]
+  #[cfg(feature = "std")]
+  fn cipher_formats_round_trip() {
+    let key_pair = parse_sm2_private_key_der(&hex(PKCS8_KEY)).unwrap();
+    let message = BitSequence::with_bytes(b"cipher format");
+
+    for format in [CipherFormat::C1C2C3, CipherFormat::C1C3C2] {
+      let cipher_text =
+        encrypt_with_format(&SM2_PARAMS, &message, &key_pair.public_key, format).unwrap();
+      let plain_text = decrypt_with_format(&SM2_PARAMS, &cipher_text, key_pair.private_key, format);
+
+      assert_eq!(cipher_text.get_bytes().len(), 65 + 32 + 13);
+      assert!(plain_text.unwrap() == message);
+    }
+
+    let cipher_text =
+      encrypt_with_format(&SM2_PARAMS, &message, &key_pair.public_key, CipherFormat::C1C3C2)
+        .unwrap();
+    assert!(decrypt_with_format(
+      &SM2_PARAMS,
+      &cipher_text,
+      key_pair.private_key,
+      CipherFormat::C1C2C3
+    )
+    .is_err());
+  }
+
+  /// # GmSSL 格式（C1 || C3 || C2）的 `OPENSSL_CIPHER_TEXT`
+  ///
+  /// 由 DER 密文中的 x、y、C3、C2 字段依次拼接得到，与 GmSSL 的原始密文布局一致
+  const C1C3C2_CIPHER_TEXT: &str = concat!(
+    "04a3a54398095c08b0b7e99d4f65ebcfebc3889f8b14535775262e252de8218758e5e17c248973356bbbe58311fb",
+    "e1f1e71d7271bb315a9caf954a36817c6b77a8713a63932e7cd80e423ddf30a10e207da493faf5551aad7cd5c1a8",
+    "6fcf947c6ec6a78675c3746c26f30ada70d2f226db6850aa"
+  );
+
+  #[test]
+  fn decrypt_c1c3c2_cipher_text() {
+    let key_pair = parse_sm2_private_key_der(&hex(PKCS8_KEY)).unwrap();
+    let cipher_text = BitSequence::with_bytes(&hex(C1C3C2_CIPHER_TEXT));
+
+    let plain_text =
+      decrypt_with_format(&SM2_PARAMS, &cipher_text, key_pair.private_key, CipherFormat::C1C3C2);
+    assert_eq!(plain_text.unwrap().get_bytes(), b"encryption standard");
+
+    let wrong_format =
+      decrypt_with_format(&SM2_PARAMS, &cipher_text, key_pair.private_key, CipherFormat::C1C2C3);
+    assert!(wrong_format.is_err());
+  }
+
+  #[test]
+  #[cfg(feature = "std")]
+  fn aad_binds_the_cipher_text() {
+    let key_pair = parse_sm2_private_key_der(&hex(PKCS8_KEY)).unwrap();
+    let message = BitSequence::with_bytes(b"associated data");
+    let aad = BitSequence::with_bytes(b"header v1");
+    let wrong_aad = BitSequence::with_bytes(b"header v2");
+
+    let cipher_text = encrypt_with_aad(&SM2_PARAMS, &message, &aad, &key_pair.public_key).unwrap();
+
+    let plain_text = decrypt_with_aad(&SM2_PARAMS, &cipher_text, &aad, key_pair.private_key);
+    assert!(plain_text.unwrap() == message);
+
+    for other in [wrong_aad, BitSequence::new_empty()] {
+      let result = decrypt_with_aad(&SM2_PARAMS, &cipher_text, &other, key_pair.private_key);
+      assert_eq!(result.err(), Some(Error::DigestMismatch));
+    }
+    assert_eq!(
+      decrypt(&SM2_PARAMS, &cipher_text, key_pair.private_key).err(),
+      Some(Error::DigestMismatch)
+    );
+  }
+
+  #[test]
+  fn empty_aad_matches_decrypt() {
+    let key_pair = parse_sm2_private_key_der(&hex(PKCS8_KEY)).unwrap();
+    let cipher_text = ciphertext_from_der(&hex(OPENSSL_CIPHER_TEXT)).unwrap();
+    let aad = BitSequence::new_empty();
+
+    let plain_text = decrypt_with_aad(&SM2_PARAMS, &cipher_text, &aad, key_pair.private_key);
+    assert_eq!(plain_text.unwrap().get_bytes(), b"encryption standard");
+  }
+
+  #[test]
+  fn decrypt_reports_specific_errors() {
+    let key_pair = parse_sm2_private_key_der(&hex(PKCS8_KEY)).unwrap();
+    let bytes = ciphertext_from_der(&hex(OPENSSL_CIPHER_TEXT)).unwrap().get_bytes().to_vec();
+    let decrypt_error = |bytes: &[u8]| {
+      decrypt(&SM2_PARAMS, &BitSequence::with_bytes(bytes), key_pair.private_key).err()
+    };
+
+    assert_eq!(decrypt_error(&bytes), None);
+
+    // 篡改 C3
+    let mut tampered = bytes.clone();
+    *tampered.last_mut().unwrap() ^= 1;
+    assert_eq!(decrypt_error(&tampered), Some(Error::DigestMismatch));
+
+    // 篡改 C2
+    let mut tampered = bytes.clone();
+    tampered[65] ^= 1;
+    assert_eq!(decrypt_error(&tampered), Some(Error::DigestMismatch));
+
+    // C1 的 y 坐标被修改后不在曲线上
+    let mut tampered = bytes.clone();
+    tampered[64] ^= 1;
+    assert_eq!(decrypt_error(&tampered), Some(Error::NotOnCurve));
+
+    let mut tampered = bytes.clone();
+    tampered[0] = 0x02;
+    assert_eq!(decrypt_error(&tampered), Some(Error::InvalidPoint));
+
+    // 截断
+    assert_eq!(decrypt_error(&bytes[.. 65 + 32 - 1]), Some(Error::InvalidCipherText));
+    assert_eq!(decrypt_error(&bytes[.. 64]), Some(Error::InvalidCipherText));
+    assert_eq!(decrypt_error(&[]), Some(Error::InvalidCipherText));
+    assert_eq!(decrypt_error(&bytes[.. bytes.len() - 1]), Some(Error::DigestMismatch));
+  }
+
+  #[test]
+  fn with_id_bytes_matches_bit_sequence_id() {
+    let key_pair = parse_sm2_private_key_der(&hex(PKCS8_KEY)).unwrap();
+    let (public_key, private_key) = (key_pair.public_key, key_pair.private_key);
+    let id = "ALICE123@YAHOO.COM";
+    let message = BitSequence::with_bytes(b"message digest");
+    let k = u256("59276e27d506861a16680f3ad9c02dccef3cc1fa3cdbe4ce6d54b80deac1bc21");
+
+    let from_bytes =
+      SigningInput::with_id_bytes(&SM2_PARAMS, id.as_bytes(), public_key, private_key);
+    let from_bits = SigningInput::new(
+      &SM2_PARAMS,
+      BitSequence::with_bytes(id.as_bytes()),
+      public_key,
+      private_key
+    );
+    let signature = generate_signature_with_k(&from_bytes, &message, k).unwrap();
+
+    assert_eq!(generate_signature_with_k(&from_bits, &message, k).unwrap(), signature);
+
+    let verification_input =
+      SigningVerificationInput::with_id_bytes(&SM2_PARAMS, id.as_bytes(), public_key);
+    assert!(verify_signature(&verification_input, &message, signature));
+
+    // 使用其他 ID 时 Z 不同，签名无效
+    let other_id = SigningVerificationInput::with_id_bytes(&SM2_PARAMS, DEFAULT_ID, public_key);
+    assert!(!verify_signature(&other_id, &message, signature));
+  }
+
+  #[test]
+  fn check_mac_compares_length_and_content() {
+    let expected = crate::sm_3::hash_bytes(b"mac");
+
+    assert!(check_mac(&expected, &BitSequence::with_bytes(&expected)));
+
+    let mut tampered = expected;
+    tampered[0] ^= 0x80;
+    assert!(!check_mac(&expected, &BitSequence::with_bytes(&tampered)));
+    tampered = expected;
+    tampered[31] ^= 1;
+    assert!(!check_mac(&expected, &BitSequence::with_bytes(&tampered)));
+
+    // 长度不一致时返回 false 而不崩溃
+    assert!(!check_mac(&expected, &BitSequence::with_bytes(&expected[.. 31])));
+    assert!(!check_mac(&expected, &BitSequence::with_bytes(&[&expected[..], &[0]].concat())));
+    assert!(!check_mac(&expected, &BitSequence::new_empty()));
+    assert!(!check_mac(&[], &BitSequence::with_bytes(&expected)));
+    assert!(!check_mac(&expected, &BitSequence::try_with_bits(&expected, 255).unwrap()));
+  }
+
+  #[test]
+  #[cfg(feature = "std")]
+  fn byte_apis_round_trip() {
+    let private_key = parse_sm2_private_key_der(&hex(PKCS8_KEY)).unwrap().private_key_bytes();
+    let public_key = public_key_bytes(&private_key).unwrap();
+    assert_eq!(public_key.len(), 65);
+
+    let signature = sign_bytes(&private_key, b"message").unwrap();
+    assert_eq!(signature.len(), 64);
+    assert!(verify_bytes(&public_key, b"message", &signature));
+    assert!(!verify_bytes(&public_key, b"messagE", &signature));
+    assert!(!verify_bytes(&public_key, b"message", &signature[.. 63]));
+    assert!(!verify_bytes(&public_key[.. 64], b"message", &signature));
+
+    let cipher_text = encrypt_bytes(&public_key, b"message").unwrap();
+    assert_eq!(decrypt_bytes(&private_key, &cipher_text).unwrap(), b"message");
+    assert!(encrypt_bytes(&[0; 65], b"message").is_err());
+  }
+
+  #[test]
+  fn byte_apis_reject_out_of_range_private_keys() {
+    let n_minus_1 = SM2_PARAMS.n_minus_1();
+    let cipher_text = ciphertext_from_der(&hex(OPENSSL_CIPHER_TEXT)).unwrap();
+
+    for d in [U256::C_0, n_minus_1, SM2_PARAMS.n, U256::MAX] {
+      let private_key = d.into_be_bytes();
+
+      assert_eq!(public_key_bytes(&private_key).err(), Some(Error::InvalidPrivateKey));
+      assert_eq!(
+        decrypt_bytes(&private_key, cipher_text.get_bytes()).err(),
+        Some(Error::InvalidPrivateKey)
+      );
+      #[cfg(feature = "std")]
+      assert_eq!(sign_bytes(&private_key, b"message").err(), Some(Error::InvalidPrivateKey));
+    }
+
+    // n - 2 为最大的有效私钥
+    let private_key = (n_minus_1 - U256::C_1).into_be_bytes();
+    assert!(public_key_bytes(&private_key).is_ok());
+  }
+
+  #[test]
+  fn ct_eq_bytes_checks_every_byte() {
+    let a: Vec<u8> = (0 .. 32).collect();
+
+    assert!(ct_eq_bytes(&a, &a));
+    assert!(ct_eq_bytes(&[], &[]));
+
+    for i in 0 .. a.len() {
+      let mut b = a.clone();
+      b[i] ^= 0x40;
+
+      assert!(!ct_eq_bytes(&a, &b));
+    }
+
+    assert!(!ct_eq_bytes(&a, &a[.. 31]));
+    assert!(!ct_eq_bytes(&a[.. 1], &a));
+  }
+
+  #[test]
+  #[cfg(feature = "zeroize")]
+  fn zeroize_clears_private_keys() {
+    use zeroize::Zeroize;
+
+    let mut key_pair = parse_sm2_private_key_der(&hex(PKCS8_KEY)).unwrap();
+    let public_key = key_pair.public_key;
+    assert!(!key_pair.private_key.eq_u64(0));
+    key_pair.zeroize();
+    assert_eq!(key_pair.private_key.into_le_u64_array(), [0; 4]);
+
+    let mut input = SigningInput::with_id_bytes(&SM2_PARAMS, DEFAULT_ID, public_key, U256::MAX);
+    input.zeroize();
+    assert_eq!(input.private_key.into_le_u64_array(), [0; 4]);
+  }
 }
@@ -1,4 +1,7 @@
-use crate::math::bytes::BitSequence;
+use {
+  crate::math::bytes::BitSequence,
+  alloc::{vec, vec::Vec}
+};
 
 /// # SM3 哈希函数
 ///
@@ -21,10 +24,15 @@ pub fn hash(input: &BitSequence) -> [u8; 32] {
     let mut new_input = input.clone();
 
     // 加入一个1位
-    new_input.append_bits(&BitSequence::try_with_bits(&[1], 1).unwrap());
+    new_input.append_bits(&BitSequence::try_with_bits(&[0x80], 1).unwrap());
 
-    // 填充0，留出8个字节
-    new_input.append_bytes(&vec![0; padded_size as usize / 8 - input.get_bytes().len() - 8]);
+    // 填充0，直到长度模 512 余 448
+    let zero_bits = padded_size - 64 - new_input.len();
+    if zero_bits > 0 {
+      new_input.append_bits(
+        &BitSequence::try_with_bits(&vec![0; zero_bits.div_ceil(8) as usize], zero_bits).unwrap()
+      );
+    }
 
     // 加入输入长度
     new_input.append_bytes(&input.len().to_be_bytes());
@@ -35,16 +43,7 @@ pub fn hash(input: &BitSequence) -> [u8; 32] {
   // 2. 迭代过程
 
   // 初始化V值
-  let mut result_array_u32: [u32; 8] = [
-    0x7380166f,
-    0x4914b2b9,
-    0x172442d7,
-    0xda8a0600,
-    0xa96f30bc,
-    0x163138aa,
-    0xe38dee4d,
-    0xb0fb0e4e
-  ];
+  let mut result_array_u32 = IV;
 
   // 分组，对每个组调用压缩函数，最终结果保存到 `result_array_u32`
   for i in 0 .. ((padded_size as usize) / 512) {
@@ -66,6 +65,218 @@ pub fn hash(input: &BitSequence) -> [u8; 32] {
   result_array_u8
 }
 
+/// # SM3 哈希函数，输入字节序列
+///
+/// ## 参数
+///
+/// * `input` - 输入消息，为字节序列
+///
+/// ## 返回值
+///
+/// 返回一个 256 位（32 字节）的二进制数组，表示哈希结果
+///
+/// ## 注意事项
+///
+/// * 通过流式的 `Sm3` 计算，无需复制整个消息
+pub fn hash_bytes(input: &[u8]) -> [u8; 32] {
+  let mut hasher = Sm3::new();
+  hasher.update(input);
+
+  hasher.finalize()
+}
+
+/// # SM3 哈希函数，返回比特序列
+///
+/// ## 参数
+///
+/// * `input` - 输入消息，为比特序列
+///
+/// ## 返回值
+///
+/// * `BitSequence` - 256 位的哈希结果，便于与其他比特序列拼接
+pub fn hash_to_bits(input: &BitSequence) -> BitSequence {
+  BitSequence::with_bytes(&hash(input))
+}
+
+/// # SM3 流式哈希结构体
+///
+/// 按字节分段输入消息，适用于消息较长或分段到达的场景
+///
+/// ## 成员
+///
+/// * `state` - 当前的迭代值 V
+/// * `buffer` - 尚未凑满一个分组的消息字节
+/// * `total_len` - 已输入消息的总字节数
+///
+/// ## 构造方法
+///
+/// * `Sm3::new() -> Self` - 以初始值 IV 创建
+///
+/// ## 实现特征
+///
+/// * `Clone`
+/// * `Default`
+///
+/// ## 方法
+///
+/// * `update(&mut self, data: &[u8])` - 输入消息
+/// * `finalize(self) -> [u8; 32]` - 填充并输出哈希结果
+/// * `reset(&mut self)` - 恢复到初始状态
+///
+/// ## 注意事项
+///
+/// * 仅支持字节对齐的消息，任意比特长度的消息请使用 `hash`
+#[derive(Clone)]
+pub struct Sm3 {
+  state: [u32; 8],
+  buffer: Vec<u8>,
+  total_len: u64
+}
+
+impl Sm3 {
+  pub fn new() -> Self {
+    Self { state: IV, buffer: Vec::with_capacity(64), total_len: 0 }
+  }
+
+  pub fn update(&mut self, data: &[u8]) {
+    self.total_len += data.len() as u64;
+
+    let mut data = data;
+
+    // 先补满缓冲区中不完整的分组
+    if !self.buffer.is_empty() {
+      let required = (64 - self.buffer.len()).min(data.len());
+
+      self.buffer.extend_from_slice(&data[.. required]);
+      data = &data[required ..];
+
+      if self.buffer.len() < 64 {
+        return;
+      }
+
+      let message_group: [u8; 64] = self.buffer[..].try_into().unwrap();
+      cf(&mut self.state, &message_group);
+      self.buffer.clear();
+    }
+
+    let mut chunks = data.chunks_exact(64);
+
+    for chunk in &mut chunks {
+      cf(&mut self.state, chunk.try_into().unwrap());
+    }
+
+    self.buffer.extend_from_slice(chunks.remainder());
+  }
+
+  pub fn finalize(mut self) -> [u8; 32] {
+    let bit_len = self.total_len * 8;
+
+    // 加入一个1位，填充0直到长度模 512 余 448，再加入消息长度
+    let mut padding = vec![0x80u8];
+    padding.resize((119 - self.buffer.len()) % 64 + 1, 0);
+    padding.extend_from_slice(&bit_len.to_be_bytes());
+
+    self.update(&padding);
+
+    let mut result_array_u8 = [0u8; 32];
+    for (i, word) in self.state.iter().enumerate() {
+      result_array_u8[i * 4 .. (i + 1) * 4].copy_from_slice(&word.to_be_bytes());
+    }
+
+    result_array_u8
+  }
+
+  pub fn reset(&mut self) {
+    *self = Self::new();
+  }
+}
+
+impl Default for Sm3 {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// # HMAC-SM3 函数
+///
+/// 使用 SM3 作为杂凑函数的 HMAC，分组长度为 64 字节
+///
+/// ## 参数
+///
+/// * `key` - 密钥，长于 64 字节时先进行杂凑
+/// * `message` - 输入消息
+///
+/// ## 返回值
+///
+/// 返回一个 256 位（32 字节）的二进制数组，表示消息认证码
+pub fn hmac(key: &[u8], message: &[u8]) -> [u8; 32] {
+  let mut mac = HmacSm3::new(key);
+  mac.update(message);
+
+  mac.finalize()
+}
+
+/// # HMAC-SM3 流式结构体
+///
+/// 按字节分段输入消息，计算 HMAC-SM3
+///
+/// ## 成员
+///
+/// * `inner` - 已输入 K ⊕ ipad 的内层杂凑
+/// * `outer` - 已输入 K ⊕ opad 的外层杂凑
+///
+/// ## 构造方法
+///
+/// * `HmacSm3::new(key: &[u8]) -> Self` - 以密钥创建，长于 64
+///   字节的密钥先进行杂凑
+///
+/// ## 实现特征
+///
+/// * `Clone`
+///
+/// ## 方法
+///
+/// * `update(&mut self, data: &[u8])` - 输入消息
+/// * `finalize(self) -> [u8; 32]` - 输出消息认证码
+#[derive(Clone)]
+pub struct HmacSm3 {
+  inner: Sm3,
+  outer: Sm3
+}
+
+impl HmacSm3 {
+  pub fn new(key: &[u8]) -> Self {
+    // 将密钥补齐为一个分组
+    let mut block = [0u8; 64];
+    if key.len() > 64 {
+      block[.. 32].copy_from_slice(&hash_bytes(key));
+    } else {
+      block[.. key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = Sm3::new();
+    inner.update(&block.map(|b| b ^ 0x36));
+
+    let mut outer = Sm3::new();
+    outer.update(&block.map(|b| b ^ 0x5c));
+
+    Self { inner, outer }
+  }
+
+  pub fn update(&mut self, data: &[u8]) {
+    self.inner.update(data);
+  }
+
+  pub fn finalize(mut self) -> [u8; 32] {
+    self.outer.update(&self.inner.finalize());
+    self.outer.finalize()
+  }
+}
+
+/// # SM3 初始值 IV
+static IV: [u32; 8] =
+  [0x7380166f, 0x4914b2b9, 0x172442d7, 0xda8a0600, 0xa96f30bc, 0x163138aa, 0xe38dee4d, 0xb0fb0e4e];
+
 /// # SM3 压缩函数
 ///
 /// ## 参数
@@ -102,6 +313,9 @@ fn cf(last_result: &mut [u32; 8], message_group: &[u8; 64]) {
   }
 
   // 2. 进行压缩
+
+  let v = *last_result;
+
   for j in 0 .. 64 {
     let ss_1 = last_result[0]
       .rotate_left(12)
@@ -119,7 +333,7 @@ fn cf(last_result: &mut [u32; 8], message_group: &[u8; 64]) {
     let tt_2 = gg_j(j, (last_result[4], last_result[5], last_result[6]))
       .wrapping_add(last_result[7])
       .wrapping_add(ss_1)
-      .wrapping_add(wj_s[j]);
+      .wrapping_add(wj[j]);
 
     last_result[3] = last_result[2];
 
@@ -137,6 +351,11 @@ fn cf(last_result: &mut [u32; 8], message_group: &[u8; 64]) {
 
     last_result[4] = p_0(tt_2);
   }
+
+  // 3. 与上一次压缩的结果异或
+  for (word, v_word) in last_result.iter_mut().zip(v) {
+    *word ^= v_word;
+  }
 }
 
 /// # SM3 置换函数 P0
@@ -223,3 +442,180 @@ fn gg_j(index: usize, (x, y, z): (u32, u32, u32)) -> u32 {
     (x & y) | (!x & z)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const ABC_DIGEST: [u8; 32] = [
+    0x66,
+    0xc7,
+    0xf0,
+    0xf4,
+    0x62,
+    0xee,
+    0xed,
+    0xd9,
+    0xd1,
+    0xf2,
+    0xd4,
+    0x6b,
+    0xdc,
+    0x10,
+    0xe4,
+    0xe2,
+    0x41,
+    0x67,
+    0xc4,
+    0x87,
+    0x5c,
+    0xf2,
+    0xf7,
+    0xa2,
+    0x29,
+    0x7d,
+    0xa0,
+    0x2b,
+    0x8f,
+    0x4b,
+    0xa8,
+    0xe0
+  ];
+
+  const ABCD_X16_DIGEST: [u8; 32] = [
+    0xde,
+    0xbe,
+    0x9f,
+    0xf9,
+    0x22,
+    0x75,
+    0xb8,
+    0xa1,
+    0x38,
+    0x60,
+    0x48,
+    0x89,
+    0xc1,
+    0x8e,
+    0x5a,
+    0x4d,
+    0x6f,
+    0xdb,
+    0x70,
+    0xe5,
+    0x38,
+    0x7e,
+    0x57,
+    0x65,
+    0x29,
+    0x3d,
+    0xcb,
+    0xa3,
+    0x9c,
+    0x0c,
+    0x57,
+    0x32
+  ];
+
+  #[test]
+  fn hash_matches_standard_examples() {
+    assert_eq!(hash(&BitSequence::with_bytes(b"abc")), ABC_DIGEST);
+    assert_eq!(hash_bytes(b"abc"), ABC_DIGEST);
+
+    let message = b"abcd".repeat(16);
+    assert_eq!(hash(&BitSequence::with_bytes(&message)), ABCD_X16_DIGEST);
+    assert_eq!(hash_bytes(&message), ABCD_X16_DIGEST);
+  }
+
+  #[test]
+  fn streaming_matches_one_shot() {
+    let message: Vec<u8> = (0 .. 200).map(|i| i as u8).collect();
+
+    for split in [0, 1, 55, 64, 65, 128, 200] {
+      let mut hasher = Sm3::new();
+      hasher.update(&message[.. split]);
+      hasher.update(&message[split ..]);
+
+      assert_eq!(hasher.finalize(), hash(&BitSequence::with_bytes(&message)));
+    }
+
+    let mut hasher = Sm3::new();
+    for byte in b"abc" {
+      hasher.update(&[*byte]);
+    }
+    assert_eq!(hasher.finalize(), ABC_DIGEST);
+  }
+
+  fn hex(s: &str) -> Vec<u8> {
+    (0 .. s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i .. i + 2], 16).unwrap()).collect()
+  }
+
+  /// 按 HMAC 定义直接计算，密钥须不长于 64 字节
+  fn reference_hmac(key: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut block = [0u8; 64];
+    block[.. key.len()].copy_from_slice(key);
+
+    let inner = [&block.map(|b| b ^ 0x36)[..], message].concat();
+    let outer = [&block.map(|b| b ^ 0x5c)[..], &hash_bytes(&inner)].concat();
+
+    hash_bytes(&outer)
+  }
+
+  #[test]
+  fn hmac_matches_known_answers() {
+    // RFC 4231 测试用例 1、2、6 的输入，以 SM3 为杂凑函数
+    assert_eq!(
+      hmac(&[0x0b; 20], b"Hi There").to_vec(),
+      hex("51b00d1fb49832bfb01c3ce27848e59f871d9ba938dc563b338ca964755cce70")
+    );
+    assert_eq!(
+      hmac(b"Jefe", b"what do ya want for nothing?").to_vec(),
+      hex("2e87f1d16862e6d964b50a5200bf2b10b764faa9680a296a2405f24bec39f882")
+    );
+    assert_eq!(
+      hmac(&[0xaa; 131], b"Test Using Larger Than Block-Size Key - Hash Key First").to_vec(),
+      hex("b4fd844e13342002f0b2e0690ea7741f1497d993a70494cea601e657bedf67a0")
+    );
+  }
+
+  #[test]
+  fn hmac_handles_key_lengths() {
+    let message = b"abc";
+
+    assert_eq!(hmac(&[], message), reference_hmac(&[], message));
+    assert_eq!(hmac(&[], message), hmac(&[0; 64], message));
+
+    let key: Vec<u8> = (0 .. 64).collect();
+    assert_eq!(hmac(&key, message), reference_hmac(&key, message));
+
+    // 长于 64 字节的密钥先进行杂凑
+    let key: Vec<u8> = (0 .. 100).collect();
+    assert_eq!(hmac(&key, message), reference_hmac(&hash_bytes(&key), message));
+    assert_ne!(hmac(&key, message), hmac(&key[.. 64], message));
+  }
+
+  #[test]
+  fn hmac_streaming_matches_one_shot() {
+    let key = b"streaming key";
+    let message: Vec<u8> = (0 .. 200).map(|i| i as u8).collect();
+
+    for split in [0, 1, 63, 64, 65, 200] {
+      let mut mac = HmacSm3::new(key);
+      mac.update(&message[.. split]);
+      mac.update(&message[split ..]);
+
+      assert_eq!(mac.finalize(), hmac(key, &message));
+    }
+  }
+
+  #[test]
+  fn hash_to_bits_matches_hash() {
+    for message in [&b""[..], b"abc", &[0x5a; 100]] {
+      let input = BitSequence::with_bytes(message);
+      let bits = hash_to_bits(&input);
+
+      assert_eq!(bits.len(), 256);
+      assert_eq!(bits.get_bytes(), hash(&input));
+    }
+  }
+}
@@ -1,10 +1,12 @@
+use {crate::Error, alloc::vec::Vec};
+
 /// # SM4 S盒
 ///
 /// 用于非线性变换的 16x16 字节替换表
 static SBOX: [[u8; 16]; 16] = [
   [0xd6, 0x90, 0xe9, 0xfe, 0xcc, 0xe1, 0x3d, 0xb7, 0x16, 0xb6, 0x14, 0xc2, 0x28, 0xfb, 0x2c, 0x05],
   [0x2b, 0x67, 0x9a, 0x76, 0x2a, 0xbe, 0x04, 0xc3, 0xaa, 0x44, 0x13, 0x26, 0x49, 0x86, 0x06, 0x99],
-  [0x9c, 0x42, 0x50, 0xf4, 0x91, 0xef, 0x98, 0x7a, 0x33, 0x64, 0x0b, 0x43, 0xed, 0xcf, 0xac, 0x62],
+  [0x9c, 0x42, 0x50, 0xf4, 0x91, 0xef, 0x98, 0x7a, 0x33, 0x54, 0x0b, 0x43, 0xed, 0xcf, 0xac, 0x62],
   [0xe4, 0xb3, 0x1c, 0xa9, 0xc9, 0x08, 0xe8, 0x95, 0x80, 0xdf, 0x94, 0xfa, 0x75, 0x8f, 0x3f, 0xa6],
   [0x47, 0x07, 0xa7, 0xfc, 0xf3, 0x73, 0x17, 0xba, 0x83, 0x59, 0x3c, 0x19, 0xe6, 0x85, 0x4f, 0xa8],
   [0x68, 0x6b, 0x81, 0xb2, 0x71, 0x64, 0xda, 0x8b, 0xf8, 0xeb, 0x0f, 0x4b, 0x70, 0x56, 0x9d, 0x35],
@@ -38,7 +40,7 @@ static CK: [u32; 32] = [
   0xa8afb6bd,
   0xc4cbd2d9,
   0xe0e7eef5,
-  0xdc030a11,
+  0xfc030a11,
   0x181f262d,
   0x343b4249,
   0x50575e65,
@@ -63,27 +65,6 @@ static CK: [u32; 32] = [
   0x646b7279
 ];
 
-/// # SM4 工作模式
-///
-/// 定义 SM4 算法的工作模式：加密或解密
-///
-/// ## 成员
-///
-/// * `Encrypt` - 加密模式
-/// * `Decrypt` - 解密模式
-///
-/// ## 实现特征
-///
-/// * `Clone`
-/// * `Copy`
-/// * `Eq`
-/// * `PartialEq`
-#[derive(Clone, Copy, Eq, PartialEq)]
-enum Mode {
-  Encrypt,
-  Decrypt
-}
-
 /// # SM4 加密函数
 ///
 /// 对16字节的明文数据进行加密，生成16字节的密文。
@@ -97,7 +78,7 @@ enum Mode {
 ///
 /// * `[u8; 16]` - 16 字节的密文数据
 pub fn encrypt(input: &[u8; 16], key: &[u8; 16]) -> [u8; 16] {
-  alter_group(input, key, Mode::Encrypt)
+  Sm4::new(key).encrypt_block(input)
 }
 
 /// # SM4 解密函数
@@ -117,21 +98,355 @@ pub fn encrypt(input: &[u8; 16], key: &[u8; 16]) -> [u8; 16] {
 ///
 /// * 解密过程与加密过程使用相同的算法，但轮密钥的使用顺序相反。
 pub fn decrypt(input: &[u8; 16], key: &[u8; 16]) -> [u8; 16] {
-  alter_group(input, key, Mode::Decrypt)
+  Sm4::new(key).decrypt_block(input)
+}
+
+/// # SM4 迭代加密函数
+///
+/// 使用同一密钥对数据反复加密 `rounds` 次，每次以上一次的密文作为输入。
+/// 密钥扩展只进行一次。
+///
+/// ## 参数
+///
+/// * `input` - 初始明文
+/// * `key` - 加密密钥
+/// * `rounds` - 加密次数
+///
+/// ## 返回值
+///
+/// * `[u8; 16]` - 16 字节的最终密文数据
+///
+/// ## 注意事项
+///
+/// * 标准附录中的示例以 1000000 次迭代加密作为已知答案测试
+pub fn encrypt_iterated(input: &[u8; 16], key: &[u8; 16], rounds: usize) -> [u8; 16] {
+  let key = Sm4::new(key);
+  let mut result = *input;
+
+  for _ in 0 .. rounds {
+    result = key.encrypt_block(&result);
+  }
+
+  result
+}
+
+/// # SM4-ECB 加密函数
+///
+/// 先进行 PKCS#7 填充，再逐个分组独立加密
+///
+/// ## 参数
+///
+/// * `data` - 待加密的明文，长度任意
+/// * `key` - 加密密钥
+///
+/// ## 返回值
+///
+/// * `Vec<u8>` - 密文，长度为 16 的倍数
+///
+/// ## 注意事项
+///
+/// * ECB 模式下相同的明文分组得到相同的密文分组，会泄露明文的结构
+pub fn encrypt_ecb(data: &[u8], key: &[u8; 16]) -> Vec<u8> {
+  let key = Sm4::new(key);
+
+  pad_pkcs7(data, 16)
+    .chunks_exact(16)
+    .flat_map(|block| key.encrypt_block_ct(block.try_into().unwrap()))
+    .collect()
+}
+
+/// # SM4-ECB 解密函数
+///
+/// 逐个分组独立解密，再去除 PKCS#7 填充
+///
+/// ## 参数
+///
+/// * `data` - 待解密的密文，长度须为 16 的倍数
+/// * `key` - 解密密钥
+///
+/// ## 返回值
+///
+/// * `Ok(Vec<u8>)` - 去除填充后的明文
+/// * `Err(Error)` - 密文长度不是 16 的正整数倍时为 `Error::InvalidLength`，
+///   填充无效时为 `Error::InvalidPadding`
+pub fn decrypt_ecb(data: &[u8], key: &[u8; 16]) -> Result<Vec<u8>, Error> {
+  if data.is_empty() || !data.len().is_multiple_of(16) {
+    return Err(Error::InvalidLength);
+  }
+
+  let key = Sm4::new(key);
+  let plain: Vec<u8> = data
+    .chunks_exact(16)
+    .flat_map(|block| key.decrypt_block_ct(block.try_into().unwrap()))
+    .collect();
+
+  unpad_pkcs7(&plain, 16)
+}
+
+/// # PKCS#7 填充函数
+///
+/// 填充 n 个值为 n 的字节，使长度成为分组长度的倍数；长度已是分组长度的倍数时，
+/// 填充一个完整的分组
+///
+/// ## 参数
+///
+/// * `data` - 待填充的数据
+/// * `block` - 分组长度，取值范围为 [1, 255]
+///
+/// ## 返回值
+///
+/// * `Vec<u8>` - 填充后的数据
+///
+/// ## 注意事项
+///
+/// * `block` 不在 [1, 255] 范围内时 panic
+pub fn pad_pkcs7(data: &[u8], block: usize) -> Vec<u8> {
+  assert!((1 ..= 255).contains(&block), "Invalid block size");
+
+  let pad = block - data.len() % block;
+
+  let mut result = Vec::with_capacity(data.len() + pad);
+  result.extend_from_slice(data);
+  result.resize(data.len() + pad, pad as u8);
+
+  result
+}
+
+/// # PKCS#7 去填充函数
+///
+/// ## 参数
+///
+/// * `data` - 已填充的数据
+/// * `block` - 分组长度，与填充时一致
+///
+/// ## 返回值
+///
+/// * `Ok(Vec<u8>)` - 去除填充后的数据
+/// * `Err(Error)` - 数据为空，填充长度为 0 或大于分组长度，或填充字节无效
+pub fn unpad_pkcs7(data: &[u8], block: usize) -> Result<Vec<u8>, Error> {
+  let pad = *data.last().ok_or(Error::InvalidPadding)? as usize;
+
+  if pad == 0 || pad > block || pad > data.len() {
+    return Err(Error::InvalidPadding);
+  }
+
+  let (content, padding) = data.split_at(data.len() - pad);
+
+  if padding.iter().any(|b| *b as usize != pad) {
+    return Err(Error::InvalidPadding);
+  }
+
+  Ok(content.to_vec())
+}
+
+/// # SM4-CBC 加密函数
+///
+/// 每个明文分组先与上一个密文分组（第一个分组为 IV）异或，再进行加密
+///
+/// ## 参数
+///
+/// * `data` - 待加密的明文，长度须为 16 的倍数
+/// * `key` - 加密密钥
+/// * `iv` - 初始向量
+///
+/// ## 返回值
+///
+/// * `Ok(Vec<u8>)` - 与明文等长的密文
+/// * `Err(Error)` - 明文长度不是 16 的倍数
+///
+/// ## 注意事项
+///
+/// * 不进行填充，任意长度的明文须由调用者先行填充
+pub fn encrypt_cbc(data: &[u8], key: &[u8; 16], iv: &[u8; 16]) -> Result<Vec<u8>, Error> {
+  if !data.len().is_multiple_of(16) {
+    return Err(Error::InvalidLength);
+  }
+
+  let key = Sm4::new(key);
+  let mut result = Vec::with_capacity(data.len());
+  let mut chain = *iv;
+
+  for block in data.chunks_exact(16) {
+    for (c, b) in chain.iter_mut().zip(block) {
+      *c ^= b;
+    }
+
+    chain = key.encrypt_block_ct(&chain);
+    result.extend_from_slice(&chain);
+  }
+
+  Ok(result)
+}
+
+/// # SM4-CBC 解密函数
+///
+/// 每个密文分组解密后与上一个密文分组（第一个分组为 IV）异或，得到明文分组
+///
+/// ## 参数
+///
+/// * `data` - 待解密的密文，长度须为 16 的倍数
+/// * `key` - 解密密钥
+/// * `iv` - 初始向量，须与加密时一致
+///
+/// ## 返回值
+///
+/// * `Ok(Vec<u8>)` - 与密文等长的明文
+/// * `Err(Error)` - 密文长度不是 16 的倍数
+pub fn decrypt_cbc(data: &[u8], key: &[u8; 16], iv: &[u8; 16]) -> Result<Vec<u8>, Error> {
+  if !data.len().is_multiple_of(16) {
+    return Err(Error::InvalidLength);
+  }
+
+  let key = Sm4::new(key);
+  let mut result = Vec::with_capacity(data.len());
+  let mut chain = *iv;
+
+  for block in data.chunks_exact(16) {
+    let block: &[u8; 16] = block.try_into().unwrap();
+    let plain = key.decrypt_block_ct(block);
+
+    result.extend(plain.iter().zip(chain).map(|(p, c)| p ^ c));
+    chain = *block;
+  }
+
+  Ok(result)
+}
+
+/// # SM4-CTR 加密函数
+///
+/// 以大端序的 128 位计数器为输入生成密钥流，与数据异或；每个分组后计数器加 1，
+/// 最后一个分组不足 16 字节时只使用密钥流的前若干字节
+///
+/// ## 参数
+///
+/// * `data` - 待加密的数据，长度任意
+/// * `key` - 加密密钥
+/// * `nonce` - 计数器初始值
+///
+/// ## 返回值
+///
+/// * `Vec<u8>` - 与输入等长的输出
+///
+/// ## 注意事项
+///
+/// * 加密与解密为同一运算，解密时以相同的密钥和 `nonce` 再次调用即可
+/// * 同一密钥下不得重复使用 `nonce`
+pub fn encrypt_ctr(data: &[u8], key: &[u8; 16], nonce: &[u8; 16]) -> Vec<u8> {
+  let key = Sm4::new(key);
+  let mut counter = u128::from_be_bytes(*nonce);
+  let mut result = Vec::with_capacity(data.len());
+
+  for block in data.chunks(16) {
+    let keystream = key.encrypt_block_ct(&counter.to_be_bytes());
+    result.extend(block.iter().zip(keystream).map(|(b, k)| b ^ k));
+
+    counter = counter.wrapping_add(1);
+  }
+
+  result
 }
 
-/// # SM4 通用变换函数
+/// # SM4 分组密码结构体
+///
+/// 缓存密钥扩展得到的加密与解密轮密钥，
+/// 对同一密钥处理多个分组时无需重复进行密钥扩展
+///
+/// ## 成员
+///
+/// * `enc_keys` - 32 个轮密钥，按加密顺序排列
+/// * `dec_keys` - 32 个轮密钥，按解密顺序（即加密的逆序）排列
+///
+/// ## 构造方法
+///
+/// * `Sm4::new(key)` - 由初始密钥创建，密钥扩展使用常数时间 S 盒
+///
+/// ## 实现特征
+///
+/// * `Clone`
+/// * `Zeroize`、`ZeroizeOnDrop` - 启用 `zeroize` 特性时，丢弃时清零轮密钥
+///
+/// ## 方法
+///
+/// * `encrypt_block(&self, input: &[u8; 16]) -> [u8; 16]` - 加密一个分组
+/// * `decrypt_block(&self, input: &[u8; 16]) -> [u8; 16]` - 解密一个分组
+/// * `encrypt_block_ct(&self, input: &[u8; 16]) -> [u8; 16]` -
+///   常数时间加密一个分组
+/// * `decrypt_block_ct(&self, input: &[u8; 16]) -> [u8; 16]` -
+///   常数时间解密一个分组
+///
+/// ## 注意事项
+///
+/// * `encrypt_block`/`decrypt_block` 使用查表 S 盒，访问地址与数据相关，
+///   可能泄露时间信息；对安全性敏感的场景请使用 `_ct` 版本
+#[derive(Clone)]
+pub struct Sm4 {
+  enc_keys: [u32; 32],
+  dec_keys: [u32; 32]
+}
+
+/// # SM4 密钥结构体
+///
+/// 即 `Sm4`，保留此名称以兼容旧代码
+pub type Sm4Key = Sm4;
+
+impl Sm4 {
+  pub fn new(key: &[u8; 16]) -> Self {
+    let enc_keys = expand_key(key, ita_ct);
+
+    let mut dec_keys = enc_keys;
+    dec_keys.reverse();
+
+    Self { enc_keys, dec_keys }
+  }
+
+  pub fn encrypt_block(&self, input: &[u8; 16]) -> [u8; 16] {
+    crypt_group(input, &self.enc_keys, ita)
+  }
+
+  pub fn decrypt_block(&self, input: &[u8; 16]) -> [u8; 16] {
+    crypt_group(input, &self.dec_keys, ita)
+  }
+
+  pub fn encrypt_block_ct(&self, input: &[u8; 16]) -> [u8; 16] {
+    crypt_group(input, &self.enc_keys, ita_ct)
+  }
+
+  pub fn decrypt_block_ct(&self, input: &[u8; 16]) -> [u8; 16] {
+    crypt_group(input, &self.dec_keys, ita_ct)
+  }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Sm4 {
+  fn zeroize(&mut self) {
+    self.enc_keys.zeroize();
+    self.dec_keys.zeroize();
+  }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Sm4 {
+  fn drop(&mut self) {
+    zeroize::Zeroize::zeroize(self);
+  }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for Sm4 {
+}
+
+/// # SM4 分组变换函数
 ///
 /// ## 参数
 ///
 /// * `input` - 输入数据
-/// * `key` - 密钥
-/// * `mode` - 工作模式
+/// * `round_keys` - 按使用顺序排列的 32 个轮密钥，解密时为加密轮密钥的逆序
+/// * `tau` - 非线性变换，`ita` 或常数时间的 `ita_ct`
 ///
 /// ## 返回值
 ///
 /// * `[u8; 16]` - 16 字节的输出数据
-fn alter_group(input: &[u8; 16], key: &[u8; 16], mode: Mode) -> [u8; 16] {
+fn crypt_group(input: &[u8; 16], round_keys: &[u32; 32], tau: fn(u32) -> u32) -> [u8; 16] {
   // 1. 转换输入数据
 
   let mut result_array_u32 = {
@@ -147,25 +462,17 @@ fn alter_group(input: &[u8; 16], key: &[u8; 16], mode: Mode) -> [u8; 16] {
     new_input
   };
 
-  // 2. 计算轮密钥，解密时使用反向密钥序
-
-  let round_keys = if mode == Mode::Encrypt {
-    expand_key(key)
-  } else {
-    expand_key(key).into_iter().rev().collect::<Vec<_>>().try_into().unwrap()
-  };
-
-  // 3. 使用轮密钥进行 32 轮迭代
+  // 2. 使用轮密钥进行 32 轮迭代
 
-  for i in 0 .. 32 {
-    round(&mut result_array_u32, round_keys[i]);
+  for round_key in round_keys {
+    round(&mut result_array_u32, *round_key, tau);
   }
 
-  // 4. 反序变换
+  // 3. 反序变换
 
-  result_array_u32 = result_array_u32.into_iter().rev().collect::<Vec<_>>().try_into().unwrap();
+  result_array_u32.reverse();
 
-  // 5. 转换数据返回
+  // 4. 转换数据返回
 
   let mut result_array_u8 = [0u8; 16];
   for i in 0 .. 4 {
@@ -181,11 +488,12 @@ fn alter_group(input: &[u8; 16], key: &[u8; 16], mode: Mode) -> [u8; 16] {
 /// ## 参数
 ///
 /// * `key` - 初始密钥
+/// * `tau` - 非线性变换，`ita` 或常数时间的 `ita_ct`
 ///
 /// ## 返回值
 ///
 /// * `[u32; 32]` - 32 个轮密钥
-fn expand_key(key: &[u8; 16]) -> [u32; 32] {
+fn expand_key(key: &[u8; 16], tau: fn(u32) -> u32) -> [u32; 32] {
   let mut mk = [0u32; 4];
   for i in 0 .. 4 {
     mk[i] = (key[i * 4] as u32) << 24
@@ -194,14 +502,14 @@ fn expand_key(key: &[u8; 16]) -> [u32; 32] {
       | (key[i * 4 + 3] as u32);
   }
 
-  let mut k = vec![0u32; 36];
+  let mut k = [0u32; 36];
 
   for i in 0 .. 4 {
     k[i] = mk[i] ^ FK[i];
   }
 
   for i in 4 .. 36 {
-    k[i] = k[i - 4] ^ t_alter_s(k[i - 3] ^ k[i - 2] ^ k[i - 1] ^ CK[i - 4]);
+    k[i] = k[i - 4] ^ t_alter_s(k[i - 3] ^ k[i - 2] ^ k[i - 1] ^ CK[i - 4], tau);
   }
 
   k[4 ..].try_into().unwrap()
@@ -212,12 +520,13 @@ fn expand_key(key: &[u8; 16]) -> [u32; 32] {
 /// ## 参数
 ///
 /// * `input` - 输入值
+/// * `tau` - 非线性变换
 ///
 /// ## 返回值
 ///
 /// * `u32` - 经过合成置换后的 32 位无符号整数
-fn t_alter(input: u32) -> u32 {
-  let b = ita(input);
+fn t_alter(input: u32, tau: fn(u32) -> u32) -> u32 {
+  let b = tau(input);
 
   b ^ b.rotate_left(2) ^ b.rotate_left(10) ^ b.rotate_left(18) ^ b.rotate_left(24)
 }
@@ -227,12 +536,13 @@ fn t_alter(input: u32) -> u32 {
 /// ## 参数
 ///
 /// * `input` - 输入值
+/// * `tau` - 非线性变换
 ///
 /// ## 返回值
 ///
 /// * `u32` - 经过合成置换后的 32 位无符号整数
-fn t_alter_s(input: u32) -> u32 {
-  let b = ita(input);
+fn t_alter_s(input: u32, tau: fn(u32) -> u32) -> u32 {
+  let b = tau(input);
 
   b ^ b.rotate_left(13) ^ b.rotate_left(23)
 }
@@ -258,13 +568,61 @@ fn ita(input: u32) -> u32 {
     | (b_bytes[3] as u32)
 }
 
+/// # SM4 常数时间合成置换非线性变换
+///
+/// 与 `ita` 结果相同，但每个字节都遍历整个 S 盒，访问模式与输入无关
+///
+/// ## 参数
+///
+/// * `input` - 输入值
+///
+/// ## 返回值
+///
+/// * `u32` - 经过 S 盒变换后的 32 位无符号整数
+fn ita_ct(input: u32) -> u32 {
+  let bytes = input.to_be_bytes();
+
+  let b_bytes = bytes.map(sbox_ct);
+
+  (b_bytes[0] as u32) << 24
+    | (b_bytes[1] as u32) << 16
+    | (b_bytes[2] as u32) << 8
+    | (b_bytes[3] as u32)
+}
+
+/// # SM4 常数时间 S 盒查找
+///
+/// ## 参数
+///
+/// * `input` - 输入字节
+///
+/// ## 返回值
+///
+/// * `u8` - S 盒替换结果
+fn sbox_ct(input: u8) -> u8 {
+  let mut result = 0u8;
+
+  for (i, row) in SBOX.iter().enumerate() {
+    for (j, item) in row.iter().enumerate() {
+      // diff 为 0 时 mask 为 0xff，否则为 0x00，不产生分支
+      let diff = ((i * 16 + j) as u32) ^ (input as u32);
+      let mask = (diff.wrapping_sub(1) >> 8) as u8;
+
+      result |= item & mask;
+    }
+  }
+
+  result
+}
+
 /// # SM4 轮函数
 ///
 /// ## 参数
 ///
 /// * `input` - 轮函数输入状态
 /// * `round_key` - 轮密钥
-fn round(input: &mut [u32; 4], round_key: u32) {
+/// * `tau` - 非线性变换
+fn round(input: &mut [u32; 4], round_key: u32, tau: fn(u32) -> u32) {
   // 1. 状态字的循环左移
 
   let temp = input[0];
@@ -274,5 +632,280 @@ fn round(input: &mut [u32; 4], round_key: u32) {
 
   // 2. 使用轮密钥和合成置换进行变换
 
-  input[3] = temp ^ t_alter(input[0] ^ input[1] ^ input[2] ^ round_key);
+  input[3] = temp ^ t_alter(input[0] ^ input[1] ^ input[2] ^ round_key, tau);
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const KEY: [u8; 16] = [
+    0x01,
+    0x23,
+    0x45,
+    0x67,
+    0x89,
+    0xab,
+    0xcd,
+    0xef,
+    0xfe,
+    0xdc,
+    0xba,
+    0x98,
+    0x76,
+    0x54,
+    0x32,
+    0x10
+  ];
+
+  #[test]
+  fn constant_time_block_matches_table_block() {
+    let key = Sm4::new(&KEY);
+    let mut block = KEY;
+
+    for _ in 0 .. 64 {
+      let encrypted = key.encrypt_block(&block);
+
+      assert_eq!(key.encrypt_block_ct(&block), encrypted);
+      assert_eq!(key.decrypt_block_ct(&encrypted), key.decrypt_block(&encrypted));
+
+      block = encrypted;
+    }
+  }
+
+  #[test]
+  fn encrypt_iterated_matches_standard_example() {
+    let expected = [
+      0x59,
+      0x52,
+      0x98,
+      0xc7,
+      0xc6,
+      0xfd,
+      0x27,
+      0x1f,
+      0x04,
+      0x02,
+      0xf8,
+      0x04,
+      0xc3,
+      0x3d,
+      0x3f,
+      0x66
+    ];
+
+    assert_eq!(encrypt_iterated(&KEY, &KEY, 1_000_000), expected);
+  }
+
+  #[test]
+  fn encrypt_matches_standard_example() {
+    let expected = [
+      0x68,
+      0x1e,
+      0xdf,
+      0x34,
+      0xd2,
+      0x06,
+      0x96,
+      0x5e,
+      0x86,
+      0xb3,
+      0xe9,
+      0x4f,
+      0x53,
+      0x6e,
+      0x42,
+      0x46
+    ];
+
+    assert_eq!(encrypt(&KEY, &KEY), expected);
+    assert_eq!(decrypt(&expected, &KEY), KEY);
+    assert_eq!(Sm4::new(&KEY).encrypt_block_ct(&KEY), expected);
+  }
+
+  #[test]
+  fn cached_key_schedule_matches_stateless_path() {
+    let key = Sm4::new(&KEY);
+    let mut block = [0u8; 16];
+
+    for i in 0 .. 1000u32 {
+      block[.. 4].copy_from_slice(&i.to_be_bytes());
+
+      let mut dec_keys = expand_key(&KEY, ita);
+      dec_keys.reverse();
+      let encrypted = crypt_group(&block, &expand_key(&KEY, ita), ita);
+
+      assert_eq!(key.encrypt_block(&block), encrypted);
+      assert_eq!(key.decrypt_block(&encrypted), crypt_group(&encrypted, &dec_keys, ita));
+      assert_eq!(key.decrypt_block(&encrypted), block);
+
+      block = encrypted;
+    }
+  }
+
+  #[test]
+  fn pkcs7_padding_round_trips() {
+    assert_eq!(pad_pkcs7(b"abc", 16), [&b"abc"[..], &[13; 13]].concat());
+    assert_eq!(pad_pkcs7(&[7; 16], 16), [[7; 16], [16; 16]].concat());
+    assert_eq!(pad_pkcs7(&[], 16), [16; 16]);
+
+    for len in [0, 1, 15, 16, 17, 32] {
+      let data: Vec<u8> = (0 .. len as u8).collect();
+
+      assert_eq!(unpad_pkcs7(&pad_pkcs7(&data, 16), 16), Ok(data));
+    }
+  }
+
+  #[test]
+  fn pkcs7_rejects_invalid_padding() {
+    assert_eq!(unpad_pkcs7(&[], 16), Err(Error::InvalidPadding));
+    assert_eq!(unpad_pkcs7(&[1, 2, 0], 16), Err(Error::InvalidPadding));
+    assert_eq!(unpad_pkcs7(&[1, 2, 3, 3], 16), Err(Error::InvalidPadding));
+    assert_eq!(unpad_pkcs7(&[5, 5], 16), Err(Error::InvalidPadding));
+    // 填充长度超过分组长度
+    assert_eq!(unpad_pkcs7(&[0x20; 32], 16), Err(Error::InvalidPadding));
+    assert_eq!(unpad_pkcs7(&[0x20; 32], 32), Ok(Vec::new()));
+  }
+
+  #[test]
+  fn ecb_round_trips_and_checks_length() {
+    for len in [0, 5, 16, 33] {
+      let data: Vec<u8> = (0 .. len as u8).collect();
+      let cipher_text = encrypt_ecb(&data, &KEY);
+
+      assert_eq!(cipher_text.len(), (len / 16 + 1) * 16);
+      assert_eq!(decrypt_ecb(&cipher_text, &KEY), Ok(data));
+    }
+
+    assert_eq!(&encrypt_ecb(&[0; 16], &KEY)[.. 16], &encrypt(&[0; 16], &KEY));
+    assert_eq!(decrypt_ecb(&[], &KEY), Err(Error::InvalidLength));
+    assert_eq!(decrypt_ecb(&[0; 15], &KEY), Err(Error::InvalidLength));
+
+    // 最后一个分组解密得到的填充字节为 0，无效
+    let bad = encrypt(&[0; 16], &KEY);
+    assert_eq!(decrypt_ecb(&bad, &KEY), Err(Error::InvalidPadding));
+  }
+
+  #[test]
+  fn cbc_round_trips() {
+    let iv = [0x5a; 16];
+
+    for blocks in [1, 2, 5] {
+      let data: Vec<u8> = (0 .. 16 * blocks as u8).collect();
+      let cipher_text = encrypt_cbc(&data, &KEY, &iv).unwrap();
+
+      assert_eq!(cipher_text.len(), data.len());
+      assert_ne!(cipher_text, data);
+      assert_eq!(decrypt_cbc(&cipher_text, &KEY, &iv), Ok(data));
+    }
+
+    // IV 全零时第一个分组与单分组加密相同
+    assert_eq!(encrypt_cbc(&KEY, &KEY, &[0; 16]).unwrap(), encrypt(&KEY, &KEY));
+  }
+
+  #[test]
+  fn cbc_known_answer() {
+    let iv = [
+      0x00,
+      0x01,
+      0x02,
+      0x03,
+      0x04,
+      0x05,
+      0x06,
+      0x07,
+      0x08,
+      0x09,
+      0x0a,
+      0x0b,
+      0x0c,
+      0x0d,
+      0x0e,
+      0x0f
+    ];
+    let plain_text =
+      [[0xaa; 4], [0xbb; 4], [0xcc; 4], [0xdd; 4], [0xee; 4], [0xff; 4], [0xaa; 4], [0xbb; 4]]
+        .concat();
+    let expected = [
+      0x78,
+      0xeb,
+      0xb1,
+      0x1c,
+      0xc4,
+      0x0b,
+      0x0a,
+      0x48,
+      0x31,
+      0x2a,
+      0xae,
+      0xb2,
+      0x04,
+      0x02,
+      0x44,
+      0xcb,
+      0x4c,
+      0xb7,
+      0x01,
+      0x69,
+      0x51,
+      0x90,
+      0x92,
+      0x26,
+      0x97,
+      0x9b,
+      0x0d,
+      0x15,
+      0xdc,
+      0x6a,
+      0x8f,
+      0x6d
+    ];
+
+    assert_eq!(encrypt_cbc(&plain_text, &KEY, &iv).unwrap(), expected);
+    assert_eq!(decrypt_cbc(&expected, &KEY, &iv).unwrap(), plain_text);
+  }
+
+  #[test]
+  fn cbc_rejects_partial_blocks() {
+    let iv = [0; 16];
+
+    for len in [1, 15, 17, 31] {
+      assert_eq!(encrypt_cbc(&[0; 31][.. len], &KEY, &iv), Err(Error::InvalidLength));
+      assert_eq!(decrypt_cbc(&[0; 31][.. len], &KEY, &iv), Err(Error::InvalidLength));
+    }
+
+    assert_eq!(encrypt_cbc(&[], &KEY, &iv), Ok(Vec::new()));
+  }
+
+  #[test]
+  fn ctr_round_trips_partial_block() {
+    let nonce = [0xf0; 16];
+    let data: Vec<u8> = (0 .. 37).collect();
+    let cipher_text = encrypt_ctr(&data, &KEY, &nonce);
+
+    assert_eq!(cipher_text.len(), 37);
+    assert_ne!(cipher_text, data);
+    assert_eq!(encrypt_ctr(&cipher_text, &KEY, &nonce), data);
+
+    let mut other_nonce = nonce;
+    other_nonce[15] ^= 1;
+    assert_ne!(encrypt_ctr(&data, &KEY, &other_nonce), cipher_text);
+
+    // 第一个分组的密钥流为 nonce 的单分组加密结果
+    let keystream = encrypt(&nonce, &KEY);
+    assert!(cipher_text[.. 16].iter().zip(&data).zip(keystream).all(|((c, d), k)| c ^ d == k));
+  }
+
+  #[test]
+  #[cfg(feature = "zeroize")]
+  fn zeroize_clears_round_keys() {
+    use zeroize::Zeroize;
+
+    let mut key = Sm4::new(&KEY);
+    assert!(key.enc_keys.iter().any(|&k| k != 0));
+    key.zeroize();
+
+    assert!(key.enc_keys.iter().all(|&k| k == 0));
+    assert!(key.dec_keys.iter().all(|&k| k == 0));
+  }
 }